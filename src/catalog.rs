@@ -0,0 +1,171 @@
+//! A small bundled catalog of well-known JSON files and their schemas, used
+//! by `jvl init` to suggest mappings for files that don't declare `$schema`.
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+/// A well-known kind of JSON file.
+#[derive(Debug)]
+pub struct CatalogEntry {
+    /// Human-readable name.
+    pub name: &'static str,
+    /// File name patterns, matched against the last path component.
+    pub file_names: &'static [&'static str],
+    /// Schema URL.
+    pub url: &'static str,
+}
+
+/// Well-known files. A file name matching several entries uses the first.
+pub const CATALOG: &[CatalogEntry] = &[
+    CatalogEntry {
+        name: "TypeScript compiler configuration",
+        file_names: &["tsconfig.json", "tsconfig.*.json"],
+        url: "https://json.schemastore.org/tsconfig.json",
+    },
+    CatalogEntry {
+        name: "JavaScript project configuration",
+        file_names: &["jsconfig.json", "jsconfig.*.json"],
+        url: "https://json.schemastore.org/jsconfig.json",
+    },
+    CatalogEntry {
+        name: "npm package manifest",
+        file_names: &["package.json"],
+        url: "https://json.schemastore.org/package.json",
+    },
+    CatalogEntry {
+        name: "ESLint configuration",
+        file_names: &[".eslintrc.json"],
+        url: "https://json.schemastore.org/eslintrc.json",
+    },
+    CatalogEntry {
+        name: "Prettier configuration",
+        file_names: &[".prettierrc.json"],
+        url: "https://json.schemastore.org/prettierrc.json",
+    },
+    CatalogEntry {
+        name: "Babel configuration",
+        file_names: &["babel.config.json", ".babelrc.json"],
+        url: "https://json.schemastore.org/babelrc.json",
+    },
+    CatalogEntry {
+        name: "Lerna configuration",
+        file_names: &["lerna.json"],
+        url: "https://json.schemastore.org/lerna.json",
+    },
+    CatalogEntry {
+        name: "Renovate configuration",
+        file_names: &["renovate.json", ".renovaterc.json"],
+        url: "https://docs.renovatebot.com/renovate-schema.json",
+    },
+    CatalogEntry {
+        name: "Turborepo configuration",
+        file_names: &["turbo.json"],
+        url: "https://turbo.build/schema.json",
+    },
+    CatalogEntry {
+        name: "Vercel configuration",
+        file_names: &["vercel.json"],
+        url: "https://openapi.vercel.sh/vercel.json",
+    },
+    CatalogEntry {
+        name: "Deno configuration",
+        file_names: &["deno.json", "deno.jsonc"],
+        url: "https://raw.githubusercontent.com/denoland/deno/main/cli/schemas/config-file.v1.json",
+    },
+    CatalogEntry {
+        name: "Dev container configuration",
+        file_names: &["devcontainer.json", ".devcontainer.json"],
+        url: "https://raw.githubusercontent.com/devcontainers/spec/main/schemas/devContainer.schema.json",
+    },
+    CatalogEntry {
+        name: "Composer package manifest",
+        file_names: &["composer.json"],
+        url: "https://getcomposer.org/schema.json",
+    },
+    CatalogEntry {
+        name: "dprint configuration",
+        file_names: &[
+            "dprint.json",
+            ".dprint.json",
+            "dprint.jsonc",
+            ".dprint.jsonc",
+        ],
+        url: "https://dprint.dev/schemas/v0.json",
+    },
+    CatalogEntry {
+        name: "markdownlint configuration",
+        file_names: &[".markdownlint.json", ".markdownlint.jsonc"],
+        url: "https://raw.githubusercontent.com/DavidAnson/markdownlint/main/schema/markdownlint-config-schema.json",
+    },
+    CatalogEntry {
+        name: ".NET SDK selection",
+        file_names: &["global.json"],
+        url: "https://json.schemastore.org/global.json",
+    },
+    CatalogEntry {
+        name: "ASP.NET Core application settings",
+        file_names: &["appsettings.json", "appsettings.*.json"],
+        url: "https://json.schemastore.org/appsettings.json",
+    },
+    CatalogEntry {
+        name: "jvl configuration",
+        file_names: &["jvl.json", "jvl.jsonc", ".jvl.json"],
+        url: "https://raw.githubusercontent.com/sargunv/jvl/main/config.schema.json",
+    },
+];
+
+/// Matches file names against the [`CATALOG`].
+pub struct Catalog {
+    set: GlobSet,
+    /// Glob index in `set` → index into [`CATALOG`].
+    entries: Vec<usize>,
+}
+
+impl Catalog {
+    pub fn new() -> Self {
+        let mut builder = GlobSetBuilder::new();
+        let mut entries = Vec::new();
+        for (i, entry) in CATALOG.iter().enumerate() {
+            for pattern in entry.file_names {
+                builder.add(Glob::new(pattern).expect("catalog patterns are valid globs"));
+                entries.push(i);
+            }
+        }
+        Self {
+            set: builder.build().expect("catalog patterns are valid globs"),
+            entries,
+        }
+    }
+
+    /// The catalog entry for a file name, if any.
+    pub fn lookup(&self, file_name: &str) -> Option<&'static CatalogEntry> {
+        self.set
+            .matches(file_name)
+            .first()
+            .map(|&i| &CATALOG[self.entries[i]])
+    }
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_matches_file_name_patterns() {
+        let catalog = Catalog::new();
+        assert_eq!(
+            catalog.lookup("tsconfig.build.json").unwrap().url,
+            "https://json.schemastore.org/tsconfig.json"
+        );
+        assert_eq!(
+            catalog.lookup("package.json").unwrap().name,
+            "npm package manifest"
+        );
+        assert!(catalog.lookup("data.json").is_none());
+    }
+}
@@ -0,0 +1,747 @@
+//! Checks that span files: references from values in one file to IDs
+//! defined in others, as declared by the config's `references` rules, and
+//! values that must be unique across files, as declared by its `unique`
+//! rules.
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use jsonschema::paths::LocationSegment;
+use serde_json::Value;
+
+use crate::diagnostic::{FileDiagnostic, RelatedInfo, Severity, SourceLocation};
+use crate::discover::{
+    self, CompiledFileFilter, CompiledParsers, Config, ConfigError, ReferenceRule, UniqueRule,
+};
+use crate::git::Tree;
+use crate::parse::{self, ParsedFile};
+
+/// A JSON pointer whose `*` segments match every property or array item.
+#[derive(Debug, Clone)]
+pub struct PointerPattern(Vec<String>);
+
+impl PointerPattern {
+    pub fn parse(pointer: &str) -> Result<Self, ConfigError> {
+        if pointer.is_empty() {
+            return Ok(Self(vec![]));
+        }
+        let Some(rest) = pointer.strip_prefix('/') else {
+            return Err(ConfigError::PointerError {
+                pointer: pointer.to_string(),
+            });
+        };
+        Ok(Self(
+            rest.split('/')
+                .map(|s| s.replace("~1", "/").replace("~0", "~"))
+                .collect(),
+        ))
+    }
+
+    /// Every value the pattern selects, with its location.
+    pub fn select<'a>(&self, value: &'a Value) -> Vec<(Vec<LocationSegment<'a>>, &'a Value)> {
+        let mut found = vec![(vec![], value)];
+        for segment in &self.0 {
+            let mut next = Vec::new();
+            for (path, value) in found {
+                let children: Vec<(LocationSegment<'a>, &'a Value)> = match value {
+                    Value::Object(map) if segment == "*" => map
+                        .iter()
+                        .map(|(k, v)| (LocationSegment::Property(Cow::Borrowed(k.as_str())), v))
+                        .collect(),
+                    Value::Object(map) => map
+                        .get_key_value(segment.as_str())
+                        .map(|(k, v)| (LocationSegment::Property(Cow::Borrowed(k.as_str())), v))
+                        .into_iter()
+                        .collect(),
+                    Value::Array(items) if segment == "*" => items
+                        .iter()
+                        .enumerate()
+                        .map(|(i, v)| (LocationSegment::Index(i), v))
+                        .collect(),
+                    Value::Array(items) => segment
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|i| Some((LocationSegment::Index(i), items.get(i)?)))
+                        .into_iter()
+                        .collect(),
+                    _ => vec![],
+                };
+                for (segment, child) in children {
+                    let mut path = path.clone();
+                    path.push(segment);
+                    next.push((path, child));
+                }
+            }
+            found = next;
+        }
+        found
+    }
+
+    /// Whether the pattern matches a concrete pointer. A `*` segment in
+    /// `pointer` (an array item at an unknown index) only matches `*`.
+    pub fn matches(&self, pointer: &[String]) -> bool {
+        self.0.len() == pointer.len()
+            && self
+                .0
+                .iter()
+                .zip(pointer)
+                .all(|(pattern, segment)| pattern == "*" || pattern == segment)
+    }
+}
+
+impl std::fmt::Display for PointerPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return f.write_str("the root");
+        }
+        for segment in &self.0 {
+            write!(f, "/{}", segment.replace('~', "~0").replace('/', "~1"))?;
+        }
+        Ok(())
+    }
+}
+
+/// All cross-file rules of a config.
+#[derive(Default)]
+pub struct CrossFileRules {
+    pub references: CompiledReferences,
+    pub unique: CompiledUniqueness,
+    /// The dialect each file is parsed as.
+    parsers: CompiledParsers,
+    /// Watcher globs for the files the index reads; see
+    /// [`watch_globs`](Self::watch_globs).
+    watch_globs: Vec<String>,
+}
+
+/// What the cross-file rules of a project need to know about its files.
+#[derive(Debug, Default)]
+pub struct CrossFileIndex {
+    pub references: ReferenceIndex,
+    pub unique: UniqueIndex,
+}
+
+impl CrossFileRules {
+    pub fn compile(config: &Config) -> Result<Self, ConfigError> {
+        Ok(Self {
+            references: CompiledReferences::compile(config)?,
+            unique: CompiledUniqueness::compile(config)?,
+            parsers: CompiledParsers::compile(config)?,
+            watch_globs: config
+                .references
+                .iter()
+                .flat_map(|rule| &rule.target.files)
+                .chain(config.unique.iter().flat_map(|rule| &rule.files))
+                .filter(|pattern| !pattern.starts_with('!'))
+                .map(|pattern| watch_glob(pattern))
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+        })
+    }
+
+    /// Globs, relative to the project root, for file watchers that see every
+    /// file the index reads anything from. Empty when there are no rules.
+    pub fn watch_globs(&self) -> &[String] {
+        &self.watch_globs
+    }
+
+    /// Parse the file at `relative` in the dialect the config gives it.
+    pub fn parse<'a>(&self, relative: &str, content: &'a str) -> Option<ParsedFile<'a>> {
+        let options = self.parsers.resolve(relative).parse_options();
+        parse::parse_with_options(content, &options).ok()
+    }
+
+    /// Whether any rule checks the file at `relative`.
+    pub fn applies_to(&self, relative: &str) -> bool {
+        self.references.applies_to(relative) || self.unique.applies_to(relative)
+    }
+
+    /// Whether the index holds anything read from the file at `relative`.
+    pub fn indexes(&self, relative: &str) -> bool {
+        self.references.indexes(relative) || self.unique.applies_to(relative)
+    }
+
+    /// Index the files under `root` that the rules read, taking them from
+    /// `tree` if given.
+    pub fn index(&self, root: &Path, tree: Option<&Tree>) -> CrossFileIndex {
+        CrossFileIndex {
+            references: self.references.index(root, tree, &self.parsers),
+            unique: self.unique.index(root, tree, &self.parsers),
+        }
+    }
+
+    /// Replace what the index holds for the file at `relative` with what
+    /// `parsed` contains, or drop it if the file is gone. Returns whether
+    /// checks of other files may have a different outcome.
+    pub fn update(
+        &self,
+        index: &mut CrossFileIndex,
+        relative: &str,
+        parsed: Option<&ParsedFile>,
+    ) -> bool {
+        let references = self
+            .references
+            .update(&mut index.references, relative, parsed);
+        let unique = self.unique.update(&mut index.unique, relative, parsed);
+        references || unique
+    }
+
+    /// Check the file at `relative` against every rule.
+    pub fn check(
+        &self,
+        index: &CrossFileIndex,
+        relative: &str,
+        parsed: &ParsedFile,
+    ) -> Vec<FileDiagnostic> {
+        let mut diagnostics = self.references.check(&index.references, relative, parsed);
+        diagnostics.extend(self.unique.check(&index.unique, relative, parsed));
+        diagnostics.sort_by_key(|d| d.span.as_ref().map(|r| r.start));
+        diagnostics
+    }
+}
+
+/// A file watcher glob matching at least every path `pattern` matches.
+///
+/// A `*` in config patterns also matches `/`, which it doesn't in watcher
+/// globs, so only the leading literal directories and the last segment are
+/// kept, with any depth between them.
+fn watch_glob(pattern: &str) -> String {
+    let is_literal = |segment: &str| !segment.contains(['*', '?', '[', '{']);
+    let (dirs, last) = pattern.rsplit_once('/').unwrap_or(("", pattern));
+    let dirs: Vec<&str> = dirs.split('/').filter(|s| !s.is_empty()).collect();
+    let literal = dirs.iter().take_while(|s| is_literal(s)).count();
+    let mut glob: String = dirs[..literal].iter().map(|s| format!("{s}/")).collect();
+    if literal == dirs.len() && is_literal(last) {
+        glob.push_str(last);
+    } else if last.contains("**") {
+        glob.push_str("**/*");
+    } else {
+        glob.push_str("**/");
+        glob.push_str(last);
+    }
+    glob
+}
+
+/// Compiled `references` rules of a config.
+#[derive(Default)]
+pub struct CompiledReferences {
+    rules: Vec<CompiledReference>,
+}
+
+struct CompiledReference {
+    files: CompiledFileFilter,
+    pointer: PointerPattern,
+    target_files: CompiledFileFilter,
+    target_pointer: PointerPattern,
+    keys: bool,
+    /// Where the IDs come from, for messages.
+    target: String,
+}
+
+impl CompiledReference {
+    fn compile(rule: &ReferenceRule) -> Result<Self, ConfigError> {
+        let target_pointer = PointerPattern::parse(&rule.target.pointer)?;
+        let what = if rule.target.keys {
+            format!("property names at {target_pointer}")
+        } else {
+            format!("values at {target_pointer}")
+        };
+        Ok(Self {
+            files: CompiledFileFilter::from_patterns(&rule.files)?,
+            pointer: PointerPattern::parse(&rule.pointer)?,
+            target_files: CompiledFileFilter::from_patterns(&rule.target.files)?,
+            target: format!("{what} in files matching {}", rule.target.files.join(", ")),
+            target_pointer,
+            keys: rule.target.keys,
+        })
+    }
+
+    /// The IDs a target file defines.
+    fn ids<'a>(&self, value: &'a Value) -> Vec<Cow<'a, Value>> {
+        let selected = self.target_pointer.select(value).into_iter();
+        if self.keys {
+            selected
+                .filter_map(|(_, v)| v.as_object())
+                .flat_map(|map| map.keys().map(|k| Cow::Owned(Value::String(k.clone()))))
+                .collect()
+        } else {
+            selected
+                .filter(|(_, v)| is_id(v))
+                .map(|(_, v)| Cow::Borrowed(v))
+                .collect()
+        }
+    }
+}
+
+/// Whether a value can be an ID or a reference to one.
+fn is_id(value: &Value) -> bool {
+    matches!(value, Value::String(_) | Value::Number(_) | Value::Bool(_))
+}
+
+/// The span and location of the value at `path`.
+fn locate(
+    parsed: &ParsedFile,
+    path: Vec<LocationSegment>,
+) -> (Option<Range<usize>>, Option<SourceLocation>) {
+    let span = parsed.resolve_pointer(path);
+    let location = span.as_ref().map(|r| {
+        let (line, column) = parsed.offset_to_line_col(r.start);
+        SourceLocation {
+            line,
+            column,
+            offset: r.start,
+            length: r.len(),
+        }
+    });
+    (span, location)
+}
+
+/// Parse each of the `(path, relative path)` pairs in `files`, read from
+/// `tree` if given and in the dialect `parsers` gives it, ignoring files that
+/// can't be read or parsed.
+fn for_each_parsed(
+    files: &[(PathBuf, String)],
+    tree: Option<&Tree>,
+    parsers: &CompiledParsers,
+    mut f: impl FnMut(&str, &ParsedFile),
+) {
+    // A tree reads every file with one git process.
+    let mut from_tree = tree.map(|tree| {
+        let paths: Vec<&Path> = files.iter().map(|(path, _)| path.as_path()).collect();
+        tree.read_all(&paths).into_iter()
+    });
+    for (path, relative) in files {
+        let content = match &mut from_tree {
+            Some(contents) => contents.next().expect("one result per file"),
+            None => std::fs::read_to_string(path),
+        };
+        let Ok(content) = content else {
+            continue;
+        };
+        let options = parsers.resolve(relative).parse_options();
+        if let Ok(parsed) = parse::parse_with_options(&content, &options) {
+            f(relative, &parsed);
+        }
+    }
+}
+
+/// An ID and the first file (relative to the project root) defining it.
+#[derive(Debug, Clone)]
+pub struct Definition {
+    pub id: Value,
+    pub file: String,
+}
+
+/// The IDs defined for each reference rule, keyed by their JSON text, with
+/// the files (relative to the project root) defining them.
+#[derive(Debug, Default)]
+pub struct ReferenceIndex {
+    ids: Vec<BTreeMap<String, BTreeMap<String, Value>>>,
+    /// The `(rule, key)` pairs each file defines, to update it.
+    files: HashMap<String, Vec<(usize, String)>>,
+}
+
+impl CompiledReferences {
+    pub fn compile(config: &Config) -> Result<Self, ConfigError> {
+        let rules = config
+            .references
+            .iter()
+            .map(CompiledReference::compile)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether any rule checks the file at `relative`.
+    pub fn applies_to(&self, relative: &str) -> bool {
+        self.rules.iter().any(|rule| rule.files.matches(relative))
+    }
+
+    /// Whether any rule reads IDs from the file at `relative`.
+    pub fn indexes(&self, relative: &str) -> bool {
+        self.rules.iter().any(|r| r.target_files.matches(relative))
+    }
+
+    /// Read the IDs defined by every rule's target files under `root`.
+    /// Files that can't be read or parsed define nothing.
+    pub fn index(
+        &self,
+        root: &Path,
+        tree: Option<&Tree>,
+        parsers: &CompiledParsers,
+    ) -> ReferenceIndex {
+        let mut index = ReferenceIndex {
+            ids: vec![BTreeMap::new(); self.rules.len()],
+            files: HashMap::new(),
+        };
+        if self.rules.is_empty() {
+            return index;
+        }
+        let files = discover::find_files(root, tree, |relative| self.indexes(relative));
+        for_each_parsed(&files, tree, parsers, |relative, parsed| {
+            self.update(&mut index, relative, Some(parsed));
+        });
+        index
+    }
+
+    /// Replace the IDs the file at `relative` defines with those in
+    /// `parsed`, or drop them if it's `None`. Returns whether the IDs changed.
+    pub fn update(
+        &self,
+        index: &mut ReferenceIndex,
+        relative: &str,
+        parsed: Option<&ParsedFile>,
+    ) -> bool {
+        let old = index.files.remove(relative).unwrap_or_default();
+        for (rule, key) in &old {
+            if let Some(files) = index.ids[*rule].get_mut(key) {
+                files.remove(relative);
+                if files.is_empty() {
+                    index.ids[*rule].remove(key);
+                }
+            }
+        }
+        let mut new = Vec::new();
+        for (i, (rule, ids)) in self.rules.iter().zip(&mut index.ids).enumerate() {
+            let Some(parsed) = parsed.filter(|_| rule.target_files.matches(relative)) else {
+                continue;
+            };
+            for id in rule.ids(&parsed.value) {
+                let key = id.to_string();
+                ids.entry(key.clone())
+                    .or_default()
+                    .insert(relative.to_string(), id.into_owned());
+                new.push((i, key));
+            }
+        }
+        let changed = old != new;
+        if !new.is_empty() {
+            index.files.insert(relative.to_string(), new);
+        }
+        changed
+    }
+
+    /// Report every reference in the file at `relative` that no target file
+    /// defines.
+    pub fn check(
+        &self,
+        index: &ReferenceIndex,
+        relative: &str,
+        parsed: &ParsedFile,
+    ) -> Vec<FileDiagnostic> {
+        let mut diagnostics = Vec::new();
+        for (rule, ids) in self.rules.iter().zip(&index.ids) {
+            if !rule.files.matches(relative) {
+                continue;
+            }
+            for (path, value) in rule.pointer.select(&parsed.value) {
+                if !is_id(value) || ids.contains_key(&value.to_string()) {
+                    continue;
+                }
+                let (span, location) = locate(parsed, path);
+                diagnostics.push(FileDiagnostic {
+                    code: "reference(missing)".into(),
+                    message: format!("unknown reference {value}"),
+                    severity: Severity::Error,
+                    span,
+                    location,
+                    label: Some("not defined by any target file".into()),
+                    help: Some(format!("Valid references are the {}.", rule.target)),
+                    schema_path: None,
+                    related: vec![],
+                });
+            }
+        }
+        diagnostics.sort_by_key(|d| d.span.as_ref().map(|r| r.start));
+        diagnostics
+    }
+
+    /// IDs that are valid at `pointer` in the file at `relative`.
+    pub fn completions(
+        &self,
+        index: &ReferenceIndex,
+        relative: &str,
+        pointer: &[String],
+    ) -> Vec<Definition> {
+        self.rules
+            .iter()
+            .zip(&index.ids)
+            .filter(|(rule, _)| rule.files.matches(relative) && rule.pointer.matches(pointer))
+            .flat_map(|(_, ids)| ids.values())
+            .filter_map(|files| {
+                let (file, id) = files.first_key_value()?;
+                Some(Definition {
+                    id: id.clone(),
+                    file: file.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Compiled `unique` rules of a config.
+#[derive(Default)]
+pub struct CompiledUniqueness {
+    rules: Vec<CompiledUnique>,
+}
+
+struct CompiledUnique {
+    files: CompiledFileFilter,
+    pointer: PointerPattern,
+    /// The files sharing the values, for messages.
+    scope: String,
+}
+
+/// Where a value occurs.
+#[derive(Debug, Clone)]
+struct Occurrence {
+    /// Path relative to the project root.
+    file: String,
+    span: Option<Range<usize>>,
+    location: Option<SourceLocation>,
+}
+
+/// Every occurrence of each value under each uniqueness rule, keyed by the
+/// value's JSON text.
+#[derive(Debug, Default)]
+pub struct UniqueIndex {
+    root: PathBuf,
+    values: Vec<HashMap<String, Vec<Occurrence>>>,
+    /// The `(rule, key)` pairs each file contributes, to update it.
+    files: HashMap<String, Vec<(usize, String)>>,
+}
+
+impl CompiledUnique {
+    fn compile(rule: &UniqueRule) -> Result<Self, ConfigError> {
+        Ok(Self {
+            files: CompiledFileFilter::from_patterns(&rule.files)?,
+            pointer: PointerPattern::parse(&rule.pointer)?,
+            scope: rule.files.join(", "),
+        })
+    }
+
+    /// The values the rule covers in one file, with their JSON text.
+    fn values<'a>(
+        &self,
+        parsed: &'a ParsedFile,
+    ) -> impl Iterator<Item = (String, &'a Value, Vec<LocationSegment<'a>>)> {
+        self.pointer
+            .select(&parsed.value)
+            .into_iter()
+            .filter(|(_, v)| is_id(v))
+            .map(|(path, v)| (v.to_string(), v, path))
+    }
+}
+
+impl CompiledUniqueness {
+    pub fn compile(config: &Config) -> Result<Self, ConfigError> {
+        let rules = config
+            .unique
+            .iter()
+            .map(CompiledUnique::compile)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether any rule covers the file at `relative`.
+    pub fn applies_to(&self, relative: &str) -> bool {
+        self.rules.iter().any(|rule| rule.files.matches(relative))
+    }
+
+    /// Read the values covered by every rule from the files under `root`.
+    /// Files that can't be read or parsed contribute nothing.
+    pub fn index(
+        &self,
+        root: &Path,
+        tree: Option<&Tree>,
+        parsers: &CompiledParsers,
+    ) -> UniqueIndex {
+        let mut index = UniqueIndex {
+            root: root.to_path_buf(),
+            values: vec![HashMap::new(); self.rules.len()],
+            files: HashMap::new(),
+        };
+        if self.rules.is_empty() {
+            return index;
+        }
+        let files = discover::find_files(root, tree, |relative| self.applies_to(relative));
+        for_each_parsed(&files, tree, parsers, |relative, parsed| {
+            self.update(&mut index, relative, Some(parsed));
+        });
+        index
+    }
+
+    /// Replace the values the file at `relative` contributes with those in
+    /// `parsed`, or drop them if it's `None`. Returns whether the values
+    /// changed; moving them within the file doesn't count.
+    pub fn update(
+        &self,
+        index: &mut UniqueIndex,
+        relative: &str,
+        parsed: Option<&ParsedFile>,
+    ) -> bool {
+        let old = index.files.remove(relative).unwrap_or_default();
+        for (rule, key) in &old {
+            if let Some(occurrences) = index.values[*rule].get_mut(key) {
+                occurrences.retain(|o| o.file != relative);
+                if occurrences.is_empty() {
+                    index.values[*rule].remove(key);
+                }
+            }
+        }
+        let mut new = Vec::new();
+        for (i, (rule, values)) in self.rules.iter().zip(&mut index.values).enumerate() {
+            let Some(parsed) = parsed.filter(|_| rule.files.matches(relative)) else {
+                continue;
+            };
+            for (key, _, path) in rule.values(parsed) {
+                let (span, location) = locate(parsed, path);
+                values.entry(key.clone()).or_default().push(Occurrence {
+                    file: relative.to_string(),
+                    span,
+                    location,
+                });
+                new.push((i, key));
+            }
+        }
+        let changed = old != new;
+        if !new.is_empty() {
+            index.files.insert(relative.to_string(), new);
+        }
+        changed
+    }
+
+    /// Report every value in the file at `relative` that occurs elsewhere in
+    /// it or in another indexed file. The file's own entries in the index
+    /// are ignored in favor of `parsed`, which may be newer.
+    pub fn check(
+        &self,
+        index: &UniqueIndex,
+        relative: &str,
+        parsed: &ParsedFile,
+    ) -> Vec<FileDiagnostic> {
+        let mut diagnostics = Vec::new();
+        for (rule, values) in self.rules.iter().zip(&index.values) {
+            if !rule.files.matches(relative) {
+                continue;
+            }
+            let own: Vec<(String, &Value, Occurrence)> = rule
+                .values(parsed)
+                .map(|(key, value, path)| {
+                    let (span, location) = locate(parsed, path);
+                    let file = relative.to_string();
+                    (
+                        key,
+                        value,
+                        Occurrence {
+                            file,
+                            span,
+                            location,
+                        },
+                    )
+                })
+                .collect();
+            for (i, (key, value, occurrence)) in own.iter().enumerate() {
+                let same_file = own
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, (other, _, _))| *j != i && other == key)
+                    .map(|(_, (_, _, o))| (None, o));
+                let other_files = values
+                    .get(key)
+                    .into_iter()
+                    .flatten()
+                    .filter(|o| o.file != relative)
+                    .map(|o| (Some(index.root.join(&o.file).display().to_string()), o));
+                let related: Vec<RelatedInfo> = same_file
+                    .chain(other_files)
+                    .map(|(path, o)| RelatedInfo {
+                        message: "also used here".into(),
+                        path,
+                        span: o.span.clone(),
+                        location: o.location.clone(),
+                    })
+                    .collect();
+                if related.is_empty() {
+                    continue;
+                }
+                let others = match related.len() {
+                    1 => "1 other place".to_string(),
+                    n => format!("{n} other places"),
+                };
+                diagnostics.push(FileDiagnostic {
+                    code: "unique(duplicate)".into(),
+                    message: format!("duplicate value {value}"),
+                    severity: Severity::Error,
+                    span: occurrence.span.clone(),
+                    location: occurrence.location.clone(),
+                    label: Some(format!("also used in {others}")),
+                    help: Some(format!(
+                        "Values at {} must be unique across files matching {}.",
+                        rule.pointer, rule.scope
+                    )),
+                    schema_path: None,
+                    related,
+                });
+            }
+        }
+        diagnostics.sort_by_key(|d| d.span.as_ref().map(|r| r.start));
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(pointer: &str) -> PointerPattern {
+        PointerPattern::parse(pointer).unwrap()
+    }
+
+    #[test]
+    fn select_expands_wildcards() {
+        let value = serde_json::json!({
+            "deps": [{ "team": "a" }, { "team": "b" }, { "other": 1 }],
+            "owners": { "x": "c", "y": "d" }
+        });
+        let selected: Vec<&Value> = pattern("/deps/*/team")
+            .select(&value)
+            .into_iter()
+            .map(|(_, v)| v)
+            .collect();
+        assert_eq!(selected, ["a", "b"]);
+        assert_eq!(pattern("/owners/*").select(&value).len(), 2);
+        assert_eq!(pattern("/deps/1/team").select(&value)[0].1, "b");
+        assert!(pattern("/missing/*").select(&value).is_empty());
+        assert!(PointerPattern::parse("team").is_err());
+    }
+
+    #[test]
+    fn watch_globs_cover_config_patterns() {
+        assert_eq!(watch_glob("teams.json"), "teams.json");
+        assert_eq!(watch_glob("data/teams.json"), "data/teams.json");
+        assert_eq!(watch_glob("*.json"), "**/*.json");
+        assert_eq!(watch_glob("data/*.json"), "data/**/*.json");
+        assert_eq!(watch_glob("data/*/x/team.json"), "data/**/team.json");
+        assert_eq!(watch_glob("data/**"), "data/**/*");
+    }
+
+    #[test]
+    fn matches_concrete_pointers() {
+        let p = pattern("/deps/*/team");
+        let pointer = |s: &[&str]| s.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert!(p.matches(&pointer(&["deps", "0", "team"])));
+        assert!(p.matches(&pointer(&["deps", "*", "team"])));
+        assert!(!p.matches(&pointer(&["deps", "0"])));
+        assert!(!pattern("/deps/0").matches(&pointer(&["deps", "*"])));
+    }
+}
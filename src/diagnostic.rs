@@ -3,13 +3,25 @@ use std::ops::Range;
 use thiserror::Error;
 
 /// The severity of a diagnostic.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum Severity {
     Error,
     Warning,
 }
 
+/// A severity configured for a diagnostic code.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum SeverityLevel {
+    Error,
+    Warning,
+    /// Drop the diagnostic.
+    Off,
+}
+
 /// Resolved source location for a diagnostic.
 #[derive(Debug, Clone)]
 pub struct SourceLocation {
@@ -30,10 +42,23 @@ pub struct FileDiagnostic {
     pub label: Option<String>,
     pub help: Option<String>,
     pub schema_path: Option<String>,
+    /// Supplementary notes, e.g. why the other `oneOf` alternatives failed.
+    pub related: Vec<RelatedInfo>,
+}
+
+/// A note attached to a diagnostic, optionally pointing at another span in
+/// the same file or in `path`.
+#[derive(Debug, Clone)]
+pub struct RelatedInfo {
+    pub message: String,
+    /// The file the note points into, when not the diagnostic's own.
+    pub path: Option<String>,
+    pub span: Option<Range<usize>>,
+    pub location: Option<SourceLocation>,
 }
 
 /// A warning not tied to a specific file.
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, schemars::JsonSchema)]
 pub struct Warning {
     pub code: String,
     pub message: String,
@@ -91,6 +116,40 @@ impl FileResult {
             tool_error: true,
         }
     }
+
+    /// Apply configured severities, keyed by diagnostic code or by code
+    /// family (the part before `(`), then recompute validity. Tool errors
+    /// stay invalid.
+    pub fn apply_severities(&mut self, levels: &std::collections::BTreeMap<String, SeverityLevel>) {
+        if levels.is_empty() {
+            return;
+        }
+        self.errors.retain_mut(|diag| {
+            let family = diag.code.split('(').next().unwrap_or_default();
+            let level = levels.get(&diag.code).or_else(|| levels.get(family));
+            match level {
+                Some(SeverityLevel::Error) => diag.severity = Severity::Error,
+                Some(SeverityLevel::Warning) => diag.severity = Severity::Warning,
+                Some(SeverityLevel::Off) => return false,
+                None => {}
+            }
+            true
+        });
+        self.valid = !self.tool_error && self.error_count() == 0;
+    }
+
+    /// Number of diagnostics with `Severity::Error`.
+    pub fn error_count(&self) -> usize {
+        self.errors
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+            .count()
+    }
+
+    /// Number of diagnostics with `Severity::Warning`.
+    pub fn warning_count(&self) -> usize {
+        self.errors.len() - self.error_count()
+    }
 }
 
 /// Miette-compatible error for rendering rich diagnostics.
@@ -102,20 +161,32 @@ pub struct RenderableDiagnostic {
     pub span: Option<SourceSpan>,
     pub label: Option<String>,
     pub help: Option<String>,
+    pub severity: Severity,
+    /// Related notes pointing elsewhere in the same source, as secondary
+    /// labels.
+    pub secondary: Vec<(String, SourceSpan)>,
 }
 
 impl Diagnostic for RenderableDiagnostic {
+    fn severity(&self) -> Option<miette::Severity> {
+        Some(match self.severity {
+            Severity::Error => miette::Severity::Error,
+            Severity::Warning => miette::Severity::Warning,
+        })
+    }
+
     fn source_code(&self) -> Option<&dyn miette::SourceCode> {
         Some(&self.src)
     }
 
     fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
         self.span.map(|span| {
-            Box::new(std::iter::once(miette::LabeledSpan::new(
-                self.label.clone(),
-                span.offset(),
-                span.len(),
-            ))) as Box<dyn Iterator<Item = miette::LabeledSpan>>
+            let primary = miette::LabeledSpan::new(self.label.clone(), span.offset(), span.len());
+            let secondary = self.secondary.iter().map(|(message, span)| {
+                miette::LabeledSpan::new(Some(message.clone()), span.offset(), span.len())
+            });
+            Box::new(std::iter::once(primary).chain(secondary))
+                as Box<dyn Iterator<Item = miette::LabeledSpan>>
         })
     }
 
@@ -178,12 +249,38 @@ impl FileDiagnostic {
             Some(r) => SourceSpan::new(r.start.into(), r.len()),
             None => SourceSpan::new(0.into(), 0),
         });
+        // Notes elsewhere in this file become secondary labels, and notes in
+        // other files or without a span go into the help text. Notes on the
+        // diagnostic's own span are left to the JSON and LSP output, so the
+        // primary label stays the only one there.
+        let mut secondary = Vec::new();
+        let mut help = self.help.clone();
+        for r in &self.related {
+            let line = match (&r.path, &r.span, &r.location) {
+                (None, Some(range), _) if Some(range) == self.span.as_ref() => continue,
+                (None, Some(range), _) => {
+                    secondary.push((r.message.clone(), range.clone().into()));
+                    continue;
+                }
+                (Some(path), _, Some(loc)) => {
+                    format!("{} (at {path}:{}:{})", r.message, loc.line, loc.column)
+                }
+                (Some(path), _, None) => format!("{} (in {path})", r.message),
+                (None, None, _) => r.message.clone(),
+            };
+            help = Some(match help {
+                Some(help) => format!("{help}\n{line}"),
+                None => line,
+            });
+        }
         RenderableDiagnostic {
             message: format!("{}: {}", self.code, self.message),
             src: miette::NamedSource::new(file_path, source.to_owned()),
             span,
             label: self.label.clone(),
-            help: self.help.clone(),
+            help,
+            severity: self.severity,
+            secondary,
         }
     }
 }
@@ -2,22 +2,69 @@ use globset::{Glob, GlobMatcher, GlobSet, GlobSetBuilder};
 use ignore::WalkBuilder;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use thiserror::Error;
 
-use crate::diagnostic::Warning;
-use crate::schema::SchemaSource;
+use crate::diagnostic::{SeverityLevel, Warning};
+use crate::formats::CustomFormat;
+use crate::git::Tree;
+use crate::keywords::ExtensionKeyword;
+use crate::lint::KeyCase;
+use crate::parse::Dialect;
+use crate::schema::{
+    CompileOptions, RewriteTarget, SchemaDraft, SchemaError, SchemaSource, UrlRewrite,
+};
 
 fn optional_string(g: &mut schemars::r#gen::SchemaGenerator) -> schemars::schema::Schema {
     g.subschema_for::<String>()
 }
 
+fn optional_bool(g: &mut schemars::r#gen::SchemaGenerator) -> schemars::schema::Schema {
+    g.subschema_for::<bool>()
+}
+
+fn optional_draft(g: &mut schemars::r#gen::SchemaGenerator) -> schemars::schema::Schema {
+    g.subschema_for::<SchemaDraft>()
+}
+
 fn uri_schema(g: &mut schemars::r#gen::SchemaGenerator) -> schemars::schema::Schema {
     let mut schema: schemars::schema::SchemaObject = g.subschema_for::<String>().into();
     schema.format = Some("uri".to_string());
     schema.into()
 }
 
+fn string_or_string_array(g: &mut schemars::r#gen::SchemaGenerator) -> schemars::schema::Schema {
+    schemars::schema::SchemaObject {
+        subschemas: Some(Box::new(schemars::schema::SubschemaValidation {
+            any_of: Some(vec![
+                g.subschema_for::<String>(),
+                g.subschema_for::<Vec<String>>(),
+            ]),
+            ..Default::default()
+        })),
+        ..Default::default()
+    }
+    .into()
+}
+
+fn one_or_many<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(s) => vec![s],
+        OneOrMany::Many(v) => v,
+    })
+}
+
 fn non_empty_string_array(g: &mut schemars::r#gen::SchemaGenerator) -> schemars::schema::Schema {
     let mut schema: schemars::schema::SchemaObject = g.subschema_for::<Vec<String>>().into();
     schema.array.get_or_insert_with(Default::default).min_items = Some(1);
@@ -43,11 +90,57 @@ pub enum ConfigError {
         pattern: String,
         source: globset::Error,
     },
+    #[error("Invalid regex for custom format '{name}': {source}")]
+    FormatRegexError { name: String, source: regex::Error },
+    #[error("Failed to fetch config from '{url}': {reason}")]
+    FetchError { url: String, reason: String },
+    #[error("Invalid 'extends' in config file '{path}': {message}")]
+    ExtendsError { path: String, message: String },
+    #[error("Config file '{path}' has no jvl settings (expected {section})")]
+    MissingSection { path: String, section: String },
+    #[error("Invalid JSON pointer '{pointer}': must be empty or start with '/'")]
+    PointerError { pointer: String },
+}
+
+/// Config file names in order of precedence within one directory.
+/// `package.json` and `Cargo.toml` only count when they embed jvl settings.
+pub const CONFIG_FILE_NAMES: [&str; 7] = [
+    "jvl.json",
+    "jvl.jsonc",
+    ".jvl.json",
+    "jvl.yaml",
+    "jvl.yml",
+    "package.json",
+    "Cargo.toml",
+];
+
+/// Where in a config file the jvl settings live.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSection {
+    /// The whole file, as in `jvl.json`.
+    File,
+    /// The `"jvl"` key of `package.json`.
+    PackageJson,
+    /// `[package.metadata.jvl]` in `Cargo.toml`.
+    CargoPackage,
+    /// `[workspace.metadata.jvl]` in `Cargo.toml`.
+    CargoWorkspace,
+}
+
+impl std::fmt::Display for ConfigSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::File => "whole file",
+            Self::PackageJson => "\"jvl\" key",
+            Self::CargoPackage => "[package.metadata.jvl]",
+            Self::CargoWorkspace => "[workspace.metadata.jvl]",
+        })
+    }
 }
 
 /// Configuration file for jvl, the JSON Schema Validator.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-#[serde(deny_unknown_fields)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
 #[schemars(rename = "jvl configuration")]
 pub struct Config {
     /// URL to the jvl config schema for self-validation.
@@ -55,6 +148,20 @@ pub struct Config {
     #[schemars(rename = "$schema", schema_with = "optional_string")]
     pub schema_url: Option<String>,
 
+    /// Configs to inherit settings from: paths relative to this file, or
+    /// HTTP/HTTPS URLs. Later entries override earlier ones, and this file
+    /// overrides them all. `files` patterns are appended to the inherited
+    /// ones; `schemas`, `urlRewrites`, and `parsers` entries are tried
+    /// before inherited ones; `keywords`, `formats`, `lint`, and `severity`
+    /// are combined; other settings replace inherited values.
+    #[serde(
+        default,
+        deserialize_with = "one_or_many",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    #[schemars(schema_with = "string_or_string_array")]
+    pub extends: Vec<String>,
+
     /// Glob patterns for file discovery. Patterns prefixed with `!` are
     /// excludes. Order matters: later patterns override earlier ones.
     #[serde(default = "default_files")]
@@ -69,6 +176,103 @@ pub struct Config {
     /// instead of being silently skipped.
     #[serde(default)]
     pub strict: bool,
+
+    /// Which files are checked when they have no schema: `schema` (the
+    /// default) skips them, `all` still parses them and reports syntax
+    /// errors.
+    #[serde(default)]
+    pub check_syntax: CheckSyntax,
+
+    /// Parse dialects by file pattern. The first rule matching a file wins;
+    /// files matching none are parsed as JSONC.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parsers: Vec<ParserRule>,
+
+    /// Lint rules by file pattern. Every rule matching a file applies; when
+    /// two set the same option, the later one wins. Duplicate keys are
+    /// always reported.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lint: Vec<LintRule>,
+
+    /// Severity overrides by diagnostic code, e.g.
+    /// `{ "lint(key-case)": "warning", "schema(deprecated)": "error" }`. A
+    /// code family such as `lint` covers every code in it; exact codes take
+    /// precedence. `off` drops the diagnostic.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub severity: BTreeMap<String, SeverityLevel>,
+
+    /// URL rewrite rules applied before fetching remote schemas. The first
+    /// rule whose `prefix` matches wins. Original URLs are still used as the
+    /// cache key and in reported output.
+    #[serde(default)]
+    pub url_rewrites: Vec<UrlRewriteRule>,
+
+    /// JSON Schema draft used for schemas that don't declare `$schema`.
+    /// Defaults to 2020-12.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "optional_draft")]
+    pub default_draft: Option<SchemaDraft>,
+
+    /// Whether `format` keywords are validated. When unset, the draft decides:
+    /// draft-04 through draft-07 validate formats, 2019-09 and later treat them
+    /// as annotations only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "optional_bool")]
+    pub validate_formats: Option<bool>,
+
+    /// When false, schemas using an unknown `format` fail to compile instead of
+    /// the format being ignored.
+    #[serde(default = "default_true")]
+    pub ignore_unknown_formats: bool,
+
+    /// Custom `format` validators, keyed by format name. Each value is a
+    /// regular expression the string must match (unanchored, like `pattern`).
+    /// Checked whenever format validation is enabled, which 2019-09 and later
+    /// drafts only do with `validateFormats: true`.
+    #[serde(default)]
+    pub formats: BTreeMap<String, String>,
+
+    /// Built-in extension keywords to enable. Violations are reported with
+    /// the keyword as the diagnostic code, e.g. `schema(x-unique-by)`.
+    #[serde(default)]
+    pub keywords: Vec<ExtensionKeyword>,
+
+    /// When true, string values whose schema declares a JSON
+    /// `contentMediaType` are decoded (honoring `contentEncoding: base64`),
+    /// parsed, and validated against `contentSchema`.
+    #[serde(default)]
+    pub validate_content: bool,
+
+    /// When true, `jvl check` remembers which files passed in `.jvl/cache`
+    /// under the project root and skips them on later runs while neither
+    /// they, their schema, nor the settings applied to them change.
+    #[serde(default)]
+    pub result_cache: bool,
+
+    /// Cross-file reference rules. Each declares that values in some files
+    /// must be defined as IDs in others. Checked after per-file validation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<ReferenceRule>,
+
+    /// Uniqueness rules. Each declares values that must not repeat across a
+    /// set of files. Checked after per-file validation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unique: Vec<UniqueRule>,
+}
+
+/// Which files [`Config::check_syntax`] applies to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckSyntax {
+    /// Only files with a schema; the rest are skipped.
+    #[default]
+    Schema,
+    /// Every file, with or without a schema.
+    All,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 fn default_files() -> Vec<String> {
@@ -104,7 +308,7 @@ pub struct SchemaMappingUrl {
 #[serde(deny_unknown_fields)]
 pub struct SchemaMappingPath {
     /// File path to the schema, resolved relative to the project root
-    /// (directory containing jvl.json).
+    /// (directory containing the config file).
     pub path: String,
 
     /// Glob patterns matched against each file's path relative to the project
@@ -113,6 +317,140 @@ pub struct SchemaMappingPath {
     pub files: Vec<String>,
 }
 
+/// A URL rewrite rule. Exactly one of `url` or `path` must be present.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum UrlRewriteRule {
+    /// Redirect matching URLs to another URL prefix (e.g. an internal mirror).
+    Url(UrlRewriteUrl),
+    /// Redirect matching URLs to files in a local directory.
+    Path(UrlRewritePath),
+}
+
+/// URL rewrite rule targeting another URL prefix.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UrlRewriteUrl {
+    /// URL prefix to match, e.g. `https://raw.githubusercontent.com/`.
+    pub prefix: String,
+
+    /// Replacement URL prefix. The rest of the original URL is appended.
+    #[schemars(schema_with = "uri_schema")]
+    pub url: String,
+}
+
+/// URL rewrite rule targeting a local directory.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UrlRewritePath {
+    /// URL prefix to match, e.g. `https://json.schemastore.org/`.
+    pub prefix: String,
+
+    /// Directory, resolved relative to the project root, that mirrors the
+    /// prefix. The rest of the original URL is used as the relative file path.
+    pub path: String,
+}
+
+/// A cross-file reference rule: every value at `pointer` in a file matching
+/// `files` must be one of the IDs its `target` defines.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReferenceRule {
+    /// Glob patterns for the files holding references, matched against each
+    /// file's path relative to the project root.
+    #[schemars(schema_with = "non_empty_string_array")]
+    pub files: Vec<String>,
+
+    /// JSON pointer to the referencing values. A `*` segment matches every
+    /// property or array item, e.g. `/dependencies/*/team`. Values that are
+    /// `null`, objects, or arrays are ignored.
+    pub pointer: String,
+
+    /// Where the valid IDs are defined.
+    pub target: ReferenceTarget,
+}
+
+/// The files and location defining the IDs for a [`ReferenceRule`].
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReferenceTarget {
+    /// Glob patterns for the defining files, relative to the project root.
+    /// They are read from disk even when not being checked.
+    #[schemars(schema_with = "non_empty_string_array")]
+    pub files: Vec<String>,
+
+    /// JSON pointer to the IDs, with `*` segments as in
+    /// [`ReferenceRule::pointer`].
+    pub pointer: String,
+
+    /// When true, the IDs are the property names of the objects at `pointer`
+    /// instead of the values there.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub keys: bool,
+}
+
+/// A uniqueness rule: no two values at `pointer`, in any of the files
+/// matching `files`, may be equal.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UniqueRule {
+    /// Glob patterns for the files sharing the values, relative to the
+    /// project root. They are read from disk even when not being checked.
+    #[schemars(schema_with = "non_empty_string_array")]
+    pub files: Vec<String>,
+
+    /// JSON pointer to the values, with `*` segments as in
+    /// [`ReferenceRule::pointer`], e.g. `/id` or `/endpoints/*/path`.
+    pub pointer: String,
+}
+
+/// A parse dialect for the files matching `files`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ParserRule {
+    /// Glob patterns matched against each file's path relative to the project
+    /// root.
+    #[schemars(schema_with = "non_empty_string_array")]
+    pub files: Vec<String>,
+
+    /// The dialect to parse the files with: `json` rejects comments and
+    /// trailing commas, `jsonc` allows them, and `json5` also allows
+    /// unquoted keys, single-quoted strings, hexadecimal numbers, and leading
+    /// `+` signs.
+    pub parser: Dialect,
+}
+
+/// Lint options for the files matching `files`. Unset options leave those
+/// of earlier matching rules in place.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct LintRule {
+    /// Glob patterns matched against each file's path relative to the project
+    /// root.
+    #[schemars(schema_with = "non_empty_string_array")]
+    pub files: Vec<String>,
+
+    /// Naming convention for object keys. Keys starting with `$` are exempt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_case: Option<KeyCase>,
+
+    /// Maximum nesting depth of objects and arrays; the root is depth 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_depth: Option<usize>,
+
+    /// Maximum file size in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_file_size: Option<usize>,
+
+    /// Report empty objects.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_empty_objects: Option<bool>,
+
+    /// Report empty arrays.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_empty_arrays: Option<bool>,
+}
+
 impl SchemaMapping {
     pub fn files(&self) -> &[String] {
         match self {
@@ -123,53 +461,507 @@ impl SchemaMapping {
 }
 
 impl Config {
-    /// Load and parse a config file.
+    /// Load and parse a config file, merging in any configs it `extends`.
     pub fn load(path: &Path) -> Result<Self, ConfigError> {
-        let content = std::fs::read_to_string(path).map_err(|e| ConfigError::ReadError {
-            path: path.display().to_string(),
-            source: e,
-        })?;
-
-        // Parse as JSONC (allow comments)
-        let ast = jsonc_parser::parse_to_ast(
-            &content,
-            &Default::default(),
-            &crate::parse::parse_options(),
-        )
-        .map_err(|e| ConfigError::JsoncParseError {
-            path: path.display().to_string(),
-            message: e.to_string(),
-        })?;
+        Self::load_with_sources(path).map(|(config, _)| config)
+    }
 
-        let value: serde_json::Value = ast
-            .value
-            .ok_or_else(|| ConfigError::JsoncParseError {
-                path: path.display().to_string(),
-                message: "Empty config file".to_string(),
-            })?
-            .into();
+    /// Like [`load`](Self::load), but also returns the local files the
+    /// config was assembled from: `path` first, then every file in its
+    /// `extends` chain.
+    pub fn load_with_sources(path: &Path) -> Result<(Self, Vec<PathBuf>), ConfigError> {
+        Self::load_from(path, None, false, false)
+    }
 
+    /// Like [`load_with_sources`](Self::load_with_sources), but reads `path`
+    /// and the local configs it extends from `tree` if given.
+    ///
+    /// Remote configs in the `extends` chain go through the schema cache like
+    /// remote schemas: `no_cache` bypasses it, and `offline` only uses cached
+    /// copies.
+    pub fn load_from(
+        path: &Path,
+        tree: Option<&Tree>,
+        no_cache: bool,
+        offline: bool,
+    ) -> Result<(Self, Vec<PathBuf>), ConfigError> {
+        let fetch = |url: &str| {
+            crate::schema::load_url_schema(url, &[], no_cache, offline, None)
+                .map(|(content, _, _)| content)
+        };
+        let mut sources = Vec::new();
+        let value = load_config_value(
+            &ConfigLocation::File(path.to_path_buf()),
+            &mut Vec::new(),
+            &mut sources,
+            tree,
+            &fetch,
+        )?;
         let config: Config =
             serde_json::from_value(value).map_err(|e| ConfigError::ParseError {
                 path: path.display().to_string(),
                 source: e,
             })?;
+        Ok((config, sources))
+    }
 
-        Ok(config)
+    /// Which part of the config file at `path` holds the jvl settings, or
+    /// `None` if it embeds none.
+    pub fn section(path: &Path) -> Result<Option<ConfigSection>, ConfigError> {
+        let display = path.display().to_string();
+        let content = std::fs::read_to_string(path).map_err(|e| ConfigError::ReadError {
+            path: display.clone(),
+            source: e,
+        })?;
+        Ok(extract_section(&file_name(path), &content, &display)?.map(|(section, _)| section))
     }
 
     /// Default config when no config file is found.
     pub fn default_config() -> Self {
         Config {
             schema_url: None,
+            extends: vec![],
             files: default_files(),
             schemas: vec![],
             strict: false,
+            check_syntax: CheckSyntax::Schema,
+            parsers: vec![],
+            lint: vec![],
+            severity: BTreeMap::new(),
+            url_rewrites: vec![],
+            default_draft: None,
+            validate_formats: None,
+            ignore_unknown_formats: true,
+            formats: BTreeMap::new(),
+            keywords: vec![],
+            validate_content: false,
+            result_cache: false,
+            references: vec![],
+            unique: vec![],
+        }
+    }
+
+    /// Build the schema compile options for this config, resolving local
+    /// paths relative to `project_root`. Fails if a custom format regex is
+    /// invalid.
+    pub fn compile_options(&self, project_root: &Path) -> Result<CompileOptions, ConfigError> {
+        let custom_formats = self
+            .formats
+            .iter()
+            .map(|(name, pattern)| {
+                CustomFormat::new(pattern)
+                    .map(|format| (name.clone(), format))
+                    .map_err(|e| ConfigError::FormatRegexError {
+                        name: name.clone(),
+                        source: e,
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+
+        let url_rewrites = self
+            .url_rewrites
+            .iter()
+            .map(|rule| match rule {
+                UrlRewriteRule::Url(r) => UrlRewrite {
+                    prefix: r.prefix.clone(),
+                    target: RewriteTarget::Url(r.url.clone()),
+                },
+                UrlRewriteRule::Path(r) => UrlRewrite {
+                    prefix: r.prefix.clone(),
+                    target: RewriteTarget::Directory(crate::schema::normalize_file_path(
+                        &project_root.join(&r.path),
+                    )),
+                },
+            })
+            .collect();
+        Ok(CompileOptions {
+            url_rewrites,
+            default_draft: self.default_draft,
+            validate_formats: self.validate_formats,
+            ignore_unknown_formats: self.ignore_unknown_formats,
+            custom_formats,
+            keywords: self.keywords.clone(),
+            validate_content: self.validate_content,
+            offline: false,
+        })
+    }
+}
+
+/// Where a config file in an `extends` chain lives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ConfigLocation {
+    File(PathBuf),
+    Url(String),
+}
+
+impl std::fmt::Display for ConfigLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Url(url) => f.write_str(url),
+        }
+    }
+}
+
+impl ConfigLocation {
+    /// The location with file paths normalized, for cycle detection.
+    fn normalized(&self) -> Self {
+        match self {
+            Self::File(path) => Self::File(crate::schema::normalize_file_path(path)),
+            Self::Url(url) => Self::Url(url.clone()),
+        }
+    }
+
+    /// Resolve an `extends` entry relative to this config.
+    fn join(&self, reference: &str) -> Result<Self, String> {
+        if reference.starts_with("http://") || reference.starts_with("https://") {
+            return Ok(Self::Url(reference.to_string()));
+        }
+        match self {
+            Self::File(path) => {
+                let dir = path.parent().unwrap_or(Path::new("."));
+                Ok(Self::File(crate::schema::normalize_file_path(
+                    &dir.join(reference),
+                )))
+            }
+            Self::Url(url) => reqwest::Url::parse(url)
+                .and_then(|base| base.join(reference))
+                .map(|u| Self::Url(u.to_string()))
+                .map_err(|e| format!("cannot resolve '{reference}' against {url}: {e}")),
+        }
+    }
+
+    /// Rewrite a `path` setting declared in this config so it no longer
+    /// depends on the config's location: absolute for files, a URL for
+    /// remote configs.
+    fn rebase(&self, path: &str) -> serde_json::Value {
+        match self {
+            Self::File(file) => {
+                let dir = file.parent().unwrap_or(Path::new("."));
+                serde_json::Value::String(dir.join(path).display().to_string())
+            }
+            Self::Url(_) => match self.join(path) {
+                Ok(Self::Url(url)) => serde_json::Value::String(url),
+                _ => serde_json::Value::String(path.to_string()),
+            },
+        }
+    }
+}
+
+/// Read a config as JSON with its `extends` chain merged in and removed.
+///
+/// `stack` holds the configs currently being loaded (for cycle detection);
+/// local files are appended to `sources`, and read from `tree` if given.
+/// Remote configs are read with `fetch`.
+fn load_config_value(
+    location: &ConfigLocation,
+    stack: &mut Vec<ConfigLocation>,
+    sources: &mut Vec<PathBuf>,
+    tree: Option<&Tree>,
+    fetch: &dyn Fn(&str) -> Result<String, SchemaError>,
+) -> Result<serde_json::Value, ConfigError> {
+    let display = location.to_string();
+    let name = match location {
+        ConfigLocation::File(path) => file_name(path),
+        ConfigLocation::Url(url) => url.rsplit('/').next().unwrap_or_default().to_string(),
+    };
+    let content = match location {
+        ConfigLocation::File(path) => {
+            sources.push(path.clone());
+            match tree {
+                Some(tree) => tree.read(path),
+                None => std::fs::read_to_string(path),
+            }
+            .map_err(|e| ConfigError::ReadError {
+                path: display.clone(),
+                source: e,
+            })?
+        }
+        ConfigLocation::Url(url) => fetch(url).map_err(|e| ConfigError::FetchError {
+            url: url.clone(),
+            reason: e.reason().to_string(),
+        })?,
+    };
+
+    let mut value = match extract_section(&name, &content, &display)? {
+        Some((_, value)) => value,
+        None => {
+            return Err(ConfigError::MissingSection {
+                path: display,
+                section: match name.as_str() {
+                    "Cargo.toml" => "[package.metadata.jvl] or [workspace.metadata.jvl]",
+                    _ => "a \"jvl\" key",
+                }
+                .to_string(),
+            });
+        }
+    };
+
+    let extends_error = |message: String| ConfigError::ExtendsError {
+        path: display.clone(),
+        message,
+    };
+    let extends = match value.as_object_mut().and_then(|obj| obj.remove("extends")) {
+        None => vec![],
+        Some(v) => one_or_many(v).map_err(|e| extends_error(e.to_string()))?,
+    };
+    if extends.is_empty() {
+        return Ok(value);
+    }
+
+    stack.push(location.normalized());
+    let mut base = serde_json::Value::Object(Default::default());
+    for reference in &extends {
+        let parent = location.join(reference).map_err(extends_error)?;
+        if stack.contains(&parent) {
+            return Err(extends_error(format!(
+                "'{reference}' extends back to {parent}"
+            )));
+        }
+        let mut parent_value = load_config_value(&parent, stack, sources, tree, fetch)?;
+        rebase_paths(&mut parent_value, &parent);
+        base = merge_config_values(base, parent_value);
+    }
+    stack.pop();
+
+    Ok(merge_config_values(base, value))
+}
+
+/// Make the `path` of every schema mapping and URL rewrite in an inherited
+/// config independent of where the config lives.
+fn rebase_paths(value: &mut serde_json::Value, location: &ConfigLocation) {
+    for key in ["schemas", "urlRewrites"] {
+        let Some(entries) = value.get_mut(key).and_then(|v| v.as_array_mut()) else {
+            continue;
+        };
+        for entry in entries {
+            let Some(obj) = entry.as_object_mut() else {
+                continue;
+            };
+            let Some(path) = obj.get("path").and_then(|p| p.as_str()) else {
+                continue;
+            };
+            let rebased = location.rebase(path);
+            // A schema file next to a remote config is fetched from the same
+            // server.
+            if key == "schemas" && matches!(location, ConfigLocation::Url(_)) {
+                obj.remove("path");
+                obj.insert("url".into(), rebased);
+            } else {
+                obj.insert("path".into(), rebased);
+            }
         }
     }
 }
 
-/// Discover the config file by walking up from the start directory.
+/// Merge config `over` on top of the inherited `base`.
+fn merge_config_values(base: serde_json::Value, over: serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+
+    // Anything but an object is left for deserialization to report.
+    let Value::Object(mut merged) = base else {
+        return over;
+    };
+    let over = match over {
+        Value::Object(over) => over,
+        other => return other,
+    };
+    // The `$schema` of an inherited config describes that file, not this one.
+    merged.remove("$schema");
+
+    for (key, value) in over {
+        let inherited = merged.remove(&key);
+        let value = match (key.as_str(), inherited, value) {
+            // Later patterns override earlier ones, so the extending config
+            // can add to or exclude from the inherited patterns.
+            ("files", Some(Value::Array(mut base)), Value::Array(over)) => {
+                base.extend(over);
+                Value::Array(base)
+            }
+            // First match wins, so the extending config's entries go first.
+            (
+                "schemas" | "urlRewrites" | "parsers",
+                Some(Value::Array(base)),
+                Value::Array(mut over),
+            ) => {
+                over.extend(base);
+                Value::Array(over)
+            }
+            ("keywords", Some(Value::Array(mut base)), Value::Array(over)) => {
+                for keyword in over {
+                    if !base.contains(&keyword) {
+                        base.push(keyword);
+                    }
+                }
+                Value::Array(base)
+            }
+            (
+                "references" | "unique" | "lint",
+                Some(Value::Array(mut base)),
+                Value::Array(over),
+            ) => {
+                base.extend(over);
+                Value::Array(base)
+            }
+            ("formats" | "severity", Some(Value::Object(mut base)), Value::Object(over)) => {
+                base.extend(over);
+                Value::Object(base)
+            }
+            (_, _, value) => value,
+        };
+        merged.insert(key, value);
+    }
+    Value::Object(merged)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Parse a JSONC document into a value.
+fn parse_jsonc(content: &str, display: &str) -> Result<serde_json::Value, ConfigError> {
+    let ast =
+        jsonc_parser::parse_to_ast(content, &Default::default(), &crate::parse::parse_options())
+            .map_err(|e| ConfigError::JsoncParseError {
+                path: display.to_string(),
+                message: e.to_string(),
+            })?;
+
+    Ok(ast
+        .value
+        .ok_or_else(|| ConfigError::JsoncParseError {
+            path: display.to_string(),
+            message: "Empty config file".to_string(),
+        })?
+        .into())
+}
+
+/// Parse a YAML document into a value.
+fn parse_yaml(content: &str, display: &str) -> Result<serde_json::Value, ConfigError> {
+    let converted = crate::yaml::to_json(content).map_err(|e| ConfigError::JsoncParseError {
+        path: display.to_string(),
+        message: e.message,
+    })?;
+    serde_json::from_str(&converted.json).map_err(|e| ConfigError::ParseError {
+        path: display.to_string(),
+        source: e,
+    })
+}
+
+/// Extract the jvl settings from the content of a config file named `name`.
+/// Returns `None` for a `package.json` or `Cargo.toml` without jvl settings.
+fn extract_section(
+    name: &str,
+    content: &str,
+    display: &str,
+) -> Result<Option<(ConfigSection, serde_json::Value)>, ConfigError> {
+    match name {
+        "package.json" => {
+            let mut value = parse_jsonc(content, display)?;
+            Ok(value
+                .as_object_mut()
+                .and_then(|obj| obj.remove("jvl"))
+                .map(|v| (ConfigSection::PackageJson, v)))
+        }
+        "Cargo.toml" => {
+            let mut table: toml::Table =
+                toml::from_str(content).map_err(|e| ConfigError::JsoncParseError {
+                    path: display.to_string(),
+                    message: e.message().to_string(),
+                })?;
+            let mut metadata = |key: &str| {
+                table
+                    .get_mut(key)?
+                    .get_mut("metadata")?
+                    .as_table_mut()?
+                    .remove("jvl")
+            };
+            let (section, value) = match metadata("package") {
+                Some(v) => (ConfigSection::CargoPackage, v),
+                None => match metadata("workspace") {
+                    Some(v) => (ConfigSection::CargoWorkspace, v),
+                    None => return Ok(None),
+                },
+            };
+            let value = serde_json::to_value(value).map_err(|e| ConfigError::ParseError {
+                path: display.to_string(),
+                source: e,
+            })?;
+            Ok(Some((section, value)))
+        }
+        _ if name.ends_with(".yaml") || name.ends_with(".yml") => {
+            Ok(Some((ConfigSection::File, parse_yaml(content, display)?)))
+        }
+        _ => Ok(Some((ConfigSection::File, parse_jsonc(content, display)?))),
+    }
+}
+
+/// Whether `path` is a config file. An unreadable `package.json` or
+/// `Cargo.toml` is not treated as one.
+fn is_config_file(path: &Path) -> bool {
+    let name = file_name(path);
+    match name.as_str() {
+        "package.json" | "Cargo.toml" => std::fs::read_to_string(path)
+            .is_ok_and(|content| embeds_settings(&name, &content, path)),
+        _ => path.is_file(),
+    }
+}
+
+/// Whether the `package.json` or `Cargo.toml` at `path` embeds jvl settings.
+/// Most don't, so only those that mention `jvl` at all are parsed.
+fn embeds_settings(name: &str, content: &str, path: &Path) -> bool {
+    content.contains("jvl")
+        && matches!(
+            extract_section(name, content, &path.display().to_string()),
+            Ok(Some(_))
+        )
+}
+
+/// The config file in `dir`, if any, by [`CONFIG_FILE_NAMES`] precedence.
+pub fn config_file_in(dir: &Path) -> Option<PathBuf> {
+    CONFIG_FILE_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| is_config_file(path))
+}
+
+/// All config files in `dir`, in order of precedence. Only the first is
+/// used; the rest are shadowed by it.
+pub fn config_files_in(dir: &Path) -> Vec<PathBuf> {
+    CONFIG_FILE_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .filter(|path| is_config_file(path))
+        .collect()
+}
+
+/// Whether `path` has one of the [`CONFIG_FILE_NAMES`].
+pub fn is_config_file_name(path: &Path) -> bool {
+    CONFIG_FILE_NAMES.contains(&file_name(path).as_str())
+}
+
+/// The config file in `dir` of `tree`, if any, by [`CONFIG_FILE_NAMES`]
+/// precedence.
+pub fn tree_config_file_in(tree: &Tree, dir: &Path) -> Option<PathBuf> {
+    CONFIG_FILE_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| {
+            let name = file_name(path);
+            match name.as_str() {
+                "package.json" | "Cargo.toml" => tree
+                    .read(path)
+                    .is_ok_and(|content| embeds_settings(&name, &content, path)),
+                _ => tree.contains(path),
+            }
+        })
+}
+
+/// Discover the config file by walking up from the start directory. The
+/// nearest directory with a config file wins; within a directory,
+/// [`CONFIG_FILE_NAMES`] gives the precedence.
 pub fn find_config_file(start: &Path) -> Option<PathBuf> {
     let mut dir = if start.is_file() {
         start.parent()?
@@ -178,9 +970,8 @@ pub fn find_config_file(start: &Path) -> Option<PathBuf> {
     };
 
     loop {
-        let candidate = dir.join("jvl.json");
-        if candidate.exists() {
-            return Some(candidate);
+        if let Some(config) = config_file_in(dir) {
+            return Some(config);
         }
         match dir.parent() {
             Some(parent) => dir = parent,
@@ -189,25 +980,87 @@ pub fn find_config_file(start: &Path) -> Option<PathBuf> {
     }
 }
 
+/// Like [`find_config_file`], for the files of a git commit. The walk stops
+/// at the top of the repository.
+pub fn find_tree_config_file(tree: &Tree, start: &Path) -> Option<PathBuf> {
+    let mut dir = if tree.contains(start) {
+        start.parent()?
+    } else {
+        start
+    };
+
+    while dir.starts_with(tree.root()) {
+        if let Some(config) = tree_config_file_in(tree, dir) {
+            return Some(config);
+        }
+        dir = dir.parent()?;
+    }
+    None
+}
+
+/// Files found by [`discover_files`].
+pub struct Discovery {
+    /// Files matching the config's `files` patterns.
+    pub files: Vec<PathBuf>,
+    /// Config files in directories below the project root. Their subtrees
+    /// are governed by that config and are not walked.
+    pub nested_configs: Vec<PathBuf>,
+    /// Errors encountered while walking.
+    pub warnings: Vec<Warning>,
+}
+
 /// Discover files using config patterns, respecting .gitignore.
 ///
-/// Returns `(files, warnings)` where warnings include any walk errors encountered.
+/// Directories below `project_root` that contain their own config file are
+/// skipped and reported in [`Discovery::nested_configs`] instead.
 pub fn discover_files(
     project_root: &Path,
     walk_roots: &[PathBuf],
     config: &Config,
-) -> Result<(Vec<PathBuf>, Vec<Warning>), ConfigError> {
+) -> Result<Discovery, ConfigError> {
     let file_filter = CompiledFileFilter::compile(config)?;
 
     let mut files = Vec::new();
     let mut warnings = Vec::new();
+    let nested_configs = Arc::new(Mutex::new(Vec::new()));
+    // The config file of each directory checked so far, so overlapping walk
+    // roots don't read the same `package.json` or `Cargo.toml` twice.
+    let config_files: Arc<Mutex<HashMap<PathBuf, Option<PathBuf>>>> = Arc::default();
 
     for walk_root in walk_roots {
+        let root = project_root.to_path_buf();
+        let nested = Arc::clone(&nested_configs);
+        let config_files = Arc::clone(&config_files);
         let walker = WalkBuilder::new(walk_root)
             .hidden(false)
             .git_ignore(true)
             .git_global(true)
             .git_exclude(true)
+            .filter_entry(move |entry| {
+                let path = entry.path();
+                if !entry.file_type().is_some_and(|ft| ft.is_dir()) || path == root {
+                    return true;
+                }
+                if !path.starts_with(&root) {
+                    return true;
+                }
+                let config = config_files
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .entry(path.to_path_buf())
+                    .or_insert_with(|| config_file_in(path))
+                    .clone();
+                match config {
+                    Some(config) => {
+                        nested
+                            .lock()
+                            .unwrap_or_else(|e| e.into_inner())
+                            .push(config);
+                        false
+                    }
+                    None => true,
+                }
+            })
             .build();
 
         for entry in walker {
@@ -240,10 +1093,112 @@ pub fn discover_files(
         }
     }
 
-    Ok((files, warnings))
+    let mut nested_configs =
+        std::mem::take(&mut *nested_configs.lock().unwrap_or_else(|e| e.into_inner()));
+    nested_configs.sort();
+    nested_configs.dedup();
+
+    Ok(Discovery {
+        files,
+        nested_configs,
+        warnings,
+    })
+}
+
+/// Like [`discover_files`], for the files of a git commit: those in `tree`
+/// under `walk_roots` that match the config's `files`. Every file in the
+/// tree is tracked, so nothing is ignored.
+pub fn discover_tree_files(
+    tree: &Tree,
+    project_root: &Path,
+    walk_roots: &[PathBuf],
+    config: &Config,
+) -> Result<Discovery, ConfigError> {
+    let file_filter = CompiledFileFilter::compile(config)?;
+
+    let mut nested_configs: Vec<PathBuf> = tree
+        .files_under(project_root)
+        .filter(|path| is_config_file_name(path))
+        .filter_map(|path| {
+            let dir = path.parent()?;
+            if dir == project_root {
+                return None;
+            }
+            tree_config_file_in(tree, dir)
+        })
+        .collect();
+    nested_configs.sort();
+    nested_configs.dedup();
+    // Configs further down belong to the nested projects, as when walking.
+    let dirs: Vec<PathBuf> = nested_configs
+        .iter()
+        .filter_map(|config| Some(config.parent()?.to_path_buf()))
+        .collect();
+    let under_nested = |path: &Path| dirs.iter().any(|dir| path != dir && path.starts_with(dir));
+    nested_configs.retain(|config| !config.parent().is_some_and(under_nested));
+
+    let mut files = Vec::new();
+    for walk_root in walk_roots {
+        for path in tree.files_under(walk_root) {
+            let Ok(relative) = path.strip_prefix(project_root) else {
+                continue;
+            };
+            if !under_nested(&path) && file_filter.matches(&relative.to_string_lossy()) {
+                files.push(path);
+            }
+        }
+    }
+
+    Ok(Discovery {
+        files,
+        nested_configs,
+        warnings: vec![],
+    })
+}
+
+/// Find every file under `root` whose path relative to it satisfies
+/// `matches`, respecting .gitignore, or among the files of `tree` if given.
+/// Unlike [`discover_files`], directories with their own config are walked
+/// too. Returns `(path, relative path)` pairs sorted by path.
+pub fn find_files(
+    root: &Path,
+    tree: Option<&Tree>,
+    matches: impl Fn(&str) -> bool,
+) -> Vec<(PathBuf, String)> {
+    if let Some(tree) = tree {
+        return tree
+            .files_under(root)
+            .filter_map(|path| {
+                let relative = path
+                    .strip_prefix(root)
+                    .ok()?
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                matches(&relative).then_some((path, relative))
+            })
+            .collect();
+    }
+    let mut files: Vec<(PathBuf, String)> = WalkBuilder::new(root)
+        .hidden(false)
+        .build()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_some_and(|ft| ft.is_file()))
+        .filter_map(|entry| {
+            let relative = entry
+                .path()
+                .strip_prefix(root)
+                .ok()?
+                .to_string_lossy()
+                .replace('\\', "/");
+            matches(&relative).then(|| (entry.path().to_path_buf(), relative))
+        })
+        .collect();
+    files.sort();
+    files
 }
 
 /// Pre-compiled schema mappings for efficient per-file resolution.
+#[derive(Default)]
 pub struct CompiledSchemaMappings {
     entries: Vec<CompiledSchemaEntry>,
 }
@@ -256,8 +1211,13 @@ struct CompiledSchemaEntry {
 impl CompiledSchemaMappings {
     /// Pre-compile all schema mapping glob patterns from a config.
     pub fn compile(config: &Config) -> Result<Self, ConfigError> {
-        let entries = config
-            .schemas
+        Self::from_mappings(&config.schemas)
+    }
+
+    /// Pre-compile schema mappings that don't come from a config file, e.g.
+    /// ones supplied by an editor.
+    pub fn from_mappings(mappings: &[SchemaMapping]) -> Result<Self, ConfigError> {
+        let entries = mappings
             .iter()
             .map(|mapping| {
                 let globset = build_globset(mapping.files())?;
@@ -272,19 +1232,64 @@ impl CompiledSchemaMappings {
 
     /// Resolve a schema for a file based on pre-compiled mappings.
     pub fn resolve(&self, file_relative: &str, project_root: &Path) -> Option<SchemaSource> {
-        for entry in &self.entries {
-            if entry.globset.is_match(file_relative) {
-                return Some(match &entry.mapping {
+        self.resolve_match(file_relative, project_root)
+            .map(|m| m.source)
+    }
+
+    /// Like [`resolve`](Self::resolve), but also reports which mapping and
+    /// which of its patterns matched.
+    pub fn resolve_match(&self, file_relative: &str, project_root: &Path) -> Option<MappingMatch> {
+        self.entries.iter().enumerate().find_map(|(index, entry)| {
+            let pattern = *entry.globset.matches(file_relative).first()?;
+            Some(MappingMatch {
+                index,
+                pattern: entry.mapping.files()[pattern].clone(),
+                source: match &entry.mapping {
                     SchemaMapping::Url(m) => SchemaSource::Url(m.url.clone()),
                     SchemaMapping::Path(m) => SchemaSource::file(project_root.join(&m.path)),
-                });
-            }
-        }
-        None
+                },
+            })
+        })
+    }
+}
+
+/// Pre-compiled `parsers` rules for resolving each file's dialect.
+#[derive(Default)]
+pub struct CompiledParsers {
+    entries: Vec<(GlobSet, Dialect)>,
+}
+
+impl CompiledParsers {
+    /// Pre-compile the `parsers` glob patterns of a config.
+    pub fn compile(config: &Config) -> Result<Self, ConfigError> {
+        let entries = config
+            .parsers
+            .iter()
+            .map(|rule| Ok((build_globset(&rule.files)?, rule.parser)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { entries })
+    }
+
+    /// The dialect of the first rule matching a file, or JSONC.
+    pub fn resolve(&self, file_relative: &str) -> Dialect {
+        self.entries
+            .iter()
+            .find(|(globset, _)| globset.is_match(file_relative))
+            .map_or(Dialect::Jsonc, |(_, dialect)| *dialect)
     }
 }
 
-fn build_globset(patterns: &[String]) -> Result<GlobSet, ConfigError> {
+/// The schema mapping that matched a file.
+#[derive(Debug, Clone)]
+pub struct MappingMatch {
+    /// Index of the mapping in the config's `schemas`.
+    pub index: usize,
+    /// The mapping's pattern that matched.
+    pub pattern: String,
+    pub source: SchemaSource,
+}
+
+pub(crate) fn build_globset(patterns: &[String]) -> Result<GlobSet, ConfigError> {
     let mut builder = GlobSetBuilder::new();
     for pattern in patterns {
         let glob = Glob::new(pattern).map_err(|e| ConfigError::GlobError {
@@ -349,7 +1354,12 @@ pub struct CompiledFileFilter {
 impl CompiledFileFilter {
     /// Compile the `files` patterns from a config.
     pub fn compile(config: &Config) -> Result<Self, ConfigError> {
-        let patterns = build_ordered_patterns(&config.files)?;
+        Self::from_patterns(&config.files)
+    }
+
+    /// Compile an ordered list of include and `!`-prefixed exclude patterns.
+    pub fn from_patterns(patterns: &[String]) -> Result<Self, ConfigError> {
+        let patterns = build_ordered_patterns(patterns)?;
         Ok(Self { patterns })
     }
 
@@ -0,0 +1,419 @@
+//! JSON regions embedded in other files: fenced code blocks and front matter
+//! in Markdown.
+//!
+//! Regions are made of byte ranges into the host file, so diagnostics for a
+//! region can be mapped back to the host by offsetting their spans.
+
+use std::borrow::Cow;
+use std::ops::Range;
+use std::path::Path;
+
+/// File extensions treated as Markdown hosts.
+const MARKDOWN_EXTENSIONS: &[&str] = &["md", "markdown"];
+
+/// Code block languages treated as JSON.
+const JSON_LANGUAGES: &[&str] = &["json", "jsonc"];
+
+/// Where an embedded region came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionKind {
+    /// JSON front matter: a `---json` block or a leading `{ ... }` object.
+    FrontMatter,
+    /// YAML front matter: a block between `---` lines.
+    YamlFrontMatter,
+    /// A fenced code block with a `json` or `jsonc` info string.
+    CodeBlock,
+}
+
+/// A JSON region inside a host file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub kind: RegionKind,
+    /// Byte ranges of the region's text in the host file. A code block in a
+    /// block quote or list item has one per line, leaving out the `>` markers
+    /// and indentation that aren't part of its text.
+    pub segments: Vec<Range<usize>>,
+    /// Byte range of the line opening the region (the fence or `---json`
+    /// line), used for diagnostics without a span of their own.
+    pub opener: Range<usize>,
+    /// The `schema=` attribute of a code block's info string.
+    pub schema: Option<String>,
+}
+
+impl Region {
+    /// The region's text.
+    pub fn text<'a>(&self, source: &'a str) -> Cow<'a, str> {
+        match self.segments.as_slice() {
+            [segment] => Cow::Borrowed(&source[segment.clone()]),
+            segments => Cow::Owned(segments.iter().map(|s| &source[s.clone()]).collect()),
+        }
+    }
+
+    /// Translate a span of the region's text to a span of the host file.
+    pub fn host_span(&self, span: &Range<usize>) -> Range<usize> {
+        let start = self.host_offset(span.start, false);
+        start..self.host_offset(span.end, true).max(start)
+    }
+
+    /// Translate an offset into the region's text. An offset between two
+    /// segments is the end of the first when `at_end`, else the start of the
+    /// second.
+    fn host_offset(&self, offset: usize, at_end: bool) -> usize {
+        let mut base = 0;
+        for segment in &self.segments {
+            let end = base + segment.len();
+            if offset < end || (at_end && offset == end) {
+                return segment.start + offset.saturating_sub(base);
+            }
+            base = end;
+        }
+        self.segments.last().map_or(0, |s| s.end)
+    }
+}
+
+/// Whether `path` is a file whose JSON is embedded rather than the whole file.
+pub fn is_host_file(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()).is_some_and(|e| {
+        MARKDOWN_EXTENSIONS
+            .iter()
+            .any(|m| e.eq_ignore_ascii_case(m))
+    })
+}
+
+/// Find the front matter and JSON code blocks in a Markdown source.
+pub fn extract_regions(source: &str) -> Vec<Region> {
+    let mut regions = Vec::new();
+    let mut body_start = 0;
+    if let Some(front_matter) = front_matter(source) {
+        body_start = front_matter.end;
+        regions.push(front_matter.region);
+    }
+    regions.extend(code_blocks(source, body_start));
+    regions
+}
+
+struct FrontMatter {
+    region: Region,
+    /// Offset just past the front matter, where the document body starts.
+    end: usize,
+}
+
+/// Lines of `source` from `start`, with the byte offset of each line start.
+/// Line text excludes the line terminator.
+fn lines_from(source: &str, start: usize) -> impl Iterator<Item = (usize, &str)> {
+    let mut offset = start;
+    source[start..].split_inclusive('\n').map(move |line| {
+        let line_start = offset;
+        offset += line.len();
+        (line_start, line.trim_end_matches(['\n', '\r']))
+    })
+}
+
+fn front_matter(source: &str) -> Option<FrontMatter> {
+    if source.starts_with('{') {
+        // Hugo-style: the document starts with a JSON object.
+        let end = matching_brace(source)?;
+        let text = 0..end;
+        return Some(FrontMatter {
+            region: Region {
+                kind: RegionKind::FrontMatter,
+                segments: vec![text],
+                opener: 0..1,
+                schema: None,
+            },
+            end,
+        });
+    }
+
+    let mut lines = lines_from(source, 0);
+    let (_, first) = lines.next()?;
+    let (kind, closers): (_, &[&str]) = match first.trim_end() {
+        "---json" => (RegionKind::FrontMatter, &["---"]),
+        // YAML may also end its document with `...`.
+        "---" => (RegionKind::YamlFrontMatter, &["---", "..."]),
+        _ => return None,
+    };
+    let content_start = source.find('\n')? + 1;
+    let (close_start, close) = lines.find(|(_, line)| closers.contains(&line.trim_end()))?;
+    let text = content_start..close_start;
+    Some(FrontMatter {
+        region: Region {
+            kind,
+            segments: vec![text],
+            opener: 0..first.len(),
+            schema: None,
+        },
+        end: close_start + close.len(),
+    })
+}
+
+/// Offset just past the `}` closing the object that starts `source`, skipping
+/// braces inside strings.
+fn matching_brace(source: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, b) in source.bytes().enumerate() {
+        if in_string {
+            match b {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match b {
+            b'"' => in_string = true,
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// An open code fence: its marker byte, marker length, and JSON metadata.
+struct OpenFence {
+    marker: u8,
+    len: usize,
+    /// Block quote depth of the fence.
+    quotes: usize,
+    /// Columns of indentation removed from content lines: the fence's own,
+    /// including the list item markers before it.
+    indent: usize,
+    segments: Vec<Range<usize>>,
+    opener: Range<usize>,
+    /// `Some` for a JSON code block, holding its `schema=` attribute.
+    json: Option<Option<String>>,
+}
+
+impl OpenFence {
+    fn push(&mut self, range: Range<usize>) {
+        match self.segments.last_mut() {
+            Some(last) if last.end == range.start => last.end = range.end,
+            Some(last) if last.start == last.end => *last = range,
+            _ => self.segments.push(range),
+        }
+    }
+}
+
+fn code_blocks(source: &str, start: usize) -> Vec<Region> {
+    let mut regions = Vec::new();
+    let mut close = |fence: OpenFence| {
+        if let Some(schema) = fence.json {
+            regions.push(Region {
+                kind: RegionKind::CodeBlock,
+                segments: fence.segments,
+                opener: fence.opener,
+                schema,
+            });
+        }
+    };
+
+    let mut open: Option<OpenFence> = None;
+    for (line_start, line) in lines_from(source, start) {
+        let line_end = next_line(source, line_start + line.len());
+        if let Some(mut fence) = open.take() {
+            // A fence in a block quote ends with the quote.
+            if let Some(quoted) = strip_quotes(line, fence.quotes) {
+                let rest = &line[quoted..];
+                let trimmed = rest.trim_start();
+                let len = trimmed.bytes().take_while(|&b| b == fence.marker).count();
+                if len >= fence.len && trimmed[len..].trim().is_empty() {
+                    close(fence);
+                    continue;
+                }
+                let indent = rest
+                    .bytes()
+                    .take(fence.indent)
+                    .take_while(|&b| b == b' ')
+                    .count();
+                fence.push(line_start + quoted + indent..line_end);
+                open = Some(fence);
+                continue;
+            }
+            close(fence);
+        }
+        open = open_fence(line, line_start, line_end);
+    }
+    // An unclosed fence runs to the end of the document.
+    if let Some(fence) = open {
+        close(fence);
+    }
+    regions
+}
+
+/// The fence opened by `line`, if any. Block quote and list item markers may
+/// come before it, as in `> - ```json`.
+fn open_fence(line: &str, line_start: usize, line_end: usize) -> Option<OpenFence> {
+    let mut quotes = 0;
+    let mut quoted = 0;
+    while let Some(offset) = strip_quotes(&line[quoted..], 1) {
+        quotes += 1;
+        quoted += offset;
+    }
+    let mut offset = quoted;
+    while let Some(width) = list_marker(&line[offset..]) {
+        offset += width;
+    }
+    let trimmed = line[offset..].trim_start();
+    let fence_start = line.len() - trimmed.len();
+    let marker = *trimmed.as_bytes().first()?;
+    let len = trimmed.bytes().take_while(|&b| b == marker).count();
+    if !matches!(marker, b'`' | b'~') || len < 3 {
+        return None;
+    }
+    // Content starts on the next line, empty until a line is added.
+    let content = line_end..line_end;
+    Some(OpenFence {
+        marker,
+        len,
+        quotes,
+        indent: fence_start - quoted,
+        segments: vec![content],
+        opener: line_start + fence_start..line_start + line.len(),
+        json: json_info(&trimmed[len..]),
+    })
+}
+
+/// Offset of the text after `depth` block quote markers (`>`, each with up
+/// to three spaces before it and an optional space after it), or `None` if
+/// `line` is quoted less deeply.
+fn strip_quotes(line: &str, depth: usize) -> Option<usize> {
+    let mut offset = 0;
+    for _ in 0..depth {
+        let rest = &line[offset..];
+        let spaces = rest.bytes().take(3).take_while(|&b| b == b' ').count();
+        if rest.as_bytes().get(spaces) != Some(&b'>') {
+            return None;
+        }
+        offset += spaces + 1;
+        if matches!(line.as_bytes().get(offset), Some(b' ' | b'\t')) {
+            offset += 1;
+        }
+    }
+    Some(offset)
+}
+
+/// Width of the list item marker starting `text` (`-`, `*`, `+`, `1.` or
+/// `1)`, with its indentation and the spaces after it), if there is one.
+fn list_marker(text: &str) -> Option<usize> {
+    let indent = text.bytes().take_while(|&b| b == b' ').count();
+    let rest = &text.as_bytes()[indent..];
+    let digits = rest
+        .iter()
+        .take(9)
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+    let marker = match rest.get(digits)? {
+        b'-' | b'*' | b'+' if digits == 0 => 1,
+        b'.' | b')' if digits > 0 => digits + 1,
+        _ => return None,
+    };
+    let spaces = rest[marker..].iter().take_while(|&&b| b == b' ').count();
+    (spaces > 0).then_some(indent + marker + spaces)
+}
+
+/// Offset of the line after the one ending at `line_end`.
+fn next_line(source: &str, line_end: usize) -> usize {
+    source[line_end..]
+        .find('\n')
+        .map_or(source.len(), |i| line_end + i + 1)
+}
+
+/// Parse a code block info string such as `json schema=./x.json`. Returns
+/// `None` unless the language is JSON, else the `schema=` attribute.
+fn json_info(info: &str) -> Option<Option<String>> {
+    let mut words = info.split_whitespace();
+    let language = words.next()?;
+    if !JSON_LANGUAGES
+        .iter()
+        .any(|l| language.eq_ignore_ascii_case(l))
+    {
+        return None;
+    }
+    let schema = words
+        .filter_map(|word| word.strip_prefix("schema="))
+        .map(|value| value.trim_matches(['"', '\'']).to_string())
+        .next();
+    Some(schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts<'a>(source: &'a str, regions: &[Region]) -> Vec<Cow<'a, str>> {
+        regions.iter().map(|r| r.text(source)).collect()
+    }
+
+    #[test]
+    fn finds_json_code_blocks_with_schema_attribute() {
+        let source = "# Title\n\n```json schema=./a.json\n{ \"a\": 1 }\n```\n\n\
+                      ```sh\necho {}\n```\n\n  ~~~~jsonc\n[1]\n~~~\n~~~~\n";
+        let regions = extract_regions(source);
+        assert_eq!(texts(source, &regions), ["{ \"a\": 1 }\n", "[1]\n~~~\n"]);
+        assert_eq!(regions[0].schema.as_deref(), Some("./a.json"));
+        assert_eq!(
+            &source[regions[0].opener.clone()],
+            "```json schema=./a.json"
+        );
+        assert_eq!(regions[1].schema, None);
+        assert_eq!(&source[regions[1].opener.clone()], "~~~~jsonc");
+    }
+
+    #[test]
+    fn finds_front_matter() {
+        let source = "---json\n{ \"title\": \"x\" }\n---\n```json\n{}\n```\n";
+        let regions = extract_regions(source);
+        assert_eq!(regions[0].kind, RegionKind::FrontMatter);
+        assert_eq!(texts(source, &regions), ["{ \"title\": \"x\" }\n", "{}\n"]);
+
+        let source = "{ \"title\": \"}\" }\n\nBody with { braces }.\n";
+        let regions = extract_regions(source);
+        assert_eq!(texts(source, &regions), ["{ \"title\": \"}\" }"]);
+
+        let source = "---\ntitle: x\n...\n```json\n{}\n```\n";
+        let regions = extract_regions(source);
+        assert_eq!(regions[0].kind, RegionKind::YamlFrontMatter);
+        assert_eq!(texts(source, &regions), ["title: x\n", "{}\n"]);
+
+        // A thematic break isn't front matter unless it's closed.
+        let source = "---\n\n```json\n{}\n```\n";
+        assert_eq!(texts(source, &extract_regions(source)), ["{}\n"]);
+    }
+
+    #[test]
+    fn finds_code_blocks_in_list_items_and_block_quotes() {
+        let source = "- ```json\n  { \"a\": 1 }\n  ```\n\n1. Step:\n\n   ```json\n   [\n     1\n   ]\n   ```\n\n\
+                      > ```json\n> {\n>   \"b\": 2\n> }\n> ```\n\n> - ```json\n>   {}\n\nafter\n";
+        let regions = extract_regions(source);
+        assert_eq!(
+            texts(source, &regions),
+            [
+                "{ \"a\": 1 }\n",
+                "[\n  1\n]\n",
+                "{\n  \"b\": 2\n}\n",
+                "{}\n"
+            ]
+        );
+        assert_eq!(&source[regions[0].opener.clone()], "```json");
+
+        // Spans of the text map back past the `> ` markers.
+        let quoted = &regions[2];
+        let b = quoted.text(source).find("\"b\"").unwrap();
+        assert_eq!(&source[quoted.host_span(&(b..b + 3))], "\"b\"");
+        assert_eq!(
+            &source[quoted.host_span(&(0..quoted.text(source).len()))]
+                .lines()
+                .count(),
+            &3
+        );
+    }
+}
@@ -0,0 +1,456 @@
+//! Tracing for `jvl explain`: how a file's schema was chosen, and which
+//! subschemas apply at a location in the file.
+
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::diagnostic::FileDiagnostic;
+use crate::schema::{self, CacheOutcome, CompileOptions, SchemaAnnotation, SchemaSource};
+
+/// Maximum number of `$ref`s followed from one subschema.
+const MAX_REFS: usize = 32;
+
+/// Which resolution step chose the schema.
+#[derive(Debug, Clone)]
+pub enum SchemaOrigin {
+    /// The `--schema` flag.
+    Flag,
+    /// The file's own `$schema` field.
+    SchemaField,
+    /// A mapping in a config file.
+    Mapping {
+        config: Option<PathBuf>,
+        /// Index of the mapping in the resolved config's `schemas`.
+        index: usize,
+        pattern: String,
+    },
+}
+
+impl fmt::Display for SchemaOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Flag => f.write_str("--schema flag"),
+            Self::SchemaField => f.write_str("$schema field"),
+            Self::Mapping {
+                config,
+                index,
+                pattern,
+            } => {
+                write!(f, "schemas[{index}] matching \"{pattern}\"")?;
+                if let Some(config) = config {
+                    write!(f, " in {}", config.display())?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// How the schema was loaded.
+#[derive(Debug, Clone, Copy)]
+pub enum CacheStatus {
+    /// A local schema file, which is never cached on disk.
+    LocalFile,
+    /// The disk-cache outcome for a URL schema.
+    Url(CacheOutcome),
+}
+
+impl fmt::Display for CacheStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LocalFile => f.write_str("local file, not cached"),
+            Self::Url(CacheOutcome::Hit) => f.write_str("hit"),
+            Self::Url(CacheOutcome::Miss) => f.write_str("miss, fetched from the network"),
+            Self::Url(CacheOutcome::Stale) => f.write_str("stale entry, re-fetch attempted"),
+            Self::Url(CacheOutcome::Bypassed) => f.write_str("bypassed (--no-cache)"),
+        }
+    }
+}
+
+/// A composition branch (`allOf`/`oneOf`/`anyOf`) of a subschema.
+#[derive(Debug, Clone)]
+pub struct Branch {
+    pub keyword: &'static str,
+    pub index: usize,
+    /// Location of the branch in the schema document, with `$ref`s followed.
+    pub location: Option<String>,
+    pub title: Option<String>,
+    /// Whether the value at the location is valid against the branch; `None`
+    /// if there is no value there or the branch can't be compiled.
+    pub valid: Option<bool>,
+}
+
+/// One subschema in the chain leading to a location.
+#[derive(Debug, Clone)]
+pub struct Step {
+    /// Location in the file as a JSON pointer; empty for the root.
+    pub pointer: String,
+    /// `$ref`s followed to reach the subschema.
+    pub refs: Vec<String>,
+    /// Location of the subschema in the schema document.
+    pub location: Option<String>,
+    pub annotation: Option<SchemaAnnotation>,
+    pub branches: Vec<Branch>,
+}
+
+/// The subschemas that apply at each level of a JSON pointer.
+#[derive(Debug, Clone, Default)]
+pub struct Chain {
+    pub steps: Vec<Step>,
+    /// The first location no subschema was found for, if any.
+    pub unresolved: Option<String>,
+}
+
+/// Parse a JSON pointer given on the command line: `/a/b`, `#/a/b`, or empty
+/// for the root.
+pub fn parse_pointer(pointer: &str) -> Result<Vec<String>, String> {
+    let pointer = pointer.strip_prefix('#').unwrap_or(pointer);
+    if pointer.is_empty() {
+        return Ok(vec![]);
+    }
+    let Some(rest) = pointer.strip_prefix('/') else {
+        return Err(format!("JSON pointer must start with '/': {pointer}"));
+    };
+    Ok(rest
+        .split('/')
+        .map(|s| s.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+fn to_pointer(segments: &[String]) -> String {
+    segments
+        .iter()
+        .map(|s| format!("/{}", s.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+/// Find where `target` lives in `root`, by identity, as a `#`-prefixed JSON
+/// pointer.
+fn locate(root: &serde_json::Value, target: &serde_json::Value) -> Option<String> {
+    fn walk(value: &serde_json::Value, target: &serde_json::Value, path: &mut Vec<String>) -> bool {
+        if std::ptr::eq(value, target) {
+            return true;
+        }
+        let children: Box<dyn Iterator<Item = (String, &serde_json::Value)>> = match value {
+            serde_json::Value::Object(map) => Box::new(map.iter().map(|(k, v)| (k.clone(), v))),
+            serde_json::Value::Array(items) => {
+                Box::new(items.iter().enumerate().map(|(i, v)| (i.to_string(), v)))
+            }
+            _ => return false,
+        };
+        for (key, child) in children {
+            path.push(key);
+            if walk(child, target, path) {
+                return true;
+            }
+            path.pop();
+        }
+        false
+    }
+
+    let mut path = Vec::new();
+    walk(root, target, &mut path).then(|| format!("#{}", to_pointer(&path)))
+}
+
+/// Follow fragment `$ref`s from `schema`, recording each one.
+fn follow_refs<'a>(
+    root: &'a serde_json::Value,
+    mut schema: &'a serde_json::Value,
+    refs: &mut Vec<String>,
+) -> &'a serde_json::Value {
+    while refs.len() < MAX_REFS
+        && let Some(reference) = schema.get("$ref").and_then(|v| v.as_str())
+        && let Some(fragment) = reference.strip_prefix('#')
+        && let Some(target) = root.pointer(fragment)
+    {
+        refs.push(reference.to_string());
+        schema = target;
+    }
+    schema
+}
+
+/// The subschema directly under `parent` for one pointer segment, before any
+/// `$ref` is followed. `None` if it is only reachable through `allOf`.
+fn direct_child<'a>(parent: &'a serde_json::Value, segment: &str) -> Option<&'a serde_json::Value> {
+    if let Some(child) = parent.get("properties").and_then(|p| p.get(segment)) {
+        return Some(child);
+    }
+    let index = segment.parse::<usize>().ok()?;
+    parent
+        .get("items")
+        .filter(|items| items.is_object() || items.is_boolean())
+        .or_else(|| parent.get("prefixItems")?.get(index))
+}
+
+/// Trace the subschemas that apply at each prefix of `pointer`, evaluating
+/// composition branches against the corresponding value in `instance`.
+/// Branches are compiled with `$ref`s resolved as when compiling `root`,
+/// fetching referenced documents per `no_cache`.
+pub fn subschema_chain(
+    root: &serde_json::Value,
+    pointer: &[String],
+    instance: &serde_json::Value,
+    options: &CompileOptions,
+    no_cache: bool,
+) -> Chain {
+    let mut chain = Chain::default();
+    let mut parent: Option<&serde_json::Value> = None;
+    for depth in 0..=pointer.len() {
+        let prefix = &pointer[..depth];
+        let pointer_str = to_pointer(prefix);
+        let Some(node) = schema::resolve_subschema_at_pointer(root, prefix) else {
+            chain.unresolved = Some(pointer_str);
+            break;
+        };
+
+        let mut refs = Vec::new();
+        let raw = match parent {
+            None => Some(root),
+            Some(parent) => direct_child(parent, &pointer[depth - 1]),
+        };
+        if let Some(raw) = raw {
+            follow_refs(root, raw, &mut refs);
+        }
+
+        let location = locate(root, node);
+        let value = instance.pointer(&pointer_str);
+        let mut branches = Vec::new();
+        for keyword in ["allOf", "oneOf", "anyOf"] {
+            let Some(items) = node.get(keyword).and_then(|v| v.as_array()) else {
+                continue;
+            };
+            for (index, branch) in items.iter().enumerate() {
+                let resolved = follow_refs(root, branch, &mut Vec::new());
+                let branch_location = locate(root, resolved);
+                let valid = match (&branch_location, value) {
+                    (Some(loc), Some(value)) => schema::subschema_is_valid(
+                        root,
+                        loc.trim_start_matches('#'),
+                        value,
+                        options,
+                        no_cache,
+                    ),
+                    _ => None,
+                };
+                branches.push(Branch {
+                    keyword,
+                    index,
+                    location: branch_location,
+                    title: resolved
+                        .get("title")
+                        .and_then(|v| v.as_str())
+                        .map(str::to_string),
+                    valid,
+                });
+            }
+        }
+
+        chain.steps.push(Step {
+            pointer: pointer_str,
+            refs,
+            location,
+            annotation: schema::lookup_schema_annotation(root, prefix),
+            branches,
+        });
+        parent = Some(node);
+    }
+    chain
+}
+
+/// Everything `jvl explain` reports about a file.
+#[derive(Debug)]
+pub struct Report {
+    pub file: String,
+    pub schema: Option<(SchemaSource, SchemaOrigin)>,
+    pub cache: Option<CacheStatus>,
+    /// Errors and warnings from validating the file.
+    pub diagnostics: Vec<FileDiagnostic>,
+    /// Subschemas at the requested location, if one was given.
+    pub chain: Option<Chain>,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "file: {}", self.file)?;
+        let Some((source, origin)) = &self.schema else {
+            writeln!(f, "schema: none")?;
+            writeln!(
+                f,
+                "  no --schema flag, $schema field, or matching config mapping"
+            )?;
+            return Ok(());
+        };
+        writeln!(f, "schema: {source}")?;
+        writeln!(f, "  chosen by: {origin}")?;
+        if let Some(cache) = &self.cache {
+            writeln!(f, "  cache: {cache}")?;
+        }
+
+        let errors = self
+            .diagnostics
+            .iter()
+            .filter(|d| d.severity == crate::diagnostic::Severity::Error)
+            .count();
+        match errors {
+            0 => writeln!(f, "result: valid")?,
+            1 => writeln!(f, "result: 1 error")?,
+            n => writeln!(f, "result: {n} errors")?,
+        }
+        for diag in &self.diagnostics {
+            match &diag.location {
+                Some(loc) => writeln!(
+                    f,
+                    "  {}:{} {}: {}",
+                    loc.line, loc.column, diag.code, diag.message
+                )?,
+                None => writeln!(f, "  {}: {}", diag.code, diag.message)?,
+            }
+        }
+
+        let Some(chain) = &self.chain else {
+            return Ok(());
+        };
+        writeln!(f)?;
+        for step in &chain.steps {
+            let pointer = if step.pointer.is_empty() {
+                "(root)"
+            } else {
+                &step.pointer
+            };
+            match &step.location {
+                Some(location) => writeln!(f, "{pointer} -> {location}")?,
+                None => writeln!(f, "{pointer}")?,
+            }
+            for reference in &step.refs {
+                writeln!(f, "  $ref: {reference}")?;
+            }
+            if let Some(annotation) = &step.annotation {
+                if let Some(title) = &annotation.title {
+                    writeln!(f, "  title: {title}")?;
+                }
+                if let Some(description) = &annotation.description {
+                    writeln!(f, "  description: {description}")?;
+                }
+            }
+            let one_of_valid = step
+                .branches
+                .iter()
+                .filter(|b| b.keyword == "oneOf" && b.valid == Some(true))
+                .count();
+            for branch in &step.branches {
+                write!(f, "  {}[{}]", branch.keyword, branch.index)?;
+                if let Some(location) = &branch.location {
+                    write!(f, " {location}")?;
+                }
+                if let Some(title) = &branch.title {
+                    write!(f, " ({title})")?;
+                }
+                match branch.valid {
+                    Some(true) if branch.keyword == "oneOf" && one_of_valid == 1 => {
+                        write!(f, ": valid, selected")?
+                    }
+                    Some(true) => write!(f, ": valid")?,
+                    Some(false) => write!(f, ": invalid")?,
+                    None => {}
+                }
+                writeln!(f)?;
+            }
+        }
+        if let Some(unresolved) = &chain.unresolved {
+            writeln!(f, "{unresolved}")?;
+            writeln!(f, "  no subschema found")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_pointer_forms() {
+        assert_eq!(parse_pointer("").unwrap(), Vec::<String>::new());
+        assert_eq!(parse_pointer("#/a/0").unwrap(), ["a", "0"]);
+        assert_eq!(parse_pointer("/a~1b/c~0d").unwrap(), ["a/b", "c~d"]);
+        assert!(parse_pointer("a").is_err());
+    }
+
+    #[test]
+    fn chain_follows_refs_and_selects_one_of_branch() {
+        let schema = json!({
+            "properties": { "port": { "$ref": "#/$defs/port" } },
+            "$defs": {
+                "port": {
+                    "title": "Port",
+                    "oneOf": [
+                        { "title": "Number", "type": "integer" },
+                        { "title": "Name", "type": "string" }
+                    ]
+                }
+            }
+        });
+        let chain = subschema_chain(
+            &schema,
+            &["port".to_string()],
+            &json!({ "port": "http" }),
+            &CompileOptions::default(),
+            false,
+        );
+        assert_eq!(chain.steps.len(), 2);
+        let step = &chain.steps[1];
+        assert_eq!(step.pointer, "/port");
+        assert_eq!(step.refs, ["#/$defs/port"]);
+        assert_eq!(step.location.as_deref(), Some("#/$defs/port"));
+        assert_eq!(
+            step.annotation.as_ref().unwrap().title.as_deref(),
+            Some("Port")
+        );
+        let valid: Vec<Option<bool>> = step.branches.iter().map(|b| b.valid).collect();
+        assert_eq!(valid, [Some(false), Some(true)]);
+        assert!(chain.unresolved.is_none());
+    }
+
+    #[test]
+    fn branches_resolve_refs_against_the_schema_id() {
+        let schema = json!({
+            "$id": "https://example.com/root.json",
+            "properties": {
+                "port": {
+                    "anyOf": [
+                        { "$ref": "root.json#/$defs/number" },
+                        { "$ref": "root.json#/$defs/name" }
+                    ]
+                }
+            },
+            "$defs": {
+                "number": { "type": "integer" },
+                "name": { "type": "string" }
+            }
+        });
+        let chain = subschema_chain(
+            &schema,
+            &["port".to_string()],
+            &json!({ "port": 80 }),
+            &CompileOptions::default(),
+            false,
+        );
+        let valid: Vec<Option<bool>> = chain.steps[1].branches.iter().map(|b| b.valid).collect();
+        assert_eq!(valid, [Some(true), Some(false)]);
+    }
+
+    #[test]
+    fn chain_reports_unresolved_location() {
+        let schema = json!({ "properties": { "a": {} } });
+        let chain = subschema_chain(
+            &schema,
+            &["b".to_string(), "c".to_string()],
+            &json!({}),
+            &CompileOptions::default(),
+            false,
+        );
+        assert_eq!(chain.steps.len(), 1);
+        assert_eq!(chain.unresolved.as_deref(), Some("/b"));
+    }
+}
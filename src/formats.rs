@@ -0,0 +1,211 @@
+//! Extra `format` validators registered with every compiled schema.
+
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// A custom `format` from the config: a regex the value must match.
+///
+/// Compiled once when the config is loaded and shared by every validator.
+/// Compares and hashes by its pattern, so it can be part of a cache key.
+#[derive(Debug, Clone)]
+pub struct CustomFormat(Arc<regex::Regex>);
+
+impl CustomFormat {
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        regex::Regex::new(pattern).map(|regex| Self(Arc::new(regex)))
+    }
+
+    pub fn is_match(&self, value: &str) -> bool {
+        self.0.is_match(value)
+    }
+}
+
+impl PartialEq for CustomFormat {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl Eq for CustomFormat {}
+
+impl Hash for CustomFormat {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.as_str().hash(state);
+    }
+}
+
+/// Validate a [Semantic Versioning 2.0.0](https://semver.org/) string,
+/// e.g. `1.2.3`, `1.0.0-alpha.1`, or `1.0.0+build.5`.
+pub fn is_semver(value: &str) -> bool {
+    let (rest, build) = match value.split_once('+') {
+        Some((rest, build)) => (rest, Some(build)),
+        None => (value, None),
+    };
+    let (core, pre) = match rest.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (rest, None),
+    };
+
+    let mut parts = core.split('.');
+    for _ in 0..3 {
+        match parts.next() {
+            Some(part) if is_numeric_identifier(part) => {}
+            _ => return false,
+        }
+    }
+    if parts.next().is_some() {
+        return false;
+    }
+
+    let pre_ok = pre.is_none_or(|pre| {
+        pre.split('.').all(|id| {
+            is_alphanumeric_identifier(id)
+                && (!id.bytes().all(|b| b.is_ascii_digit()) || is_numeric_identifier(id))
+        })
+    });
+    let build_ok = build.is_none_or(|build| build.split('.').all(is_alphanumeric_identifier));
+
+    pre_ok && build_ok
+}
+
+/// A non-empty run of digits without a leading zero (except `0` itself).
+fn is_numeric_identifier(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) && (s == "0" || !s.starts_with('0'))
+}
+
+/// A non-empty run of ASCII alphanumerics and hyphens.
+fn is_alphanumeric_identifier(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+}
+
+/// Validate an ISO 8601 duration as profiled by RFC 3339 Appendix A,
+/// e.g. `P1Y2M`, `PT30S`, or `P2W`.
+///
+/// Registered for every draft, so `duration` is also checked for schemas
+/// older than 2019-09 (where the library has no built-in validator).
+pub fn is_duration(value: &str) -> bool {
+    let Some(rest) = value.strip_prefix('P') else {
+        return false;
+    };
+    let (date, time) = match rest.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None => (rest, None),
+    };
+
+    // Weeks cannot be combined with any other unit.
+    if let Some(weeks) = date.strip_suffix('W')
+        && time.is_none()
+        && !weeks.is_empty()
+        && weeks.bytes().all(|b| b.is_ascii_digit())
+    {
+        return true;
+    }
+
+    let Some(date_units) = parse_units(date, b"YMD") else {
+        return false;
+    };
+    let time_units = match time {
+        // A `T` designator must be followed by at least one time component.
+        Some(time) => match parse_units(time, b"HMS") {
+            Some(0) | None => return false,
+            Some(n) => n,
+        },
+        None => 0,
+    };
+
+    date_units + time_units > 0
+}
+
+/// Parse `<digits><unit>` components whose units appear in `order` without
+/// repetition. Returns the number of components, or `None` if malformed.
+fn parse_units(s: &str, order: &[u8]) -> Option<usize> {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    let mut next_unit = 0;
+    let mut count = 0;
+    while i < bytes.len() {
+        let start = i;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == start || i == bytes.len() {
+            return None;
+        }
+        let idx = order[next_unit..].iter().position(|&u| u == bytes[i])?;
+        next_unit += idx + 1;
+        count += 1;
+        i += 1;
+    }
+    Some(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_formats_compare_by_pattern() {
+        let format = CustomFormat::new("^[a-z]+$").unwrap();
+        assert!(format.is_match("abc") && !format.is_match("ABC"));
+        assert_eq!(format, CustomFormat::new("^[a-z]+$").unwrap());
+        assert_ne!(format, CustomFormat::new("^[a-z]*$").unwrap());
+        assert!(CustomFormat::new("[").is_err());
+    }
+
+    #[test]
+    fn semver_valid() {
+        for v in [
+            "0.0.0",
+            "1.2.3",
+            "10.20.30",
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-0.3.7",
+            "1.0.0-x-y-z.--",
+            "1.0.0+20130313144700",
+            "1.0.0-beta+exp.sha.5114f85",
+        ] {
+            assert!(is_semver(v), "{v} should be valid");
+        }
+    }
+
+    #[test]
+    fn semver_invalid() {
+        for v in [
+            "",
+            "1",
+            "1.2",
+            "1.2.3.4",
+            "01.2.3",
+            "1.02.3",
+            "1.2.3-",
+            "1.2.3+",
+            "1.2.3-01",
+            "1.2.3-a..b",
+            "v1.2.3",
+            "1.2.x",
+        ] {
+            assert!(!is_semver(v), "{v} should be invalid");
+        }
+    }
+
+    #[test]
+    fn duration_valid() {
+        for v in [
+            "P1Y", "P1M", "P1D", "P1Y2M3D", "P1Y3D", "PT1H", "PT1M", "PT1S", "PT1H30M", "P1DT12H",
+            "P2W",
+        ] {
+            assert!(is_duration(v), "{v} should be valid");
+        }
+    }
+
+    #[test]
+    fn duration_invalid() {
+        for v in [
+            "", "P", "PT", "1Y", "P1", "P1D2Y", "P1Y1Y", "P1DT", "PT1D", "P1W2D", "P1WT1H",
+            "P1.5Y", "P-1D",
+        ] {
+            assert!(!is_duration(v), "{v} should be invalid");
+        }
+    }
+}
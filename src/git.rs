@@ -0,0 +1,257 @@
+//! Git integration: selecting files by their git status, so only what
+//! changed is checked, and reading the files of a commit without checking it
+//! out.
+//!
+//! Runs the `git` CLI rather than linking a git library.
+
+use std::collections::{BTreeMap, HashSet};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Which files to select.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selection {
+    /// Files that differ from `HEAD` in the index or working tree, and
+    /// untracked files.
+    Changed,
+    /// Files whose staged content differs from `HEAD`. Their staged
+    /// content is what gets checked; see [`Tree::load_index`].
+    Staged,
+    /// Files that differ from the given ref in the working tree, and
+    /// untracked files.
+    Since(String),
+}
+
+/// Canonical absolute paths of the files in `selection`, for the repository
+/// containing `dir`. Deleted files are left out; untracked files honor
+/// `.gitignore`.
+pub fn selected_files(dir: &Path, selection: &Selection) -> Result<HashSet<PathBuf>, String> {
+    let toplevel = toplevel(dir)?;
+
+    let mut diff = vec!["diff", "--name-only", "-z", "--diff-filter=d"];
+    let untracked = match selection {
+        Selection::Changed => {
+            diff.push("HEAD");
+            true
+        }
+        Selection::Staged => {
+            diff.push("--cached");
+            false
+        }
+        Selection::Since(rev) => {
+            // `--end-of-options` keeps a ref starting with `-` from being
+            // read as an option.
+            diff.extend(["--end-of-options", rev.as_str()]);
+            true
+        }
+    };
+
+    let mut output = git(&toplevel, &diff)?;
+    if untracked {
+        output.push_str(&git(
+            &toplevel,
+            &["ls-files", "--others", "--exclude-standard", "-z"],
+        )?);
+    }
+    Ok(output
+        .split('\0')
+        .filter(|p| !p.is_empty())
+        .map(|p| toplevel.join(p))
+        .collect())
+}
+
+/// The canonical top-level directory of the repository containing `dir`.
+fn toplevel(dir: &Path) -> Result<PathBuf, String> {
+    let toplevel = git(dir, &["rev-parse", "--show-toplevel"])?;
+    let toplevel = PathBuf::from(toplevel.trim_end_matches('\n'));
+    Ok(std::fs::canonicalize(&toplevel).unwrap_or(toplevel))
+}
+
+/// Run git in `dir` and return its standard output.
+fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| format!("failed to run git: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git {} failed: {}", args[0], stderr.trim()));
+    }
+    String::from_utf8(output.stdout).map_err(|_| format!("git {} printed invalid UTF-8", args[0]))
+}
+
+/// The files of a commit or of the index, read from the object database
+/// without checking them out.
+///
+/// Files are addressed by where they would be in a checkout: absolute paths
+/// under [`root`](Self::root), the repository's top-level directory.
+pub struct Tree {
+    root: PathBuf,
+    /// Path relative to the root → blob ID.
+    blobs: BTreeMap<String, String>,
+}
+
+impl Tree {
+    /// Load the tree of `rev` in the repository containing `dir`.
+    pub fn load(dir: &Path, rev: &str) -> Result<Self, String> {
+        let root = toplevel(dir)?;
+        let commit = format!("{rev}^{{commit}}");
+        let commit = git(
+            &root,
+            &[
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                "--end-of-options",
+                &commit,
+            ],
+        )
+        .map_err(|_| format!("'{rev}' is not a commit"))?;
+
+        let listing = git(
+            &root,
+            &["ls-tree", "-r", "-z", "--full-tree", commit.trim_end()],
+        )?;
+        let blobs = listing
+            .split('\0')
+            .filter_map(|entry| {
+                let (info, path) = entry.split_once('\t')?;
+                let mut info = info.split(' ');
+                let (mode, kind, id) = (info.next()?, info.next()?, info.next()?);
+                // Symlinks and submodules have no content of their own.
+                (kind == "blob" && mode != "120000").then(|| (path.to_string(), id.to_string()))
+            })
+            .collect();
+        Ok(Self { root, blobs })
+    }
+
+    /// Load the staged files of the repository containing `dir`: the
+    /// content a commit would record. Unmerged paths are left out.
+    pub fn load_index(dir: &Path) -> Result<Self, String> {
+        let root = toplevel(dir)?;
+        let listing = git(&root, &["ls-files", "--stage", "-z"])?;
+        let blobs = listing
+            .split('\0')
+            .filter_map(|entry| {
+                let (info, path) = entry.split_once('\t')?;
+                let mut info = info.split(' ');
+                let (mode, id, stage) = (info.next()?, info.next()?, info.next()?);
+                // Symlinks and submodules have no content of their own.
+                (stage == "0" && mode != "120000" && mode != "160000")
+                    .then(|| (path.to_string(), id.to_string()))
+            })
+            .collect();
+        Ok(Self { root, blobs })
+    }
+
+    /// The directory the tree's paths are under.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// `path` relative to the root, as it appears in the tree.
+    pub fn tree_path(&self, path: &Path) -> Option<String> {
+        let relative = path.strip_prefix(&self.root).ok()?;
+        Some(relative.to_string_lossy().replace('\\', "/"))
+    }
+
+    /// Every file in the tree under `dir`, as absolute paths, in order.
+    pub fn files_under<'a>(&'a self, dir: &'a Path) -> impl Iterator<Item = PathBuf> + 'a {
+        self.blobs
+            .keys()
+            .map(|path| self.root.join(path))
+            .filter(move |path| path.starts_with(dir))
+    }
+
+    /// Whether the tree has a file at `path`.
+    pub fn contains(&self, path: &Path) -> bool {
+        self.blob(path).is_some()
+    }
+
+    fn blob(&self, path: &Path) -> Option<&str> {
+        self.blobs.get(&self.tree_path(path)?).map(String::as_str)
+    }
+
+    /// Read the file at `path`.
+    pub fn read(&self, path: &Path) -> std::io::Result<String> {
+        self.read_all(&[path]).pop().expect("one result per path")
+    }
+
+    /// Read many files with one git process.
+    pub fn read_all(&self, paths: &[&Path]) -> Vec<std::io::Result<String>> {
+        let ids: Vec<Option<&str>> = paths.iter().map(|path| self.blob(path)).collect();
+        let contents = match self.cat_file_batch(ids.iter().flatten().copied()) {
+            Ok(contents) => contents,
+            Err(e) => {
+                return paths
+                    .iter()
+                    .map(|_| Err(std::io::Error::new(e.kind(), e.to_string())))
+                    .collect();
+            }
+        };
+        let mut contents = contents.into_iter();
+        paths
+            .iter()
+            .zip(ids)
+            .map(|(path, id)| match id {
+                Some(_) => contents.next().unwrap_or_else(|| Err(not_found(path))),
+                None => Err(not_found(path)),
+            })
+            .collect()
+    }
+
+    /// The content of each blob, in order, from `git cat-file --batch`.
+    fn cat_file_batch<'a>(
+        &self,
+        ids: impl Iterator<Item = &'a str>,
+    ) -> std::io::Result<Vec<std::io::Result<String>>> {
+        let mut child = Command::new("git")
+            .arg("-C")
+            .arg(&self.root)
+            .args(["cat-file", "--batch"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let input: String = ids.map(|id| format!("{id}\n")).collect();
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        // Write from another thread so a full stdout pipe can't deadlock us.
+        let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+        let mut stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+
+        let mut contents = Vec::new();
+        let mut header = String::new();
+        loop {
+            header.clear();
+            if stdout.read_line(&mut header)? == 0 {
+                break;
+            }
+            // `<id> blob <size>`, then the content and a newline.
+            let size = header
+                .trim_end()
+                .rsplit(' ')
+                .next()
+                .and_then(|size| size.parse::<usize>().ok())
+                .ok_or_else(|| std::io::Error::other(format!("unexpected git output: {header}")))?;
+            let mut content = vec![0; size + 1];
+            stdout.read_exact(&mut content)?;
+            content.pop();
+            contents.push(String::from_utf8(content).map_err(|_| {
+                std::io::Error::new(std::io::ErrorKind::InvalidData, "not valid UTF-8")
+            }));
+        }
+        writer.join().expect("writer thread panicked")?;
+        child.wait()?;
+        Ok(contents)
+    }
+}
+
+fn not_found(path: &Path) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        format!("{} is not in the tree", path.display()),
+    )
+}
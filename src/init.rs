@@ -0,0 +1,305 @@
+//! Config bootstrapping for `jvl init`: scan a project, suggest schema
+//! mappings, and render them as a commented `jvl.json`.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use crate::catalog::Catalog;
+use crate::discover::{SchemaMapping, SchemaMappingPath, SchemaMappingUrl};
+use crate::parse;
+use crate::schema::{self, SchemaSource};
+
+/// URL of the jvl config schema, written as the generated config's `$schema`.
+pub const CONFIG_SCHEMA_URL: &str =
+    "https://raw.githubusercontent.com/sargunv/jvl/main/config.schema.json";
+
+/// A discovered file and the schema it declares.
+#[derive(Debug, Clone)]
+pub struct ScannedFile {
+    /// Path relative to the project root, with `/` separators.
+    pub path: String,
+    /// The `$schema` value: a URL, or a path relative to the project root.
+    pub schema: Option<String>,
+}
+
+/// A suggested schema mapping.
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    pub mapping: SchemaMapping,
+    /// Why the mapping is suggested, written as a comment above it.
+    pub reason: String,
+    /// Files without `$schema` that the mapping covers.
+    pub files: Vec<String>,
+}
+
+/// Everything `jvl init` learned about a project.
+#[derive(Debug, Default)]
+pub struct Plan {
+    pub suggestions: Vec<Suggestion>,
+    /// Distinct `$schema` values and how many files declare each.
+    pub declared: BTreeMap<String, usize>,
+    /// Files with neither a `$schema` nor a role as some file's schema.
+    pub unschemed: Vec<String>,
+}
+
+/// Read each file's `$schema`, resolving relative paths against the file and
+/// expressing them relative to `root`. Unreadable files are skipped.
+pub fn scan(root: &Path, files: &[PathBuf]) -> Vec<ScannedFile> {
+    let root = schema::normalize_file_path(root);
+    let relative = |path: &Path| {
+        let path = schema::normalize_file_path(path);
+        path.strip_prefix(&root)
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_else(|_| path.display().to_string())
+    };
+
+    let mut scanned: Vec<ScannedFile> = files
+        .iter()
+        .filter_map(|path| {
+            let content = std::fs::read_to_string(path).ok()?;
+            let schema = parse::extract_schema_field_from_str(&content).map(|reference| {
+                let dir = path.parent().unwrap_or(Path::new("."));
+                match schema::resolve_schema_ref(&reference, dir) {
+                    SchemaSource::Url(url) => url,
+                    SchemaSource::File(file) => relative(&file),
+                }
+            });
+            Some(ScannedFile {
+                path: relative(path),
+                schema,
+            })
+        })
+        .collect();
+    scanned.sort_by(|a, b| a.path.cmp(&b.path));
+    scanned
+}
+
+/// Group scanned files by file name and suggest a mapping per group:
+///
+/// - if some files with the name declare a `$schema` and they all agree, map
+///   the rest of them to it;
+/// - if none declare one, map them to the catalog's schema for the name.
+pub fn plan(files: &[ScannedFile], catalog: &Catalog) -> Plan {
+    let mut plan = Plan::default();
+    for file in files {
+        if let Some(schema) = &file.schema {
+            *plan.declared.entry(schema.clone()).or_default() += 1;
+        }
+    }
+
+    let mut by_name: BTreeMap<&str, Vec<&ScannedFile>> = BTreeMap::new();
+    for file in files {
+        // Files used as some other file's schema don't need one themselves.
+        if file.schema.is_none() && plan.declared.contains_key(&file.path) {
+            continue;
+        }
+        let name = file.path.rsplit('/').next().unwrap_or(&file.path);
+        by_name.entry(name).or_default().push(file);
+    }
+
+    // Catalog entries already suggested: entry URL → index in suggestions.
+    let mut from_catalog: HashMap<&str, usize> = HashMap::new();
+    for (name, group) in by_name {
+        let missing: Vec<String> = group
+            .iter()
+            .filter(|f| f.schema.is_none())
+            .map(|f| f.path.clone())
+            .collect();
+        plan.unschemed.extend(missing.iter().cloned());
+        if missing.is_empty() {
+            continue;
+        }
+
+        let declared: BTreeSet<&str> = group.iter().filter_map(|f| f.schema.as_deref()).collect();
+        if declared.len() == 1 {
+            let schema = declared.into_iter().next().unwrap();
+            let declaring = group.len() - missing.len();
+            plan.suggestions.push(Suggestion {
+                mapping: mapping(vec![format!("**/{name}")], schema),
+                reason: format!(
+                    "{declaring} of {} `{name}` files declare this $schema",
+                    group.len()
+                ),
+                files: missing,
+            });
+        } else if declared.is_empty()
+            && let Some(entry) = catalog.lookup(name)
+        {
+            match from_catalog.get(entry.url) {
+                Some(&i) => plan.suggestions[i].files.extend(missing),
+                None => {
+                    from_catalog.insert(entry.url, plan.suggestions.len());
+                    plan.suggestions.push(Suggestion {
+                        mapping: mapping(
+                            entry.file_names.iter().map(|n| format!("**/{n}")).collect(),
+                            entry.url,
+                        ),
+                        reason: format!("{}, from the schema catalog", entry.name),
+                        files: missing,
+                    });
+                }
+            }
+        }
+    }
+    plan.unschemed.sort();
+    plan
+}
+
+fn mapping(files: Vec<String>, schema: &str) -> SchemaMapping {
+    if schema.contains("://") {
+        SchemaMapping::Url(SchemaMappingUrl {
+            url: schema.to_string(),
+            files,
+        })
+    } else {
+        SchemaMapping::Path(SchemaMappingPath {
+            path: schema.to_string(),
+            files,
+        })
+    }
+}
+
+/// Render a commented `jvl.json` with the accepted suggestions.
+pub fn render(plan: &Plan, accepted: &[&Suggestion]) -> String {
+    let json = |s: &str| serde_json::to_string(s).unwrap();
+    let plural = |n: usize| if n == 1 { "file" } else { "files" };
+
+    let mut out = String::new();
+    out.push_str("// jvl configuration, generated by `jvl init`.\n");
+    out.push_str("// See https://github.com/sargunv/jvl#configuration for all options.\n");
+    out.push_str("{\n");
+    let _ = writeln!(out, "  \"$schema\": {},", json(CONFIG_SCHEMA_URL));
+    out.push_str("  // Files to validate. Prefix a pattern with \"!\" to exclude matches.\n");
+    out.push_str("  \"files\": [\"**/*.json\", \"**/*.jsonc\"],\n");
+    out.push_str("  // Schemas for files without a \"$schema\" field. The first match wins.\n");
+    if accepted.is_empty() {
+        out.push_str("  \"schemas\": []");
+    } else {
+        out.push_str("  \"schemas\": [\n");
+        for (i, suggestion) in accepted.iter().enumerate() {
+            let count = suggestion.files.len();
+            let _ = writeln!(
+                out,
+                "    // {} ({count} {} without $schema)",
+                suggestion.reason,
+                plural(count)
+            );
+            let files: Vec<String> = suggestion.mapping.files().iter().map(|f| json(f)).collect();
+            let (key, value) = match &suggestion.mapping {
+                SchemaMapping::Url(m) => ("url", &m.url),
+                SchemaMapping::Path(m) => ("path", &m.path),
+            };
+            let line = format!(
+                "{{ \"files\": [{}], \"{key}\": {} }}",
+                files.join(", "),
+                json(value)
+            );
+            if line.len() + 4 <= 80 {
+                let _ = write!(out, "    {line}");
+            } else {
+                let _ = write!(
+                    out,
+                    "    {{\n      \"files\": [{}],\n      \"{key}\": {}\n    }}",
+                    files.join(", "),
+                    json(value)
+                );
+            }
+            out.push_str(if i + 1 < accepted.len() { ",\n" } else { "\n" });
+        }
+        out.push_str("  ]");
+    }
+    out.push('\n');
+
+    if !plan.declared.is_empty() {
+        out.push_str("  // Schemas declared by files' own \"$schema\" fields:\n");
+        for (schema, count) in &plan.declared {
+            let _ = writeln!(out, "  //   {schema} ({count} {})", plural(*count));
+        }
+    }
+
+    let covered: BTreeSet<&str> = accepted
+        .iter()
+        .flat_map(|s| s.files.iter().map(String::as_str))
+        .collect();
+    let unmatched: Vec<&str> = plan
+        .unschemed
+        .iter()
+        .map(String::as_str)
+        .filter(|f| !covered.contains(f))
+        .collect();
+    if !unmatched.is_empty() {
+        let _ = writeln!(
+            out,
+            "  // No schema found for {} {}:",
+            unmatched.len(),
+            plural(unmatched.len())
+        );
+        for file in unmatched.iter().take(10) {
+            let _ = writeln!(out, "  //   {file}");
+        }
+        if unmatched.len() > 10 {
+            let _ = writeln!(out, "  //   ...and {} more", unmatched.len() - 10);
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, schema: Option<&str>) -> ScannedFile {
+        ScannedFile {
+            path: path.to_string(),
+            schema: schema.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn suggests_agreeing_schema_for_same_file_name() {
+        let files = [
+            file("a/app.json", Some("schemas/app.json")),
+            file("b/app.json", None),
+            file("schemas/app.json", None),
+        ];
+        let plan = plan(&files, &Catalog::new());
+        assert_eq!(plan.suggestions.len(), 1);
+        let suggestion = &plan.suggestions[0];
+        assert_eq!(suggestion.mapping.files(), ["**/app.json"]);
+        assert!(
+            matches!(&suggestion.mapping, SchemaMapping::Path(m) if m.path == "schemas/app.json")
+        );
+        assert_eq!(suggestion.files, ["b/app.json"]);
+        // The schema file itself isn't reported as missing a schema.
+        assert_eq!(plan.unschemed, ["b/app.json"]);
+    }
+
+    #[test]
+    fn no_suggestion_when_declared_schemas_disagree() {
+        let files = [
+            file("a/config.json", Some("https://example.com/a.json")),
+            file("b/config.json", Some("https://example.com/b.json")),
+            file("c/config.json", None),
+        ];
+        let plan = plan(&files, &Catalog::new());
+        assert!(plan.suggestions.is_empty());
+        assert_eq!(plan.unschemed, ["c/config.json"]);
+    }
+
+    #[test]
+    fn catalog_suggestions_merge_file_names() {
+        let files = [
+            file("tsconfig.json", None),
+            file("packages/x/tsconfig.build.json", None),
+        ];
+        let plan = plan(&files, &Catalog::new());
+        assert_eq!(plan.suggestions.len(), 1);
+        assert_eq!(
+            plan.suggestions[0].mapping.files(),
+            ["**/tsconfig.json", "**/tsconfig.*.json"]
+        );
+        assert_eq!(plan.suggestions[0].files.len(), 2);
+    }
+}
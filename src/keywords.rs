@@ -0,0 +1,222 @@
+//! Built-in extension keywords that can be enabled in `jvl.json`.
+//!
+//! Errors from these keywords are reported with the keyword name as the
+//! diagnostic code, e.g. `schema(x-unique-by)`.
+
+use jsonschema::paths::Location;
+use jsonschema::{Keyword, ValidationError, ValidationOptions};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::diagnostic::Severity;
+
+/// An extension keyword that jvl knows how to validate.
+#[derive(
+    Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+pub enum ExtensionKeyword {
+    /// `"x-deprecated": true | "<message>"` — any value at this location is
+    /// reported, as a warning.
+    #[serde(rename = "x-deprecated")]
+    XDeprecated,
+    /// `"x-unique-by": "<property>" | ["<property>", ...]` — array items must
+    /// be unique by the given property (or combination of properties).
+    #[serde(rename = "x-unique-by")]
+    XUniqueBy,
+}
+
+impl ExtensionKeyword {
+    /// The keyword as it appears in schemas.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::XDeprecated => "x-deprecated",
+            Self::XUniqueBy => "x-unique-by",
+        }
+    }
+
+    /// The keyword named `name`, if jvl has one.
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::XDeprecated, Self::XUniqueBy]
+            .into_iter()
+            .find(|keyword| keyword.name() == name)
+    }
+
+    /// The severity of the keyword's diagnostics before `severity`
+    /// overrides. Deprecations are warnings, like those of `deprecated`.
+    pub fn severity(self) -> Severity {
+        match self {
+            Self::XDeprecated => Severity::Warning,
+            Self::XUniqueBy => Severity::Error,
+        }
+    }
+
+    /// Register this keyword's validator with the schema compiler.
+    pub fn register(self, options: ValidationOptions) -> ValidationOptions {
+        match self {
+            Self::XDeprecated => options.with_keyword(self.name(), deprecated_factory),
+            Self::XUniqueBy => options.with_keyword(self.name(), unique_by_factory),
+        }
+    }
+}
+
+/// Accepts every instance. Used when a keyword is present but disabled,
+/// e.g. `"x-deprecated": false`.
+struct AlwaysValid;
+
+impl Keyword for AlwaysValid {
+    fn validate<'i>(&self, _instance: &'i Value) -> Result<(), ValidationError<'i>> {
+        Ok(())
+    }
+
+    fn is_valid(&self, _instance: &Value) -> bool {
+        true
+    }
+}
+
+struct Deprecated {
+    message: String,
+}
+
+impl Keyword for Deprecated {
+    fn validate<'i>(&self, _instance: &'i Value) -> Result<(), ValidationError<'i>> {
+        Err(ValidationError::custom(self.message.clone()))
+    }
+
+    fn is_valid(&self, _instance: &Value) -> bool {
+        false
+    }
+}
+
+fn deprecated_factory<'a>(
+    _parent: &'a Map<String, Value>,
+    value: &'a Value,
+    _path: Location,
+) -> Result<Box<dyn Keyword>, ValidationError<'a>> {
+    match value {
+        Value::Bool(false) => Ok(Box::new(AlwaysValid)),
+        Value::Bool(true) => Ok(Box::new(Deprecated {
+            message: "value is deprecated".into(),
+        })),
+        Value::String(reason) => Ok(Box::new(Deprecated {
+            message: format!("value is deprecated: {reason}"),
+        })),
+        _ => Err(ValidationError::schema(
+            "x-deprecated must be a boolean or a string",
+        )),
+    }
+}
+
+struct UniqueBy {
+    properties: Vec<String>,
+}
+
+impl UniqueBy {
+    /// Find the first pair of items with equal keys. Items missing any of
+    /// the key properties are ignored.
+    fn find_duplicate<'i>(&self, items: &'i [Value]) -> Option<(usize, usize, Vec<&'i Value>)> {
+        let keys: Vec<Option<Vec<&Value>>> = items
+            .iter()
+            .map(|item| {
+                let obj = item.as_object()?;
+                self.properties.iter().map(|p| obj.get(p)).collect()
+            })
+            .collect();
+        for (j, key) in keys.iter().enumerate() {
+            let Some(key) = key else { continue };
+            if let Some(i) = keys[..j].iter().position(|k| k.as_ref() == Some(key)) {
+                return Some((i, j, key.clone()));
+            }
+        }
+        None
+    }
+}
+
+impl Keyword for UniqueBy {
+    fn validate<'i>(&self, instance: &'i Value) -> Result<(), ValidationError<'i>> {
+        let Some(items) = instance.as_array() else {
+            return Ok(());
+        };
+        match self.find_duplicate(items) {
+            Some((i, j, key)) => {
+                let key: Vec<String> = key.iter().map(|v| v.to_string()).collect();
+                Err(ValidationError::custom(format!(
+                    "items at index {i} and {j} have the same {} ({})",
+                    self.properties
+                        .iter()
+                        .map(|p| format!("'{p}'"))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    key.join(", ")
+                )))
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn is_valid(&self, instance: &Value) -> bool {
+        instance
+            .as_array()
+            .is_none_or(|items| self.find_duplicate(items).is_none())
+    }
+}
+
+fn unique_by_factory<'a>(
+    _parent: &'a Map<String, Value>,
+    value: &'a Value,
+    _path: Location,
+) -> Result<Box<dyn Keyword>, ValidationError<'a>> {
+    let properties = match value {
+        Value::String(p) => vec![p.clone()],
+        Value::Array(items) if !items.is_empty() => items
+            .iter()
+            .map(|v| v.as_str().map(str::to_string))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| ValidationError::schema("x-unique-by items must be strings"))?,
+        _ => {
+            return Err(ValidationError::schema(
+                "x-unique-by must be a property name or a non-empty array of property names",
+            ));
+        }
+    };
+    Ok(Box::new(UniqueBy { properties }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn unique_by(properties: &[&str]) -> UniqueBy {
+        UniqueBy {
+            properties: properties.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn unique_by_finds_first_duplicate() {
+        let items = json!([{ "id": 1 }, { "id": 2 }, { "id": 1 }]);
+        let (i, j, key) = unique_by(&["id"])
+            .find_duplicate(items.as_array().unwrap())
+            .unwrap();
+        assert_eq!((i, j), (0, 2));
+        assert_eq!(key, [&json!(1)]);
+    }
+
+    #[test]
+    fn unique_by_composite_key() {
+        let items = json!([
+            { "ns": "a", "name": "x" },
+            { "ns": "b", "name": "x" },
+        ]);
+        let rule = unique_by(&["ns", "name"]);
+        assert!(rule.is_valid(&items));
+        assert!(!unique_by(&["name"]).is_valid(&items));
+    }
+
+    #[test]
+    fn unique_by_ignores_items_without_key() {
+        let items = json!([{ "id": 1 }, {}, {}, "x", "x"]);
+        assert!(unique_by(&["id"]).is_valid(&items));
+    }
+}
@@ -1,7 +1,18 @@
+pub mod catalog;
+pub mod crossfile;
 pub mod diagnostic;
 pub mod discover;
+pub mod embed;
+pub mod explain;
+pub mod formats;
+pub mod git;
+pub mod init;
+pub mod keywords;
+pub mod lint;
 pub mod lsp;
 pub mod output;
 pub mod parse;
+pub mod result_cache;
 pub mod schema;
 pub mod validate;
+pub mod yaml;
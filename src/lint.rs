@@ -0,0 +1,326 @@
+//! Lint rules that check how a document is written rather than what the
+//! schema allows: duplicate keys, key casing, nesting depth, file size, and
+//! empty objects and arrays.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use globset::GlobSet;
+use jsonc_parser::ast::Value as AstValue;
+use jsonc_parser::common::Ranged;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::diagnostic::{FileDiagnostic, RelatedInfo, Severity, SourceLocation};
+use crate::discover::{self, Config, ConfigError, LintRule};
+use crate::parse::ParsedFile;
+
+/// A naming convention for object keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum KeyCase {
+    /// `fooBar`
+    #[serde(rename = "camelCase")]
+    Camel,
+    /// `foo_bar`
+    #[serde(rename = "snake_case")]
+    Snake,
+    /// `foo-bar`
+    #[serde(rename = "kebab-case")]
+    Kebab,
+}
+
+impl KeyCase {
+    /// Whether `key` follows the convention. Keys starting with `$`, such as
+    /// `$schema`, are exempt.
+    pub fn matches(self, key: &str) -> bool {
+        if key.starts_with('$') {
+            return true;
+        }
+        let mut chars = key.chars();
+        if !chars.next().is_some_and(|c| c.is_ascii_lowercase()) {
+            return false;
+        }
+        let separator = match self {
+            Self::Camel => {
+                return chars.all(|c| c.is_ascii_alphanumeric());
+            }
+            Self::Snake => '_',
+            Self::Kebab => '-',
+        };
+        key.split(separator).all(|word| {
+            !word.is_empty()
+                && word
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        })
+    }
+}
+
+impl std::fmt::Display for KeyCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Camel => "camelCase",
+            Self::Snake => "snake_case",
+            Self::Kebab => "kebab-case",
+        })
+    }
+}
+
+/// The lint rules enabled for one file. Duplicate keys are always checked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LintOptions {
+    pub key_case: Option<KeyCase>,
+    pub max_depth: Option<usize>,
+    pub max_file_size: Option<usize>,
+    pub no_empty_objects: bool,
+    pub no_empty_arrays: bool,
+}
+
+impl LintOptions {
+    /// Whether an edit to whitespace or comments alone can change what the
+    /// rules report. Only the file size counts those.
+    pub fn sees_trivia(&self) -> bool {
+        self.max_file_size.is_some()
+    }
+}
+
+/// Pre-compiled `lint` rules for resolving each file's [`LintOptions`].
+#[derive(Default)]
+pub struct CompiledLint {
+    entries: Vec<(GlobSet, LintRule)>,
+}
+
+impl CompiledLint {
+    pub fn compile(config: &Config) -> Result<Self, ConfigError> {
+        let entries = config
+            .lint
+            .iter()
+            .map(|rule| Ok((discover::build_globset(&rule.files)?, rule.clone())))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { entries })
+    }
+
+    /// The options for a file: every matching rule applies, and later rules
+    /// override the settings of earlier ones.
+    pub fn resolve(&self, file_relative: &str) -> LintOptions {
+        let mut options = LintOptions::default();
+        for (globset, rule) in &self.entries {
+            if !globset.is_match(file_relative) {
+                continue;
+            }
+            options.key_case = rule.key_case.or(options.key_case);
+            options.max_depth = rule.max_depth.or(options.max_depth);
+            options.max_file_size = rule.max_file_size.or(options.max_file_size);
+            options.no_empty_objects = rule.no_empty_objects.unwrap_or(options.no_empty_objects);
+            options.no_empty_arrays = rule.no_empty_arrays.unwrap_or(options.no_empty_arrays);
+        }
+        options
+    }
+}
+
+/// Run the lint rules over a parsed document.
+pub fn lint(parsed: &ParsedFile, options: &LintOptions) -> Vec<FileDiagnostic> {
+    let mut linter = Linter {
+        parsed,
+        options,
+        diagnostics: Vec::new(),
+    };
+    if let Some(max) = options.max_file_size
+        && parsed.source.len() > max
+    {
+        linter.diagnostics.push(FileDiagnostic {
+            code: "lint(max-file-size)".into(),
+            message: format!(
+                "file is {} bytes, over the limit of {max}",
+                parsed.source.len()
+            ),
+            severity: Severity::Error,
+            span: None,
+            location: None,
+            label: None,
+            help: None,
+            schema_path: None,
+            related: vec![],
+        });
+    }
+    linter.visit(&parsed.ast, 1);
+    linter.diagnostics
+}
+
+struct Linter<'a> {
+    parsed: &'a ParsedFile<'a>,
+    options: &'a LintOptions,
+    diagnostics: Vec<FileDiagnostic>,
+}
+
+impl Linter<'_> {
+    /// Check `value`, an object or array at nesting level `depth` (the root
+    /// is 1), and everything below it.
+    fn visit(&mut self, value: &AstValue, depth: usize) {
+        let (is_empty, kind, disallow_empty) = match value {
+            AstValue::Object(obj) => (
+                obj.properties.is_empty(),
+                "object",
+                self.options.no_empty_objects,
+            ),
+            AstValue::Array(arr) => (
+                arr.elements.is_empty(),
+                "array",
+                self.options.no_empty_arrays,
+            ),
+            _ => return,
+        };
+        let range = value.range();
+        let range = range.start..range.end;
+
+        if let Some(max) = self.options.max_depth
+            && depth > max
+        {
+            // Report the outermost value that is too deep, not everything in it.
+            self.push(
+                "lint(max-depth)",
+                format!("nesting depth {depth} exceeds the maximum of {max}"),
+                range,
+                format!("nested {depth} levels deep"),
+            );
+            return;
+        }
+        if is_empty && disallow_empty {
+            self.push(
+                &format!("lint(empty-{kind})"),
+                format!("empty {kind}"),
+                range,
+                format!("empty {kind}"),
+            );
+        }
+
+        match value {
+            AstValue::Object(obj) => {
+                let mut seen: HashMap<&str, Range<usize>> = HashMap::new();
+                for prop in &obj.properties {
+                    let name = prop.name.as_str();
+                    let key = prop.name.range();
+                    let key = key.start..key.end;
+                    if let Some(first) = seen.get(name) {
+                        let mut diagnostic = self.diagnostic(
+                            "lint(duplicate-key)",
+                            format!("duplicate key \"{name}\""),
+                            key.clone(),
+                            "duplicate key".into(),
+                        );
+                        diagnostic.help = Some(
+                            "Only the last value is used; the earlier ones are ignored.".into(),
+                        );
+                        diagnostic.related.push(RelatedInfo {
+                            message: "first defined here".into(),
+                            path: None,
+                            span: Some(first.clone()),
+                            location: Some(self.location(first)),
+                        });
+                        self.diagnostics.push(diagnostic);
+                    } else {
+                        seen.insert(name, key.clone());
+                    }
+                    if let Some(case) = self.options.key_case
+                        && !case.matches(name)
+                    {
+                        self.push(
+                            "lint(key-case)",
+                            format!("key \"{name}\" is not {case}"),
+                            key,
+                            format!("expected {case}"),
+                        );
+                    }
+                    self.visit(&prop.value, depth + 1);
+                }
+            }
+            AstValue::Array(arr) => {
+                for element in &arr.elements {
+                    self.visit(element, depth + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn push(&mut self, code: &str, message: String, span: Range<usize>, label: String) {
+        let diagnostic = self.diagnostic(code, message, span, label);
+        self.diagnostics.push(diagnostic);
+    }
+
+    fn diagnostic(
+        &self,
+        code: &str,
+        message: String,
+        span: Range<usize>,
+        label: String,
+    ) -> FileDiagnostic {
+        FileDiagnostic {
+            code: code.into(),
+            message,
+            severity: Severity::Error,
+            location: Some(self.location(&span)),
+            span: Some(span),
+            label: Some(label),
+            help: None,
+            schema_path: None,
+            related: vec![],
+        }
+    }
+
+    fn location(&self, span: &Range<usize>) -> SourceLocation {
+        let (line, column) = self.parsed.offset_to_line_col(span.start);
+        SourceLocation {
+            line,
+            column,
+            offset: span.start,
+            length: span.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_cases() {
+        let cases = [
+            ("fooBar", [true, false, false]),
+            ("foo", [true, true, true]),
+            ("foo_bar", [false, true, false]),
+            ("foo-bar2", [false, false, true]),
+            ("FooBar", [false, false, false]),
+            ("foo__bar", [false, false, false]),
+            ("$schema", [true, true, true]),
+        ];
+        for (key, expected) in cases {
+            let actual = [KeyCase::Camel, KeyCase::Snake, KeyCase::Kebab].map(|c| c.matches(key));
+            assert_eq!(actual, expected, "{key}");
+        }
+    }
+
+    #[test]
+    fn reports_duplicates_and_depth() {
+        let source = r#"{ "a": 1, "b": { "c": [[]] }, "a": 2 }"#;
+        let parsed = crate::parse::parse_jsonc(source).unwrap();
+        let options = LintOptions {
+            max_depth: Some(3),
+            ..Default::default()
+        };
+        let codes: Vec<(String, &str)> = lint(&parsed, &options)
+            .into_iter()
+            .map(|d| {
+                let span = d.span.unwrap();
+                (d.code, &source[span])
+            })
+            .collect();
+        assert_eq!(
+            codes,
+            [
+                ("lint(max-depth)".to_string(), "[]"),
+                ("lint(duplicate-key)".to_string(), r#""a""#),
+            ]
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use std::borrow::Cow;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
@@ -10,43 +10,340 @@ use tower_lsp_server::jsonrpc::Result;
 use tower_lsp_server::ls_types::*;
 use tower_lsp_server::{Client, LanguageServer, LspService, Server};
 
-use crate::diagnostic::{FileDiagnostic, Severity};
-use crate::discover::{self, CompiledFileFilter, CompiledSchemaMappings, Config};
+use crate::crossfile::{CrossFileIndex, CrossFileRules, Definition};
+use crate::diagnostic::{FileDiagnostic, Severity, SeverityLevel, SourceLocation};
+use crate::discover::{
+    self, CompiledFileFilter, CompiledParsers, CompiledSchemaMappings, Config, SchemaMapping,
+};
+use crate::embed;
+use crate::lint::{CompiledLint, LintOptions};
 use crate::parse;
-use crate::schema::{self, SchemaCache, SchemaSource};
+use crate::schema::{self, CompileOptions, SchemaCache, SchemaSource};
 use crate::validate;
 
 /// Compiled jvl.json config with resolved schema mappings.
 struct CompiledConfig {
     mappings: CompiledSchemaMappings,
+    parsers: CompiledParsers,
+    lint: CompiledLint,
+    severity: BTreeMap<String, SeverityLevel>,
     project_root: PathBuf,
     strict: bool,
     file_filter: CompiledFileFilter,
+    cross_file: CrossFileRules,
+    /// What the cross-file rules know about the project's files. Built on
+    /// first use, then kept current from open documents and file watcher
+    /// events; a config reload starts over with a new one.
+    cross_file_index: Mutex<Option<CrossFileIndex>>,
+    compile_options: CompileOptions,
+    /// Normalized paths of the config file and every local file it extends.
+    sources: Vec<PathBuf>,
+}
+
+impl CompiledConfig {
+    /// Run `f` on the cross-file index, building it first if needed from the
+    /// files on disk, with open documents read from `documents` instead.
+    fn with_index<T>(
+        &self,
+        documents: &Mutex<HashMap<Uri, DocumentState>>,
+        f: impl FnOnce(&mut CrossFileIndex) -> T,
+    ) -> T {
+        let mut index = self
+            .cross_file_index
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let index = index.get_or_insert_with(|| {
+            let mut index = self.cross_file.index(&self.project_root, None);
+            let open: Vec<(PathBuf, Arc<String>)> = documents
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .iter()
+                .filter_map(|(uri, state)| {
+                    Some((uri.to_file_path()?.into_owned(), state.content.clone()))
+                })
+                .collect();
+            for (path, content) in open {
+                if let Some(relative) = self.indexed_path(&path)
+                    && let Some(parsed) = self.cross_file.parse(&relative, &content)
+                {
+                    self.cross_file.update(&mut index, &relative, Some(&parsed));
+                }
+            }
+            index
+        });
+        f(index)
+    }
+
+    /// Update the cross-file index, if built, for files changed on disk.
+    /// Open documents are skipped; their content in the editor wins.
+    /// Returns whether checks of other files may have a different outcome.
+    fn reindex(&self, changed: &[PathBuf], open: &HashSet<PathBuf>) -> bool {
+        let mut index = self
+            .cross_file_index
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let Some(index) = index.as_mut() else {
+            return false;
+        };
+        let mut outcome_changed = false;
+        for path in changed {
+            if open.contains(path) {
+                continue;
+            }
+            let Some(relative) = self.indexed_path(path) else {
+                continue;
+            };
+            let content = std::fs::read_to_string(path).ok();
+            let parsed = content
+                .as_deref()
+                .and_then(|content| self.cross_file.parse(&relative, content));
+            outcome_changed |= self.cross_file.update(index, &relative, parsed.as_ref());
+        }
+        outcome_changed
+    }
+
+    /// `path` relative to the project root, if the cross-file index reads
+    /// anything from it.
+    fn indexed_path(&self, path: &Path) -> Option<String> {
+        if embed::is_host_file(path) {
+            return None;
+        }
+        // A deleted file can't be canonicalized; its path is kept as given.
+        let canonical = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let relative = canonical
+            .strip_prefix(&self.project_root)
+            .ok()?
+            .to_string_lossy()
+            .replace('\\', "/");
+        self.cross_file.indexes(&relative).then_some(relative)
+    }
 }
 
 /// Result of resolving config + schema for a single document.
 struct ResolvedDocument {
     schema_source: Option<SchemaSource>,
+    /// Schema from the editor's mappings, used only when neither jvl.json nor
+    /// the document's `$schema` provides one.
+    fallback_schema: Option<SchemaSource>,
     strict: bool,
+    dialect: parse::Dialect,
+    lint: LintOptions,
+    severity: BTreeMap<String, SeverityLevel>,
+    compile_options: CompileOptions,
     config_log: Option<String>,
+    /// The document's config and path within its project, when the config
+    /// declares cross-file rules that apply to it.
+    cross_file: Option<CrossFileDocument>,
+}
+
+/// Cross-file rules that apply to one document.
+struct CrossFileDocument {
+    config: Arc<CompiledConfig>,
+    relative: String,
+}
+
+impl CrossFileDocument {
+    /// Parse the document in the dialect its config gives it.
+    fn parse<'a>(&self, content: &'a str) -> Option<parse::ParsedFile<'a>> {
+        self.config.cross_file.parse(&self.relative, content)
+    }
+
+    /// Record the document's content in the cross-file index and check it
+    /// against the other files of its project. Also returns whether checks
+    /// of other documents may now have a different outcome.
+    fn check(
+        &self,
+        parsed: &parse::ParsedFile,
+        documents: &Mutex<HashMap<Uri, DocumentState>>,
+    ) -> (Vec<FileDiagnostic>, bool) {
+        let rules = &self.config.cross_file;
+        self.config.with_index(documents, |index| {
+            let changed = rules.update(index, &self.relative, Some(parsed));
+            (rules.check(index, &self.relative, parsed), changed)
+        })
+    }
+
+    /// IDs defined in the project that are valid references at `pointer`.
+    fn completions(
+        &self,
+        pointer: &[String],
+        documents: &Mutex<HashMap<Uri, DocumentState>>,
+    ) -> Vec<Definition> {
+        let references = &self.config.cross_file.references;
+        self.config.with_index(documents, |index| {
+            references.completions(&index.references, &self.relative, pointer)
+        })
+    }
 }
 
 impl ResolvedDocument {
     fn skip() -> Self {
         Self {
             schema_source: None,
+            fallback_schema: None,
             strict: false,
+            dialect: parse::Dialect::Jsonc,
+            lint: LintOptions::default(),
+            severity: BTreeMap::new(),
+            compile_options: CompileOptions::default(),
             config_log: None,
+            cross_file: None,
         }
     }
 
     fn error(msg: String) -> Self {
         Self {
             schema_source: None,
+            fallback_schema: None,
             strict: false,
+            dialect: parse::Dialect::Jsonc,
+            lint: LintOptions::default(),
+            severity: BTreeMap::new(),
+            compile_options: CompileOptions::default(),
             config_log: Some(msg),
+            cross_file: None,
         }
     }
+
+    /// The schema to pass to `validate_file` as an explicit source. `None`
+    /// lets it fall back to the document's own `$schema`.
+    fn explicit_schema(&self, value: Option<&serde_json::Value>) -> Option<SchemaSource> {
+        self.schema_source.clone().or_else(|| {
+            let has_schema_field = value.is_some_and(|v| parse::extract_schema_field(v).is_some());
+            if has_schema_field {
+                None
+            } else {
+                self.fallback_schema.clone()
+            }
+        })
+    }
+}
+
+/// Default cap on concurrent `spawn_blocking` validations.
+const DEFAULT_MAX_VALIDATIONS: usize = 8;
+/// Default cap on concurrent `spawn_blocking` hover/completion requests.
+const DEFAULT_MAX_REQUESTS: usize = 4;
+
+/// Settings supplied by the editor through `initializationOptions` or
+/// `workspace/didChangeConfiguration`.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct Settings {
+    /// Delay between the last edit and re-validation, in milliseconds.
+    debounce_ms: u64,
+    /// Report documents without a schema. Combined with jvl.json's `strict`.
+    strict: bool,
+    /// Extra schema mappings, matched against paths relative to the
+    /// workspace folder. Lower priority than jvl.json and `$schema`.
+    schemas: Vec<SchemaMapping>,
+    /// Never fetch schemas over the network.
+    offline: bool,
+    /// Language features to turn off.
+    disable: Vec<Feature>,
+    /// Concurrent validations. Only read from `initializationOptions`.
+    max_concurrent_validations: usize,
+    /// Concurrent hover/completion requests. Only read from
+    /// `initializationOptions`.
+    max_concurrent_requests: usize,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            debounce_ms: 200,
+            strict: false,
+            schemas: vec![],
+            offline: false,
+            disable: vec![],
+            max_concurrent_validations: DEFAULT_MAX_VALIDATIONS,
+            max_concurrent_requests: DEFAULT_MAX_REQUESTS,
+        }
+    }
+}
+
+/// A language feature that can be turned off in [`Settings`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Feature {
+    Diagnostics,
+    Hover,
+    Completion,
+}
+
+/// [`Settings`] with the schema mappings compiled, plus the workspace
+/// folders they are resolved against.
+#[derive(Default)]
+struct EditorConfig {
+    settings: Settings,
+    mappings: CompiledSchemaMappings,
+    workspace_roots: Vec<PathBuf>,
+}
+
+impl EditorConfig {
+    /// Parse settings from the editor. Accepts either the settings object
+    /// itself or an object with a `jvl` section; `null` means defaults.
+    ///
+    /// Settings this version doesn't know are ignored, so an editor
+    /// extension can be newer than the server. Also returns the names of
+    /// unknown features in `disable`, which are skipped.
+    fn load(
+        value: serde_json::Value,
+        workspace_roots: Vec<PathBuf>,
+    ) -> std::result::Result<(Self, Vec<String>), String> {
+        let mut value = match value {
+            serde_json::Value::Object(mut obj) if obj.contains_key("jvl") => {
+                obj.remove("jvl").unwrap()
+            }
+            value => value,
+        };
+        let mut unknown_features = Vec::new();
+        if let Some(serde_json::Value::Array(disable)) = value.get_mut("disable") {
+            disable.retain(|feature| {
+                let known = serde_json::from_value::<Feature>(feature.clone()).is_ok();
+                if !known {
+                    unknown_features.push(feature.to_string());
+                }
+                known
+            });
+        }
+        let settings: Settings = if value.is_null() {
+            Settings::default()
+        } else {
+            serde_json::from_value(value).map_err(|e| e.to_string())?
+        };
+        let mappings =
+            CompiledSchemaMappings::from_mappings(&settings.schemas).map_err(|e| e.to_string())?;
+        let editor = Self {
+            settings,
+            mappings,
+            workspace_roots,
+        };
+        Ok((editor, unknown_features))
+    }
+
+    fn is_enabled(&self, feature: Feature) -> bool {
+        !self.settings.disable.contains(&feature)
+    }
+
+    /// Resolve an editor schema mapping for a document. Globs are matched
+    /// against the path relative to the containing workspace folder, or to
+    /// the document's directory if it is outside every workspace folder.
+    fn resolve(&self, path: &Path) -> Option<SchemaSource> {
+        let canonical = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let (root, relative) = self
+            .workspace_roots
+            .iter()
+            .find_map(|root| Some((root.as_path(), canonical.strip_prefix(root).ok()?)))
+            .or_else(|| Some((canonical.parent()?, Path::new(canonical.file_name()?))))?;
+        self.mappings.resolve(&relative.to_string_lossy(), root)
+    }
+
+    /// Apply these settings on top of the jvl.json resolution for a document.
+    fn apply(&self, path: &Path, resolved: &mut ResolvedDocument) {
+        resolved.strict |= self.settings.strict;
+        resolved.compile_options.offline = self.settings.offline;
+        resolved.fallback_schema = self.resolve(path);
+    }
 }
 
 /// Per-document state tracked by the LSP backend.
@@ -62,6 +359,10 @@ struct DocumentState {
     /// 2. **Stale fallback**: when the current text is malformed, the cached
     ///    value from the last successful parse is used for completions.
     last_good_parse: Option<(Arc<String>, Arc<serde_json::Value>)>,
+    /// Diagnostics last published for this document, with the version they
+    /// were computed for. Edits confined to whitespace or comments shift these
+    /// instead of re-validating.
+    published: Option<(i32, Vec<FileDiagnostic>)>,
 }
 
 /// LSP server backend.
@@ -80,14 +381,19 @@ pub struct Backend {
     request_semaphore: Arc<Semaphore>,
     /// True if the client negotiated UTF-8 position encoding; false = UTF-16 (default).
     utf8_positions: Arc<AtomicBool>,
-    /// Schema file paths for which we have registered file watchers.
-    watched_schema_paths: Arc<Mutex<HashSet<PathBuf>>>,
+    /// Schema and extended config file paths for which we have registered
+    /// file watchers.
+    watched_paths: Arc<Mutex<HashSet<PathBuf>>>,
     /// Monotonically increasing counter for unique watcher registration IDs.
     next_reg_id: Arc<AtomicU64>,
     /// True if the client supports Markdown in hover content.
     hover_markdown: Arc<AtomicBool>,
     /// True if the client supports snippet syntax in completion insertText.
     snippet_support: Arc<AtomicBool>,
+    /// True if the client answers `workspace/configuration` requests.
+    configuration_support: Arc<AtomicBool>,
+    /// Settings from the editor, replaced wholesale on every change.
+    editor: Arc<Mutex<Arc<EditorConfig>>>,
 }
 
 impl std::fmt::Debug for Backend {
@@ -103,23 +409,111 @@ impl Backend {
             documents: Arc::new(Mutex::new(HashMap::new())),
             config_cache: Arc::new(Mutex::new(HashMap::new())),
             schema_cache: Arc::new(SchemaCache::new()),
-            validation_semaphore: Arc::new(Semaphore::new(8)),
-            request_semaphore: Arc::new(Semaphore::new(4)),
+            validation_semaphore: Arc::new(Semaphore::new(DEFAULT_MAX_VALIDATIONS)),
+            request_semaphore: Arc::new(Semaphore::new(DEFAULT_MAX_REQUESTS)),
             utf8_positions: Arc::new(AtomicBool::new(false)),
-            watched_schema_paths: Arc::new(Mutex::new(HashSet::new())),
+            watched_paths: Arc::new(Mutex::new(HashSet::new())),
             next_reg_id: Arc::new(AtomicU64::new(0)),
             hover_markdown: Arc::new(AtomicBool::new(true)),
             snippet_support: Arc::new(AtomicBool::new(false)),
+            configuration_support: Arc::new(AtomicBool::new(false)),
+            editor: Arc::new(Mutex::new(Arc::new(EditorConfig::default()))),
+        }
+    }
+
+    /// Snapshot the current editor settings.
+    fn editor_config(&self) -> Arc<EditorConfig> {
+        self.editor
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Replace the editor settings and re-validate (or clear) every open
+    /// document. Invalid settings are logged and ignored.
+    async fn apply_settings(&self, value: serde_json::Value) {
+        let workspace_roots = self.editor_config().workspace_roots.clone();
+        let editor = match EditorConfig::load(value, workspace_roots) {
+            Ok((editor, unknown_features)) => {
+                self.log_unknown_features(&unknown_features).await;
+                Arc::new(editor)
+            }
+            Err(e) => {
+                self.client
+                    .log_message(MessageType::WARNING, format!("jvl: invalid settings: {e}"))
+                    .await;
+                return;
+            }
+        };
+        *self.editor.lock().unwrap_or_else(|e| e.into_inner()) = Arc::clone(&editor);
+
+        let uris: Vec<Uri> = {
+            let mut docs = self.documents.lock().unwrap_or_else(|e| e.into_inner());
+            for state in docs.values_mut() {
+                state.published = None;
+            }
+            docs.keys().cloned().collect()
+        };
+        for uri in uris {
+            if editor.is_enabled(Feature::Diagnostics) {
+                self.spawn_validation(uri);
+            } else {
+                self.client.publish_diagnostics(uri, vec![], None).await;
+            }
+        }
+    }
+
+    /// Tell the user which entries of `disable` were skipped.
+    async fn log_unknown_features(&self, unknown_features: &[String]) {
+        if unknown_features.is_empty() {
+            return;
+        }
+        self.client
+            .log_message(
+                MessageType::WARNING,
+                format!(
+                    "jvl: ignoring unknown features in disable: {}",
+                    unknown_features.join(", ")
+                ),
+            )
+            .await;
+    }
+
+    /// Pull the `jvl` section via `workspace/configuration`, if the client
+    /// supports it. Returns `None` if unsupported, failed, or unset.
+    async fn pull_settings(&self) -> Option<serde_json::Value> {
+        if !self.configuration_support.load(Ordering::Relaxed) {
+            return None;
+        }
+        let item = ConfigurationItem {
+            scope_uri: None,
+            section: Some("jvl".to_string()),
+        };
+        match self.client.configuration(vec![item]).await {
+            Ok(values) => values.into_iter().next().filter(|v| !v.is_null()),
+            Err(e) => {
+                self.client
+                    .log_message(
+                        MessageType::WARNING,
+                        format!("jvl: failed to fetch settings ({e})"),
+                    )
+                    .await;
+                None
+            }
         }
     }
 
-    /// Fire-and-forget task: debounce 200ms, validate, publish diagnostics.
+    /// Fire-and-forget task: debounce (200ms by default), validate, publish diagnostics.
     ///
     /// Captures `spawn_version` at spawn time so that — after the debounce sleep —
     /// the task can detect whether a newer edit arrived and self-cancel. This prevents
     /// multiple concurrent tasks (spawned by rapid edits) from all publishing diagnostics
     /// after the debounce window expires.
     fn spawn_validation(&self, uri: Uri) {
+        if !self.editor_config().is_enabled(Feature::Diagnostics) {
+            return;
+        }
+
         // Capture the version at spawn time.  If a newer edit arrives before this task
         // wakes up, `current_version` will differ from `spawn_version` and the task discards.
         let spawn_version = {
@@ -139,7 +533,7 @@ impl Backend {
         });
     }
 
-    /// Debounced validation task. Sleeps 200ms, snapshots content+version atomically,
+    /// Debounced validation task. Sleeps for the configured debounce, snapshots content+version atomically,
     /// validates in `spawn_blocking`, then publishes diagnostics if the version is current.
     ///
     /// `spawn_version` is the document version at the time this task was spawned.
@@ -147,7 +541,8 @@ impl Backend {
     /// differ, a newer edit superseded this task and it self-cancels.
     async fn validate_and_publish(&self, uri: Uri, spawn_version: i32) {
         // 1. Debounce: wait for typing to settle.
-        tokio::time::sleep(Duration::from_millis(200)).await;
+        let editor = self.editor_config();
+        tokio::time::sleep(Duration::from_millis(editor.settings.debounce_ms)).await;
 
         // 2. Limit concurrent blocking validations.
         let Ok(_permit) = self.validation_semaphore.acquire().await else {
@@ -186,6 +581,8 @@ impl Backend {
         let config_cache_clone = Arc::clone(&self.config_cache);
         let content_clone = content.clone();
         let file_path_clone = file_path.clone();
+        let editor_clone = Arc::clone(&editor);
+        let documents = Arc::clone(&self.documents);
 
         let result = tokio::task::spawn_blocking(move || {
             // Try to parse for the stale value cache (cheap relative to validation).
@@ -193,22 +590,51 @@ impl Backend {
                 .ok()
                 .map(|p| Arc::new(p.value));
 
-            let resolved = resolve_schema_for_document(&file_path_clone, &config_cache_clone);
+            let resolved = resolve_document(&file_path_clone, &config_cache_clone, &editor_clone);
+            let schema_source = resolved.explicit_schema(parsed_value.as_deref());
 
-            let validate_result = validate::validate_file(
+            let mut validate_result = validate::validate_file(
                 &path_str,
                 &content_clone,
-                resolved.schema_source.as_ref(),
+                schema_source.as_ref(),
                 &schema_cache_clone,
+                &resolved.compile_options,
                 false, // no_cache: always use disk cache in LSP mode
-                resolved.strict,
+                validate::FileOptions {
+                    dialect: resolved.dialect,
+                    strict: resolved.strict,
+                    check_syntax: false,
+                    lint: resolved.lint,
+                },
             );
 
-            (validate_result, resolved.config_log, parsed_value)
+            let mut index_changed = false;
+            if let Some(cross_file) = &resolved.cross_file
+                && let Some(parsed) = cross_file.parse(&content_clone)
+            {
+                let (diagnostics, changed) = cross_file.check(&parsed, &documents);
+                validate_result.0.errors.extend(diagnostics);
+                index_changed = changed;
+            }
+            validate_result.0.apply_severities(&resolved.severity);
+
+            (
+                validate_result,
+                resolved.config_log,
+                parsed_value,
+                index_changed,
+                resolved.lint.sees_trivia(),
+            )
         })
         .await;
 
-        let ((file_result, warnings, _, _), config_log, parsed_value) = match result {
+        let (
+            (file_result, warnings, _, _),
+            config_log,
+            parsed_value,
+            index_changed,
+            lint_sees_trivia,
+        ) = match result {
             Ok(r) => r,
             Err(e) => {
                 self.client
@@ -221,6 +647,22 @@ impl Backend {
             }
         };
 
+        // Other documents may reference IDs this one just (un)defined, or
+        // share values with it.
+        if index_changed {
+            let others: Vec<Uri> = self
+                .documents
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .keys()
+                .filter(|other| **other != uri)
+                .cloned()
+                .collect();
+            for other in others {
+                self.spawn_validation(other);
+            }
+        }
+
         // Log config errors and cache warnings to the editor output panel.
         if let Some(msg) = config_log {
             self.client.log_message(MessageType::WARNING, msg).await;
@@ -240,7 +682,9 @@ impl Backend {
                 .get(&uri)
                 .map(|state| state.version == version)
                 .unwrap_or(false);
-            if !still_current {
+            // Diagnostics may have been turned off while validating.
+            let enabled = self.editor_config().is_enabled(Feature::Diagnostics);
+            if !still_current || !enabled {
                 return;
             }
             if let Some(value) = parsed_value
@@ -254,18 +698,98 @@ impl Backend {
         //    Compute line starts once for the full document, then pass to each converter.
         let utf8 = self.utf8_positions.load(Ordering::Relaxed);
         let line_starts = parse::compute_line_starts(&content);
+        let others = self.related_file_texts(&file_result.errors);
         let diagnostics: Vec<Diagnostic> = file_result
             .errors
             .iter()
-            .map(|d| file_diagnostic_to_lsp(d, &content, &line_starts, utf8))
+            .map(|d| file_diagnostic_to_lsp(d, &uri, &content, &line_starts, &others, utf8))
             .collect();
 
         self.client
-            .publish_diagnostics(uri, diagnostics, None)
+            .publish_diagnostics(uri.clone(), diagnostics, None)
             .await;
 
-        // Register file watchers for any newly discovered schema files.
-        self.update_schema_watchers().await;
+        // 10. Remember what was published so trivia-only edits can reuse it,
+        //     unless a lint rule such as `maxFileSize` sees trivia too.
+        //     Recorded after publishing: an edit arriving in between sees no
+        //     record for its base version and falls back to re-validating.
+        if !lint_sees_trivia {
+            let mut docs = self.documents.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(state) = docs.get_mut(&uri)
+                && state.version == version
+            {
+                state.published = Some((version, file_result.errors));
+            }
+        }
+
+        // Register file watchers for any newly discovered schema or config files.
+        self.update_file_watchers().await;
+    }
+
+    /// Update the cross-file indexes of every cached config for files changed
+    /// on disk. Returns whether open documents need re-validating.
+    async fn reindex(&self, changed: Vec<PathBuf>) -> bool {
+        let configs: Vec<Arc<CompiledConfig>> = self
+            .config_cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .cloned()
+            .collect();
+        let open: HashSet<PathBuf> = self
+            .documents
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .keys()
+            .filter_map(|uri| uri.to_file_path().map(Cow::into_owned))
+            .collect();
+        tokio::task::spawn_blocking(move || {
+            // Every config's index needs the update, so don't stop at the
+            // first that changed.
+            let mut changed_any = false;
+            for config in &configs {
+                changed_any |= config.reindex(&changed, &open);
+            }
+            changed_any
+        })
+        .await
+        .unwrap_or(false)
+    }
+
+    /// Re-validate every open document.
+    fn revalidate_all(&self) {
+        let uris: Vec<Uri> = self
+            .documents
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .keys()
+            .cloned()
+            .collect();
+        for uri in uris {
+            self.spawn_validation(uri);
+        }
+    }
+
+    /// The text of every other file the notes on `diagnostics` point into,
+    /// keyed by path: the open document's, or else the file's on disk.
+    fn related_file_texts(&self, diagnostics: &[FileDiagnostic]) -> HashMap<String, Arc<String>> {
+        let mut texts = HashMap::new();
+        for path in diagnostics
+            .iter()
+            .flat_map(|d| &d.related)
+            .filter_map(|r| r.path.as_ref())
+        {
+            if texts.contains_key(path) {
+                continue;
+            }
+            let text = Uri::from_file_path(path)
+                .and_then(|uri| self.snapshot_document(&uri))
+                .or_else(|| std::fs::read_to_string(path).ok().map(Arc::new));
+            if let Some(text) = text {
+                texts.insert(path.clone(), text);
+            }
+        }
+        texts
     }
 
     /// Snapshot the current document text for the given URI.
@@ -274,33 +798,56 @@ impl Backend {
         docs.get(uri).map(|state| state.content.clone())
     }
 
-    /// Register file watchers for newly discovered schema file paths.
+    /// Register file watchers for newly discovered schema and config paths,
+    /// and for the files cross-file indexes read.
     ///
-    /// Queries the schema cache for all `SchemaSource::File` entries and
-    /// registers a watcher for each path not already being watched.
-    async fn update_schema_watchers(&self) {
-        let new_paths: Vec<PathBuf> = {
-            let mut watched = self
-                .watched_schema_paths
-                .lock()
-                .unwrap_or_else(|e| e.into_inner());
+    /// Queries the schema cache for all `SchemaSource::File` entries, the
+    /// config cache for every file in each `extends` chain and the
+    /// [watch globs](CrossFileRules::watch_globs) of configs with cross-file
+    /// rules, and registers a watcher for each one not already being watched.
+    async fn update_file_watchers(&self) {
+        let (config_sources, cross_file_globs): (Vec<PathBuf>, Vec<(PathBuf, String)>) = {
+            let cache = self.config_cache.lock().unwrap_or_else(|e| e.into_inner());
+            let sources = cache
+                .values()
+                .flat_map(|c| c.sources.iter().cloned())
+                .collect();
+            let globs = cache
+                .values()
+                .flat_map(|c| {
+                    c.cross_file.watch_globs().iter().map(|glob| {
+                        let root =
+                            escape_glob_metacharacters(&c.project_root.display().to_string());
+                        (c.project_root.join(glob), format!("{root}/{glob}"))
+                    })
+                })
+                .collect();
+            (sources, globs)
+        };
+        let new_patterns: Vec<String> = {
+            let mut watched = self.watched_paths.lock().unwrap_or_else(|e| e.into_inner());
             self.schema_cache
                 .cached_file_paths()
                 .into_iter()
-                .filter(|p| watched.insert(p.clone()))
+                .chain(config_sources)
+                .map(|p| {
+                    let pattern = escape_glob_metacharacters(&p.display().to_string());
+                    (p, pattern)
+                })
+                .chain(cross_file_globs)
+                .filter(|(p, _)| watched.insert(p.clone()))
+                .map(|(_, pattern)| pattern)
                 .collect()
         };
 
-        if new_paths.is_empty() {
+        if new_patterns.is_empty() {
             return;
         }
 
-        let watchers: Vec<FileSystemWatcher> = new_paths
-            .iter()
-            .map(|p| FileSystemWatcher {
-                glob_pattern: GlobPattern::String(escape_glob_metacharacters(
-                    &p.display().to_string(),
-                )),
+        let watchers: Vec<FileSystemWatcher> = new_patterns
+            .into_iter()
+            .map(|pattern| FileSystemWatcher {
+                glob_pattern: GlobPattern::String(pattern),
                 kind: Some(WatchKind::Create | WatchKind::Change | WatchKind::Delete),
             })
             .collect();
@@ -360,6 +907,57 @@ impl LanguageServer for Backend {
             self.snippet_support.store(true, Ordering::Relaxed);
         }
 
+        self.configuration_support.store(
+            params
+                .capabilities
+                .workspace
+                .as_ref()
+                .and_then(|w| w.configuration)
+                .unwrap_or(false),
+            Ordering::Relaxed,
+        );
+
+        // Workspace folders anchor the editor's schema mappings.
+        #[allow(deprecated)]
+        let root_uris: Vec<Uri> = match params.workspace_folders {
+            Some(folders) => folders.into_iter().map(|f| f.uri).collect(),
+            None => params.root_uri.into_iter().collect(),
+        };
+        let workspace_roots = root_uris
+            .iter()
+            .filter_map(|uri| uri.to_file_path())
+            .map(|p| std::fs::canonicalize(&p).unwrap_or_else(|_| p.into_owned()))
+            .collect();
+
+        let options = params.initialization_options.unwrap_or_default();
+        let editor = match EditorConfig::load(options, workspace_roots) {
+            Ok((editor, unknown_features)) => {
+                self.log_unknown_features(&unknown_features).await;
+                editor
+            }
+            Err(e) => {
+                self.client
+                    .log_message(
+                        MessageType::WARNING,
+                        format!("jvl: invalid initializationOptions: {e}"),
+                    )
+                    .await;
+                EditorConfig::default()
+            }
+        };
+        // Nothing holds a permit yet, so the semaphores can be resized exactly.
+        resize_semaphore(
+            &self.validation_semaphore,
+            DEFAULT_MAX_VALIDATIONS,
+            editor.settings.max_concurrent_validations,
+        );
+        resize_semaphore(
+            &self.request_semaphore,
+            DEFAULT_MAX_REQUESTS,
+            editor.settings.max_concurrent_requests,
+        );
+        *self.editor.lock().unwrap_or_else(|e| e.into_inner()) = Arc::new(editor);
+
         let position_encoding = if utf8 {
             PositionEncodingKind::UTF8
         } else {
@@ -374,7 +972,7 @@ impl LanguageServer for Backend {
             capabilities: ServerCapabilities {
                 position_encoding: Some(position_encoding),
                 text_document_sync: Some(TextDocumentSyncCapability::Kind(
-                    TextDocumentSyncKind::FULL,
+                    TextDocumentSyncKind::INCREMENTAL,
                 )),
                 hover_provider: Some(HoverProviderCapability::Simple(true)),
                 completion_provider: Some(CompletionOptions {
@@ -388,17 +986,22 @@ impl LanguageServer for Backend {
     }
 
     async fn initialized(&self, _: InitializedParams) {
-        // Register a file watcher for jvl.json so we invalidate the config
-        // cache when the user edits their project config.
+        // Register file watchers for every config file name so we invalidate
+        // the config cache when the user edits their project config. The
+        // files cross-file indexes read are watched once a config with such
+        // rules is loaded.
         let registration = Registration {
             id: "jvl-config-watch".to_string(),
             method: "workspace/didChangeWatchedFiles".to_string(),
             register_options: Some(
                 serde_json::to_value(DidChangeWatchedFilesRegistrationOptions {
-                    watchers: vec![FileSystemWatcher {
-                        glob_pattern: GlobPattern::String("**/jvl.json".to_string()),
-                        kind: Some(WatchKind::Create | WatchKind::Change | WatchKind::Delete),
-                    }],
+                    watchers: discover::CONFIG_FILE_NAMES
+                        .iter()
+                        .map(|name| FileSystemWatcher {
+                            glob_pattern: GlobPattern::String(format!("**/{name}")),
+                            kind: Some(WatchKind::Create | WatchKind::Change | WatchKind::Delete),
+                        })
+                        .collect(),
                 })
                 .unwrap(),
             ),
@@ -415,6 +1018,30 @@ impl LanguageServer for Backend {
                 )
                 .await;
         }
+
+        // Pull-model clients provide settings via `workspace/configuration`.
+        if let Some(settings) = self.pull_settings().await {
+            self.apply_settings(settings).await;
+        }
+    }
+
+    async fn did_change_configuration(&self, params: DidChangeConfigurationParams) {
+        // Push-model clients send the settings; pull-model clients send an
+        // empty notification and expect us to ask for them.
+        let pushed = match params.settings {
+            serde_json::Value::Null => None,
+            serde_json::Value::Object(obj) if obj.is_empty() => None,
+            value => Some(value),
+        };
+        // No settings at all means they were removed: back to defaults.
+        let settings = match pushed {
+            Some(value) => value,
+            None => self
+                .pull_settings()
+                .await
+                .unwrap_or(serde_json::Value::Null),
+        };
+        self.apply_settings(settings).await;
     }
 
     async fn shutdown(&self) -> Result<()> {
@@ -447,6 +1074,7 @@ impl LanguageServer for Backend {
                     // relies on `Arc::ptr_eq` to detect content changes.
                     content: Arc::new(content),
                     last_good_parse: None,
+                    published: None,
                 },
             );
         }
@@ -457,20 +1085,80 @@ impl LanguageServer for Backend {
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
         let uri = params.text_document.uri;
         let version = params.text_document.version;
+        let utf8 = self.utf8_positions.load(Ordering::Relaxed);
 
-        // FULL sync: take the first (and only) content change.
-        let Some(content) = params.content_changes.into_iter().next().map(|c| c.text) else {
-            return;
-        };
-
-        {
+        let republish = {
             let mut docs = self.documents.lock().unwrap_or_else(|e| e.into_inner());
-            if let Some(state) = docs.get_mut(&uri) {
-                state.version = version;
-                // N.B. Always allocate a new Arc here; the completion cache
-                // relies on `Arc::ptr_eq` to detect content changes.
-                state.content = Arc::new(content);
+            let Some(state) = docs.get_mut(&uri) else {
+                return;
+            };
+
+            let mut content = state.content.as_str().to_owned();
+            // Diagnostics for the previous version, shifted along with each
+            // edit. Dropped as soon as an edit touches anything but trivia.
+            // Markdown isn't JSON, so whitespace there can still matter.
+            let is_host = uri
+                .to_file_path()
+                .is_some_and(|path| embed::is_host_file(&path));
+            let mut shifted = state
+                .published
+                .take()
+                .filter(|(published_version, _)| *published_version == state.version && !is_host)
+                .map(|(_, diagnostics)| diagnostics);
+
+            for change in params.content_changes {
+                let Some(range) = change.range else {
+                    // No range: the change carries the full new text.
+                    content = change.text;
+                    shifted = None;
+                    continue;
+                };
+                let line_starts = parse::compute_line_starts(&content);
+                let start = lsp_position_to_byte_offset(&content, &line_starts, range.start, utf8);
+                let end = lsp_position_to_byte_offset(&content, &line_starts, range.end, utf8);
+                let (Some(start), Some(end)) = (start, end) else {
+                    // Out-of-sync client; keep going with what we have and let
+                    // the next full validation report the actual state.
+                    shifted = None;
+                    continue;
+                };
+                let edit = start.min(end)..end.max(start);
+
+                if let Some(diagnostics) = &mut shifted {
+                    if parse::is_trivia_edit(&content, edit.clone(), &change.text) {
+                        shift_diagnostics(diagnostics, &edit, change.text.len());
+                    } else {
+                        shifted = None;
+                    }
+                }
+                content.replace_range(edit, &change.text);
             }
+
+            state.version = version;
+            // N.B. Always allocate a new Arc here; the completion cache
+            // relies on `Arc::ptr_eq` to detect content changes.
+            state.content = Arc::new(content);
+
+            shifted.map(|mut diagnostics| {
+                relocate_diagnostics(&mut diagnostics, &state.content);
+                state.published = Some((version, diagnostics.clone()));
+                (state.content.clone(), diagnostics)
+            })
+        };
+
+        // Only whitespace or comments changed: the previous result still
+        // holds, so republish it at the new positions without re-validating.
+        if let Some((content, diagnostics)) = republish {
+            let line_starts = parse::compute_line_starts(&content);
+            let others = self.related_file_texts(&diagnostics);
+            let diagnostics = diagnostics
+                .iter()
+                .map(|d| file_diagnostic_to_lsp(d, &uri, &content, &line_starts, &others, utf8))
+                .collect();
+            self.client
+                .publish_diagnostics(uri, diagnostics, None)
+                .await;
+            return;
         }
 
         self.spawn_validation(uri);
@@ -486,13 +1174,27 @@ impl LanguageServer for Backend {
             .remove(&uri);
 
         // Clear diagnostics for this document.
-        self.client.publish_diagnostics(uri, vec![], None).await;
+        self.client
+            .publish_diagnostics(uri.clone(), vec![], None)
+            .await;
+
+        // Unsaved edits are gone; cross-file indexes go back to the file on disk.
+        if let Some(path) = uri.to_file_path().map(Cow::into_owned)
+            && self.reindex(vec![path]).await
+        {
+            self.revalidate_all();
+        }
     }
 
     async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
         let uri = params.text_document_position_params.text_document.uri;
         let position = params.text_document_position_params.position;
 
+        let editor = self.editor_config();
+        if !editor.is_enabled(Feature::Hover) {
+            return Ok(None);
+        }
+
         // Shed load: drop request if too many are already in flight.
         let Ok(_permit) = self.request_semaphore.try_acquire() else {
             return Ok(None);
@@ -524,7 +1226,7 @@ impl LanguageServer for Backend {
 
             // 3d. Resolve schema value for this document.
             let schema_value =
-                resolve_schema_value(&uri, &parsed.value, &config_cache, &schema_cache)?;
+                resolve_schema_value(&uri, &parsed.value, &config_cache, &schema_cache, &editor)?;
 
             // 3e. Look up hover content from schema annotations.
             let hover_content = schema::lookup_hover_content(&schema_value, &pointer)?;
@@ -565,6 +1267,11 @@ impl LanguageServer for Backend {
         let uri = params.text_document_position.text_document.uri;
         let position = params.text_document_position.position;
 
+        let editor = self.editor_config();
+        if !editor.is_enabled(Feature::Completion) {
+            return Ok(None);
+        }
+
         // Shed load: drop request if too many are already in flight.
         let Ok(_permit) = self.request_semaphore.try_acquire() else {
             return Ok(None);
@@ -582,6 +1289,7 @@ impl LanguageServer for Backend {
         // 2. Clone shared state needed inside spawn_blocking.
         let config_cache = Arc::clone(&self.config_cache);
         let schema_cache = Arc::clone(&self.schema_cache);
+        let documents = Arc::clone(&self.documents);
         let utf8 = self.utf8_positions.load(Ordering::Relaxed);
         let snippet = self.snippet_support.load(Ordering::Relaxed);
         let markdown = self.hover_markdown.load(Ordering::Relaxed);
@@ -611,9 +1319,15 @@ impl LanguageServer for Backend {
             // 3c. Determine completion context via text scanning.
             let ctx = parse::completion_context(&content, byte_offset)?;
 
-            // 3d. Resolve schema value for this document.
+            // 3d. Resolve the document's schema and reference rules. Either
+            //     may be missing.
+            let file_path = uri.to_file_path().map(Cow::into_owned)?;
+            let resolved = resolve_document(&file_path, &config_cache, &editor);
             let schema_value =
-                resolve_schema_value(&uri, &parsed_value, &config_cache, &schema_cache)?;
+                document_schema_value(&resolved, &file_path, &parsed_value, &schema_cache);
+            if schema_value.is_none() && resolved.cross_file.is_none() {
+                return None;
+            }
 
             // 3e. Compute the replacement range for text_edit.
             let replace_start = match &ctx {
@@ -632,25 +1346,46 @@ impl LanguageServer for Backend {
             );
 
             // 3f. Build completion items based on context.
-            let items = match &ctx {
-                parse::CompletionContext::PropertyKey { pointer, .. } => {
-                    let props = schema::collect_properties(&schema_value, pointer);
+            let mut items = match (&ctx, &schema_value) {
+                (_, None) => vec![],
+                (parse::CompletionContext::PropertyKey { pointer, .. }, Some(schema_value)) => {
+                    let props = schema::collect_properties(schema_value, pointer);
                     let existing = existing_keys(&parsed_value, pointer);
                     build_property_items(&props, &existing, snippet, markdown, replace_range)
                 }
+                (
+                    parse::CompletionContext::PropertyValue {
+                        property_name,
+                        pointer,
+                        ..
+                    },
+                    Some(schema_value),
+                ) => {
+                    let values = schema::collect_values(schema_value, pointer, property_name);
+                    build_value_items(&values, replace_range)
+                }
+                (parse::CompletionContext::ArrayItem { pointer, .. }, Some(schema_value)) => {
+                    let values = schema::collect_array_item_values(schema_value, pointer);
+                    build_value_items(&values, replace_range)
+                }
+            };
+
+            // 3g. Add IDs defined in other files where a reference goes.
+            let value_pointer = match &ctx {
+                parse::CompletionContext::PropertyKey { .. } => None,
                 parse::CompletionContext::PropertyValue {
                     property_name,
                     pointer,
                     ..
-                } => {
-                    let values = schema::collect_values(&schema_value, pointer, property_name);
-                    build_value_items(&values, replace_range)
-                }
+                } => Some([pointer.as_slice(), std::slice::from_ref(property_name)].concat()),
                 parse::CompletionContext::ArrayItem { pointer, .. } => {
-                    let values = schema::collect_array_item_values(&schema_value, pointer);
-                    build_value_items(&values, replace_range)
+                    Some([pointer.as_slice(), &["*".to_string()]].concat())
                 }
             };
+            if let (Some(cross_file), Some(pointer)) = (&resolved.cross_file, value_pointer) {
+                let definitions = cross_file.completions(&pointer, &documents);
+                items.extend(build_reference_items(&definitions, &items, replace_range));
+            }
 
             Some(CompletionResponse::List(CompletionList {
                 is_incomplete: false,
@@ -680,22 +1415,33 @@ impl LanguageServer for Backend {
             .filter_map(|c| c.uri.to_file_path().map(Cow::into_owned))
             .collect();
 
-        // Evict config cache for jvl.json changes.
+        // Evict cached configs whose config file or any file in their extends
+        // chain changed. Any config file change may also change which config
+        // a document resolves to.
         let mut config_changed = false;
         {
+            let normalized: Vec<PathBuf> = changed
+                .iter()
+                .map(|p| schema::normalize_file_path(p))
+                .collect();
             let mut cache = self.config_cache.lock().unwrap_or_else(|e| e.into_inner());
             for path in &changed {
-                if path.file_name() == Some(std::ffi::OsStr::new("jvl.json")) {
+                if discover::is_config_file_name(path) {
                     cache.remove(path);
                     config_changed = true;
                 }
             }
+            cache.retain(|_, compiled| {
+                let stale = compiled.sources.iter().any(|s| normalized.contains(s));
+                config_changed |= stale;
+                !stale
+            });
         }
 
         // On config change, clear watched schema paths so they are
         // rediscovered during re-validation.
         if config_changed {
-            self.watched_schema_paths
+            self.watched_paths
                 .lock()
                 .unwrap_or_else(|e| e.into_inner())
                 .clear();
@@ -709,19 +1455,13 @@ impl LanguageServer for Backend {
             }
         }
 
-        // Only re-validate if something was actually invalidated.
-        if config_changed || schema_changed {
-            let uris: Vec<Uri> = self
-                .documents
-                .lock()
-                .unwrap_or_else(|e| e.into_inner())
-                .keys()
-                .cloned()
-                .collect();
+        // Configs that survived eviction keep their cross-file indexes;
+        // bring those up to date with the changed files.
+        let index_changed = self.reindex(changed).await;
 
-            for uri in uris {
-                self.spawn_validation(uri);
-            }
+        // Only re-validate if something was actually invalidated.
+        if config_changed || schema_changed || index_changed {
+            self.revalidate_all();
         }
     }
 }
@@ -734,27 +1474,52 @@ fn resolve_schema_value(
     parsed_value: &serde_json::Value,
     config_cache: &Mutex<HashMap<PathBuf, Arc<CompiledConfig>>>,
     schema_cache: &SchemaCache,
+    editor: &EditorConfig,
 ) -> Option<Arc<serde_json::Value>> {
     let file_path = uri.to_file_path().map(Cow::into_owned)?;
-    let resolved = resolve_schema_for_document(&file_path, config_cache);
+    let resolved = resolve_document(&file_path, config_cache, editor);
+    document_schema_value(&resolved, &file_path, parsed_value, schema_cache)
+}
 
+/// Compile the schema of an already resolved document and return its raw
+/// value.
+fn document_schema_value(
+    resolved: &ResolvedDocument,
+    file_path: &Path,
+    parsed_value: &serde_json::Value,
+    schema_cache: &SchemaCache,
+) -> Option<Arc<serde_json::Value>> {
     let schema_source = resolved
-        .schema_source
-        .or_else(|| schema::resolve_schema_from_value(parsed_value, &file_path))?;
+        .explicit_schema(Some(parsed_value))
+        .or_else(|| schema::resolve_schema_from_value(parsed_value, file_path))?;
 
-    match schema_cache.get_or_compile_with_value(&schema_source, false) {
+    match schema_cache.get_or_compile_with_value(&schema_source, &resolved.compile_options, false) {
         Ok(Some(v)) => Some(v),
         _ => None,
     }
 }
 
+/// Resolve the schema source and options for a document from its jvl.json,
+/// then layer the editor settings on top.
+fn resolve_document(
+    path: &Path,
+    config_cache: &Mutex<HashMap<PathBuf, Arc<CompiledConfig>>>,
+    editor: &EditorConfig,
+) -> ResolvedDocument {
+    let mut resolved = resolve_schema_for_document(path, config_cache, editor.settings.offline);
+    editor.apply(path, &mut resolved);
+    resolved
+}
+
 /// Resolve the schema source for a document by walking up to find jvl.json.
 ///
 /// On config error, returns a `ResolvedDocument` with `config_log` set so the caller can log it.
+/// Remote configs in its `extends` chain are only read from the cache when `offline`.
 /// After the first successful load, results are cached by jvl.json path.
 fn resolve_schema_for_document(
     path: &Path,
     config_cache: &Mutex<HashMap<PathBuf, Arc<CompiledConfig>>>,
+    offline: bool,
 ) -> ResolvedDocument {
     // Find the nearest jvl.json by walking up the directory tree.
     let Some(config_path) = discover::find_config_file(path) else {
@@ -771,7 +1536,7 @@ fn resolve_schema_for_document(
         Some(c) => c,
         None => {
             // Cache miss: load and compile the config.
-            let config = match Config::load(&config_path) {
+            let (config, sources) = match Config::load_from(&config_path, None, false, offline) {
                 Ok(c) => c,
                 Err(e) => {
                     return ResolvedDocument::error(format!(
@@ -795,6 +1560,26 @@ fn resolve_schema_for_document(
                 }
             };
 
+            let parsers = match CompiledParsers::compile(&config) {
+                Ok(p) => p,
+                Err(e) => {
+                    return ResolvedDocument::error(format!(
+                        "jvl: failed to compile parser patterns from {}: {e}",
+                        config_path.display()
+                    ));
+                }
+            };
+
+            let lint = match CompiledLint::compile(&config) {
+                Ok(l) => l,
+                Err(e) => {
+                    return ResolvedDocument::error(format!(
+                        "jvl: failed to compile lint patterns from {}: {e}",
+                        config_path.display()
+                    ));
+                }
+            };
+
             let file_filter = match CompiledFileFilter::compile(&config) {
                 Ok(f) => f,
                 Err(e) => {
@@ -805,11 +1590,40 @@ fn resolve_schema_for_document(
                 }
             };
 
+            let cross_file = match CrossFileRules::compile(&config) {
+                Ok(r) => r,
+                Err(e) => {
+                    return ResolvedDocument::error(format!(
+                        "jvl: failed to compile cross-file rules from {}: {e}",
+                        config_path.display()
+                    ));
+                }
+            };
+
+            let compile_options = match config.compile_options(&project_root) {
+                Ok(o) => o,
+                Err(e) => {
+                    return ResolvedDocument::error(format!(
+                        "jvl: invalid config {}: {e}",
+                        config_path.display()
+                    ));
+                }
+            };
             let new_compiled = Arc::new(CompiledConfig {
                 mappings,
+                parsers,
+                lint,
+                severity: config.severity.clone(),
                 project_root,
                 strict: config.strict,
                 file_filter,
+                cross_file,
+                cross_file_index: Mutex::new(None),
+                compile_options,
+                sources: sources
+                    .iter()
+                    .map(|p| schema::normalize_file_path(p))
+                    .collect(),
             });
 
             // Use entry().or_insert() to handle concurrent cache misses gracefully
@@ -845,10 +1659,34 @@ fn resolve_schema_for_document(
         return ResolvedDocument::skip();
     }
 
+    let rules = &compiled.cross_file;
+    let cross_file = ((rules.applies_to(&relative) || rules.indexes(&relative))
+        && !embed::is_host_file(path))
+    .then(|| CrossFileDocument {
+        config: Arc::clone(&compiled),
+        relative: relative.clone(),
+    });
+
     ResolvedDocument {
         schema_source: compiled.mappings.resolve(&relative, &compiled.project_root),
+        fallback_schema: None,
         strict: compiled.strict,
+        dialect: compiled.parsers.resolve(&relative),
+        lint: compiled.lint.resolve(&relative),
+        severity: compiled.severity.clone(),
+        compile_options: compiled.compile_options.clone(),
         config_log: fallback_warning,
+        cross_file,
+    }
+}
+
+/// Change a semaphore's capacity from `from` to `to` permits (at least one).
+fn resize_semaphore(semaphore: &Semaphore, from: usize, to: usize) {
+    let to = to.max(1);
+    if to > from {
+        semaphore.add_permits(to - from);
+    } else {
+        semaphore.forget_permits(from - to);
     }
 }
 
@@ -925,14 +1763,86 @@ fn lsp_col_to_byte(line: &str, lsp_char: u32, utf8: bool) -> usize {
     }
 }
 
+/// Move diagnostic spans to account for replacing `edit` with `inserted_len`
+/// bytes. Positions inside the replaced range collapse onto its boundaries.
+///
+/// Only the spans are updated; call [`relocate_diagnostics`] once all edits
+/// have been applied to recompute line/column locations.
+fn shift_diagnostics(
+    diagnostics: &mut [FileDiagnostic],
+    edit: &std::ops::Range<usize>,
+    inserted_len: usize,
+) {
+    let new_end = edit.start + inserted_len;
+    let shift_start = |o: usize| {
+        if o >= edit.end {
+            o - edit.end + new_end
+        } else if o <= edit.start {
+            o
+        } else {
+            new_end
+        }
+    };
+    let shift_end = |o: usize| {
+        if o <= edit.start {
+            o
+        } else if o >= edit.end {
+            o - edit.end + new_end
+        } else {
+            edit.start
+        }
+    };
+    let shift = |span: &mut std::ops::Range<usize>| {
+        let start = shift_start(span.start);
+        *span = start..shift_end(span.end).max(start);
+    };
+    for diag in diagnostics {
+        if let Some(span) = &mut diag.span {
+            shift(span);
+        }
+        for related in &mut diag.related {
+            if let Some(span) = &mut related.span {
+                shift(span);
+            }
+        }
+    }
+}
+
+/// Recompute the line/column locations of diagnostics from their spans.
+fn relocate_diagnostics(diagnostics: &mut [FileDiagnostic], source: &str) {
+    let line_starts = parse::compute_line_starts(source);
+    let locate = |span: &std::ops::Range<usize>| {
+        let (line, column) = parse::offset_to_line_col(&line_starts, span.start);
+        SourceLocation {
+            line,
+            column,
+            offset: span.start,
+            length: span.len(),
+        }
+    };
+    for diag in diagnostics {
+        if let Some(span) = &diag.span {
+            diag.location = Some(locate(span));
+        }
+        for related in &mut diag.related {
+            if let Some(span) = &related.span {
+                related.location = Some(locate(span));
+            }
+        }
+    }
+}
+
 /// Convert a `FileDiagnostic` to an `lsp_types::Diagnostic`.
 ///
 /// `source` is the full document text. `line_starts` is precomputed once per validation
-/// cycle and shared across all diagnostics.
+/// cycle and shared across all diagnostics. Related info points into the same
+/// document (`uri`), or into another file whose text is in `others`.
 fn file_diagnostic_to_lsp(
     diag: &FileDiagnostic,
+    uri: &Uri,
     source: &str,
     line_starts: &[usize],
+    others: &HashMap<String, Arc<String>>,
     utf8: bool,
 ) -> Diagnostic {
     let (start, end) = match &diag.location {
@@ -967,12 +1877,58 @@ fn file_diagnostic_to_lsp(
         Severity::Warning => DiagnosticSeverity::WARNING,
     };
 
+    let related_information = (!diag.related.is_empty()).then(|| {
+        diag.related
+            .iter()
+            .filter_map(|r| {
+                let location = match &r.path {
+                    Some(path) => {
+                        let range = match (&r.span, others.get(path)) {
+                            (Some(span), Some(text))
+                                if text.get(span.start..span.end).is_some() =>
+                            {
+                                let line_starts = parse::compute_line_starts(text);
+                                Range::new(
+                                    byte_offset_to_lsp_position(
+                                        text,
+                                        &line_starts,
+                                        span.start,
+                                        utf8,
+                                    ),
+                                    byte_offset_to_lsp_position(text, &line_starts, span.end, utf8),
+                                )
+                            }
+                            _ => Range::default(),
+                        };
+                        Location::new(Uri::from_file_path(path)?, range)
+                    }
+                    None => {
+                        let range = match &r.span {
+                            Some(span) => Range::new(
+                                byte_offset_to_lsp_position(source, line_starts, span.start, utf8),
+                                byte_offset_to_lsp_position(source, line_starts, span.end, utf8),
+                            ),
+                            None => Range::new(start, end),
+                        };
+                        Location::new(uri.clone(), range)
+                    }
+                };
+                Some(DiagnosticRelatedInformation {
+                    location,
+                    message: r.message.clone(),
+                })
+            })
+            .collect()
+    });
+
     Diagnostic {
         range: Range::new(start, end),
         severity: Some(severity),
         code: Some(NumberOrString::String(diag.code.clone())),
         source: Some("jvl".to_string()),
         message: diag.message.clone(),
+        tags: (diag.code == "schema(deprecated)").then(|| vec![DiagnosticTag::DEPRECATED]),
+        related_information,
         ..Default::default()
     }
 }
@@ -1080,12 +2036,43 @@ fn build_property_items(
                     new_text,
                 })),
                 insert_text_format,
+                tags: deprecated_tag(p.deprecated),
                 ..Default::default()
             }
         })
         .collect()
 }
 
+/// Completion tags for an item, marking deprecated items for strike-through.
+fn deprecated_tag(deprecated: bool) -> Option<Vec<CompletionItemTag>> {
+    deprecated.then(|| vec![CompletionItemTag::DEPRECATED])
+}
+
+/// Build `CompletionItem` list from the IDs a reference can name, skipping
+/// values the schema already suggested.
+fn build_reference_items(
+    definitions: &[Definition],
+    existing: &[CompletionItem],
+    replace_range: Range,
+) -> Vec<CompletionItem> {
+    definitions
+        .iter()
+        .map(|d| format_json_value(&d.id))
+        .zip(definitions)
+        .filter(|(label, _)| !existing.iter().any(|item| &item.label == label))
+        .map(|(label, definition)| CompletionItem {
+            label: label.clone(),
+            kind: Some(CompletionItemKind::REFERENCE),
+            detail: Some(format!("defined in {}", definition.file)),
+            text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+                range: replace_range,
+                new_text: label,
+            })),
+            ..Default::default()
+        })
+        .collect()
+}
+
 /// Build `CompletionItem` list from schema value suggestions.
 fn build_value_items(
     values: &[schema::ValueSuggestion],
@@ -1101,21 +2088,23 @@ fn build_value_items(
     values
         .iter()
         .flat_map(|v| match v {
-            schema::ValueSuggestion::Enum(val) => {
+            schema::ValueSuggestion::Enum(val, deprecated) => {
                 let formatted = format_json_value(val);
                 vec![CompletionItem {
                     label: formatted.clone(),
                     kind: Some(CompletionItemKind::ENUM_MEMBER),
                     text_edit: text_edit(formatted),
+                    tags: deprecated_tag(*deprecated),
                     ..Default::default()
                 }]
             }
-            schema::ValueSuggestion::Const(val) => {
+            schema::ValueSuggestion::Const(val, deprecated) => {
                 let formatted = format_json_value(val);
                 vec![CompletionItem {
                     label: formatted.clone(),
                     kind: Some(CompletionItemKind::VALUE),
                     text_edit: text_edit(formatted),
+                    tags: deprecated_tag(*deprecated),
                     ..Default::default()
                 }]
             }
@@ -1,17 +1,26 @@
 use clap::{CommandFactory, Parser, Subcommand};
 use clap_complete::{Shell, generate};
 use rayon::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::ExitCode;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Instant;
 
+use jvl::crossfile::{CrossFileIndex, CrossFileRules};
 use jvl::diagnostic::{FileResult, ToolDiagnostic, Warning};
-use jvl::discover::{self, CompiledSchemaMappings, Config};
-use jvl::output::{self, Format, Summary, VerboseFileInfo};
+use jvl::discover::{
+    self, CheckSyntax, CompiledParsers, CompiledSchemaMappings, Config, ConfigSection,
+};
+use jvl::embed;
+use jvl::git;
+use jvl::lint::CompiledLint;
+use jvl::output::{self, Format, ListFormat, Summary, VerboseFileInfo};
 use jvl::parse;
-use jvl::schema::{self, SchemaCache};
+use jvl::result_cache::{self, ResultCache};
+use jvl::schema::{self, SchemaCache, SchemaDraft};
 use jvl::validate;
 
 #[derive(Parser)]
@@ -26,12 +35,21 @@ enum Commands {
     /// Validate JSON files against JSON Schema
     Check(CheckArgs),
 
+    /// Create a jvl.json with schema mappings suggested from the project
+    Init(InitArgs),
+
+    /// Explain how a file's schema is resolved and which subschemas apply
+    Explain(ExplainArgs),
+
     /// Manage jvl configuration
     Config {
         #[command(subcommand)]
         command: ConfigCommands,
     },
 
+    /// Print the JSON Schema for `jvl check --format json` output
+    OutputSchema(OutputSchemaArgs),
+
     /// Manage the schema cache
     Cache {
         #[command(subcommand)]
@@ -73,7 +91,14 @@ enum CacheCommands {
 struct CacheListArgs {
     /// Output format
     #[arg(short = 'f', long, value_enum, default_value = "human")]
-    format: Format,
+    format: ListFormat,
+}
+
+#[derive(clap::Args)]
+struct OutputSchemaArgs {
+    /// Print the schema of one `--format jsonl` line instead
+    #[arg(long)]
+    jsonl: bool,
 }
 
 #[derive(clap::Args)]
@@ -83,6 +108,41 @@ struct ConfigPrintArgs {
     config: Option<PathBuf>,
 }
 
+#[derive(clap::Args)]
+struct InitArgs {
+    /// Accept all suggested mappings without prompting
+    #[arg(short = 'y', long)]
+    yes: bool,
+
+    /// Replace an existing jvl.json in the current directory
+    #[arg(long)]
+    force: bool,
+}
+
+#[derive(clap::Args)]
+struct ExplainArgs {
+    /// File to explain
+    file: PathBuf,
+
+    /// JSON pointer to a location in the file, e.g. /server/port
+    pointer: Option<String>,
+
+    /// Schema to validate the file against (path or URL)
+    #[arg(short = 's', long)]
+    schema: Option<String>,
+
+    /// Path to config file
+    #[arg(short = 'c', long)]
+    config: Option<PathBuf>,
+
+    /// Bypass schema cache; always fetch from network
+    #[arg(long)]
+    no_cache: bool,
+
+    #[command(flatten)]
+    validation: ValidationArgs,
+}
+
 #[derive(clap::Args)]
 struct CheckArgs {
     /// File paths to validate (use - to read from stdin)
@@ -104,17 +164,87 @@ struct CheckArgs {
     #[arg(short = 'j', long, default_value = "10", value_parser = clap::value_parser!(u16).range(1..=256))]
     jobs: u16,
 
-    /// Error if any file has no resolvable schema
-    #[arg(long)]
-    strict: bool,
-
     /// Bypass schema cache; always fetch from network
     #[arg(long)]
     no_cache: bool,
 
+    /// Skip files that passed on an earlier run and have not changed since,
+    /// remembering results in .jvl/cache
+    #[arg(long)]
+    result_cache: bool,
+
+    /// Check only files changed since HEAD, including untracked files, and
+    /// files whose schema changed
+    #[arg(long, conflicts_with_all = ["staged", "since"])]
+    changed: bool,
+
+    /// Check the staged content of staged files, and of files whose schema
+    /// is staged
+    #[arg(long, conflicts_with = "since")]
+    staged: bool,
+
+    /// Check only files changed since a git ref, including untracked files,
+    /// and files whose schema changed
+    #[arg(long, value_name = "REF")]
+    since: Option<String>,
+
+    /// Check the files of a git commit without checking it out, reading the
+    /// config and local schemas from it too
+    #[arg(
+        long,
+        value_name = "COMMIT",
+        conflicts_with_all = ["changed", "staged", "since", "result_cache"]
+    )]
+    rev: Option<String>,
+
     /// Print verbose diagnostic information to stderr
-    #[arg(short = 'v', long)]
+    #[arg(short = 'v', long, conflicts_with = "quiet")]
     verbose: bool,
+
+    /// Print only the summary, and nothing when all files are valid
+    #[arg(short = 'q', long)]
+    quiet: bool,
+
+    /// Print at most this many diagnostics (human and compact formats)
+    #[arg(long, value_name = "N")]
+    max_diagnostics: Option<usize>,
+
+    #[command(flatten)]
+    validation: ValidationArgs,
+}
+
+/// Options that override config settings for schema resolution and
+/// compilation.
+#[derive(clap::Args, Clone)]
+struct ValidationArgs {
+    /// Error if any file has no resolvable schema
+    #[arg(long)]
+    strict: bool,
+
+    /// Check the syntax of files with no resolvable schema instead of
+    /// skipping them
+    #[arg(long)]
+    syntax: bool,
+
+    /// Draft for schemas that don't declare `$schema`
+    #[arg(long, value_enum, value_name = "DRAFT")]
+    default_draft: Option<SchemaDraft>,
+
+    /// Validate `format` keywords regardless of the schema's draft
+    #[arg(long, overrides_with = "no_validate_formats")]
+    validate_formats: bool,
+
+    /// Treat `format` keywords as annotations only
+    #[arg(long, overrides_with = "validate_formats")]
+    no_validate_formats: bool,
+
+    /// Fail schema compilation on unknown `format` values
+    #[arg(long)]
+    deny_unknown_formats: bool,
+
+    /// Validate JSON embedded in strings against `contentSchema`
+    #[arg(long)]
+    validate_content: bool,
 }
 
 fn main() -> ExitCode {
@@ -122,6 +252,8 @@ fn main() -> ExitCode {
 
     match cli.command {
         Commands::Check(args) => run_check(args),
+        Commands::Init(args) => run_init(args),
+        Commands::Explain(args) => run_explain(args),
         Commands::Lsp => {
             // NOTE: tokio runtime is isolated to this subcommand to avoid making all other
             // subcommands async and to prevent reqwest::blocking from being called outside
@@ -138,6 +270,7 @@ fn main() -> ExitCode {
             ConfigCommands::Print(args) => run_config_print(args),
             ConfigCommands::Schema => run_config_schema(),
         },
+        Commands::OutputSchema(args) => run_output_schema(args),
         Commands::Cache { command } => match command {
             CacheCommands::Dir => run_cache_dir(),
             CacheCommands::List(args) => run_cache_list(args),
@@ -162,7 +295,7 @@ fn run_config_print(args: ConfigPrintArgs) -> ExitCode {
         }
     };
 
-    let (loaded_config, _project_root) = match load_config(&args.config, &cwd) {
+    let (loaded_config, _project_root) = match load_config(&args.config, &cwd, None, false) {
         Ok(result) => result,
         Err(e) => {
             let diag = ToolDiagnostic::error(format!("failed to load config: {e}"));
@@ -171,13 +304,71 @@ fn run_config_print(args: ConfigPrintArgs) -> ExitCode {
         }
     };
 
-    let config = loaded_config.unwrap_or_else(Config::default_config);
+    // Report where the config came from on stderr, keeping stdout valid JSON.
+    let config = match loaded_config {
+        Some(LoadedConfig { config, sources }) => {
+            let section = match Config::section(&sources[0]) {
+                Ok(Some(section)) if section != ConfigSection::File => format!(" {section}"),
+                _ => String::new(),
+            };
+            let _ = writeln!(stderr, "config: {}{section}", sources[0].display());
+            if args.config.is_none() {
+                let dir = sources[0].parent().unwrap_or(Path::new("."));
+                for shadowed in discover::config_files_in(dir).iter().skip(1) {
+                    let _ = writeln!(stderr, "shadowed: {}", shadowed.display());
+                }
+            }
+            for source in &sources[1..] {
+                let _ = writeln!(stderr, "extends: {}", source.display());
+            }
+            config
+        }
+        None => {
+            let _ = writeln!(stderr, "config: none found, using defaults");
+            Config::default_config()
+        }
+    };
     println!("{}", serde_json::to_string_pretty(&config).unwrap());
     ExitCode::SUCCESS
 }
 
 fn run_config_schema() -> ExitCode {
-    let schema = schemars::schema_for!(jvl::discover::Config);
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&config_schema()).unwrap()
+    );
+    ExitCode::SUCCESS
+}
+
+/// The JSON Schema for jvl config files.
+fn config_schema() -> serde_json::Value {
+    publish_schema(
+        schemars::schema_for!(jvl::discover::Config),
+        "https://code.sargunv.dev/jvl/v1/jvl-config.schema.json",
+    )
+}
+
+fn run_output_schema(args: OutputSchemaArgs) -> ExitCode {
+    let (schema, id) = if args.jsonl {
+        (
+            output::output_schema(true),
+            "https://code.sargunv.dev/jvl/v1/jvl-output-jsonl.schema.json",
+        )
+    } else {
+        (
+            output::output_schema(false),
+            "https://code.sargunv.dev/jvl/v1/jvl-output.schema.json",
+        )
+    };
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&publish_schema(schema, id)).unwrap()
+    );
+    ExitCode::SUCCESS
+}
+
+/// Convert a generated schema to the 2020-12 form jvl publishes, with `$id`.
+fn publish_schema(schema: schemars::schema::RootSchema, id: &str) -> serde_json::Value {
     let mut value = serde_json::to_value(&schema).unwrap();
 
     // Rename definitions → $defs (2020-12 convention) and update $refs.
@@ -189,13 +380,140 @@ fn run_config_schema() -> ExitCode {
             "$schema".to_string(),
             serde_json::json!("https://json-schema.org/draft/2020-12/schema"),
         );
-        obj.insert(
-            "$id".to_string(),
-            serde_json::json!("https://code.sargunv.dev/jvl/v1/jvl-config.schema.json"),
+        obj.insert("$id".to_string(), serde_json::json!(id));
+    }
+    value
+}
+
+fn run_init(args: InitArgs) -> ExitCode {
+    use std::io::{BufRead, IsTerminal};
+
+    let mut stderr = std::io::stderr().lock();
+    let fail = |stderr: &mut std::io::StderrLock, msg: String| {
+        let diag = ToolDiagnostic::error(msg);
+        let _ = writeln!(stderr, "{:?}", miette::Report::new(diag));
+        ExitCode::from(2)
+    };
+
+    let cwd = match std::env::current_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            return fail(
+                &mut stderr,
+                format!("cannot determine current directory: {e}"),
+            );
+        }
+    };
+    let path = cwd.join("jvl.json");
+    if let Some(existing) = discover::config_file_in(&cwd) {
+        if !args.force {
+            return fail(
+                &mut stderr,
+                format!(
+                    "a config already exists at {}; pass --force to replace it",
+                    existing.display()
+                ),
+            );
+        }
+        // A new jvl.json would take precedence over the existing config
+        // without replacing it.
+        if existing != path {
+            return fail(
+                &mut stderr,
+                format!(
+                    "the config at {} can't be replaced: jvl init writes jvl.json, which would \
+                     shadow it; move its settings to jvl.json or remove them first",
+                    existing.display()
+                ),
+            );
+        }
+    }
+    if !args.yes && !std::io::stdin().is_terminal() {
+        return fail(
+            &mut stderr,
+            "stdin is not a terminal; pass --yes to accept all suggestions".to_string(),
         );
     }
 
-    println!("{}", serde_json::to_string_pretty(&value).unwrap());
+    let discovery =
+        match discover::discover_files(&cwd, std::slice::from_ref(&cwd), &Config::default_config())
+        {
+            Ok(d) => d,
+            Err(e) => return fail(&mut stderr, format!("failed to discover files: {e}")),
+        };
+    let scanned = jvl::init::scan(&cwd, &discovery.files);
+    let plan = jvl::init::plan(&scanned, &jvl::catalog::Catalog::new());
+
+    let mut accepted = Vec::new();
+    let mut stdin = std::io::stdin().lock();
+    for suggestion in &plan.suggestions {
+        let schema = match &suggestion.mapping {
+            discover::SchemaMapping::Url(m) => &m.url,
+            discover::SchemaMapping::Path(m) => &m.path,
+        };
+        let summary = format!(
+            "{} -> {schema} ({} without $schema)",
+            suggestion.mapping.files().join(", "),
+            suggestion.files.len()
+        );
+        if args.yes {
+            let _ = writeln!(stderr, "mapping {summary}");
+            accepted.push(suggestion);
+            continue;
+        }
+        let _ = write!(stderr, "Map {summary}? [Y/n] ");
+        let _ = stderr.flush();
+        let mut answer = String::new();
+        if stdin.read_line(&mut answer).is_err() {
+            break;
+        }
+        if !matches!(answer.trim(), "n" | "N" | "no") {
+            accepted.push(suggestion);
+        }
+    }
+
+    let content = jvl::init::render(&plan, &accepted);
+
+    // Check the generated config against the config schema before writing it.
+    let errors: Vec<String> = match parse::parse_jsonc(&content) {
+        Ok(parsed) => match jsonschema::validator_for(&config_schema()) {
+            Ok(validator) => validator
+                .iter_errors(&parsed.value)
+                .map(|e| format!("{}: {e}", e.instance_path()))
+                .chain(
+                    serde_json::from_value::<Config>(parsed.value.clone())
+                        .err()
+                        .map(|e| e.to_string()),
+                )
+                .collect(),
+            Err(e) => vec![format!("invalid config schema: {e}")],
+        },
+        Err(errors) => errors.iter().map(|e| e.message.clone()).collect(),
+    };
+    if !errors.is_empty() {
+        return fail(
+            &mut stderr,
+            format!("generated config is invalid: {}", errors.join("; ")),
+        );
+    }
+
+    if let Err(e) = std::fs::write(&path, content) {
+        return fail(
+            &mut stderr,
+            format!("failed to write {}: {e}", path.display()),
+        );
+    }
+    let _ = writeln!(
+        stderr,
+        "Wrote {} with {} schema {}",
+        path.display(),
+        accepted.len(),
+        if accepted.len() == 1 {
+            "mapping"
+        } else {
+            "mappings"
+        }
+    );
     ExitCode::SUCCESS
 }
 
@@ -250,7 +568,7 @@ fn run_cache_list(args: CacheListArgs) -> ExitCode {
     };
 
     match args.format {
-        Format::Human => {
+        ListFormat::Human => {
             for entry in &result.entries {
                 println!(
                     "{} ({}, {})",
@@ -280,7 +598,7 @@ fn run_cache_list(args: CacheListArgs) -> ExitCode {
                 );
             }
         }
-        Format::Json => {
+        ListFormat::Json => {
             #[derive(serde::Serialize)]
             struct JsonCacheList {
                 entries: Vec<JsonCacheEntry>,
@@ -366,28 +684,49 @@ fn run_check(args: CheckArgs) -> ExitCode {
             return ExitCode::from(2);
         }
     };
-    let (loaded_config, project_root) = match load_config(&args.config, &cwd) {
-        Ok(result) => result,
-        Err(e) => {
-            let diag = ToolDiagnostic::error(format!("failed to load config: {e}"));
+    // `--rev` checks a commit and `--staged` what the next commit would
+    // record, so both read from git rather than the working tree.
+    let tree = match (&args.rev, args.staged) {
+        (Some(rev), _) => Some(git::Tree::load(&cwd, rev)),
+        (None, true) => Some(git::Tree::load_index(&cwd)),
+        (None, false) => None,
+    };
+    let tree = match tree.transpose() {
+        Ok(tree) => tree.map(Arc::new),
+        Err(msg) => {
+            let diag = ToolDiagnostic::error(msg);
             let _ = writeln!(stderr, "{:?}", miette::Report::new(diag));
             return ExitCode::from(2);
         }
     };
+    // Tree paths are under the canonical repository root.
+    let cwd = match tree {
+        Some(_) => std::fs::canonicalize(&cwd).unwrap_or(cwd),
+        None => cwd,
+    };
+    let (loaded_config, project_root) =
+        match load_config(&args.config, &cwd, tree.as_deref(), args.no_cache) {
+            Ok(result) => result,
+            Err(e) => {
+                let diag = ToolDiagnostic::error(format!("failed to load config: {e}"));
+                let _ = writeln!(stderr, "{:?}", miette::Report::new(diag));
+                return ExitCode::from(2);
+            }
+        };
     let project_root = std::fs::canonicalize(&project_root).unwrap_or(project_root);
 
     if verbose && args.format == Format::Human {
+        if let Some(rev) = &args.rev {
+            output::verbose_log(&mut stderr, &format!("reading files from: {rev}"));
+        }
         match (&loaded_config, &args.config) {
             (Some(_), Some(path)) => {
                 output::verbose_log(&mut stderr, &format!("config: {}", path.display()));
             }
-            (Some(_), None) => {
+            (Some(loaded), None) => {
                 output::verbose_log(
                     &mut stderr,
-                    &format!(
-                        "config: {} (auto-discovered)",
-                        project_root.join("jvl.json").display()
-                    ),
+                    &format!("config: {} (auto-discovered)", loaded.sources[0].display()),
                 );
             }
             (None, _) => {
@@ -400,14 +739,18 @@ fn run_check(args: CheckArgs) -> ExitCode {
         );
     }
 
-    let config = loaded_config.unwrap_or_else(Config::default_config);
-    let strict = args.strict || config.strict;
-
-    // Pre-compile schema mappings once
-    let compiled_mappings = match CompiledSchemaMappings::compile(&config) {
-        Ok(m) => m,
-        Err(e) => {
-            let diag = ToolDiagnostic::error(format!("failed to compile schema mappings: {e}"));
+    let log_nested = verbose && args.format == Format::Human;
+    let mut projects = match Projects::new(
+        loaded_config,
+        project_root,
+        &args.validation,
+        log_nested,
+        tree.clone(),
+        args.no_cache,
+    ) {
+        Ok(p) => p,
+        Err(msg) => {
+            let diag = ToolDiagnostic::error(msg);
             let _ = writeln!(stderr, "{:?}", miette::Report::new(diag));
             return ExitCode::from(2);
         }
@@ -416,7 +759,8 @@ fn run_check(args: CheckArgs) -> ExitCode {
     // Resolve schema override
     let schema_override_source = args
         .schema
-        .map(|s| jvl::schema::resolve_schema_ref(&s, &cwd));
+        .as_deref()
+        .map(|s| jvl::schema::resolve_schema_ref(s, &cwd));
 
     // Detect stdin input (-) before partitioning file args.
     let stdin_content: Option<(String, String)> = if args.files.iter().any(|p| p.as_os_str() == "-")
@@ -446,7 +790,7 @@ fn run_check(args: CheckArgs) -> ExitCode {
         }
         // No explicit arguments: discover from cwd
         let discover_start = Instant::now();
-        match discover::discover_files(&project_root, std::slice::from_ref(&cwd), &config) {
+        match projects.discover(std::slice::from_ref(&cwd), &mut stderr) {
             Ok((files, walk_warnings)) => {
                 early_warnings.extend(walk_warnings);
                 if verbose && args.format == Format::Human {
@@ -461,8 +805,8 @@ fn run_check(args: CheckArgs) -> ExitCode {
                 }
                 files
             }
-            Err(e) => {
-                let diag = ToolDiagnostic::error(format!("failed to discover files: {e}"));
+            Err(msg) => {
+                let diag = ToolDiagnostic::error(msg);
                 let _ = writeln!(stderr, "{:?}", miette::Report::new(diag));
                 return ExitCode::from(2);
             }
@@ -470,7 +814,7 @@ fn run_check(args: CheckArgs) -> ExitCode {
     } else {
         // Partition explicit args into directories and files
         let mut walk_roots: Vec<PathBuf> = Vec::new();
-        let mut explicit_files: Vec<PathBuf> = Vec::new();
+        let mut explicit_files: Vec<(PathBuf, usize)> = Vec::new();
 
         for path in &file_args {
             let resolved = if path.is_absolute() {
@@ -478,10 +822,28 @@ fn run_check(args: CheckArgs) -> ExitCode {
             } else {
                 cwd.join(path)
             };
-            if resolved.is_dir() {
+            let is_dir = match &tree {
+                Some(tree) => {
+                    !tree.contains(&resolved) && tree.files_under(&resolved).next().is_some()
+                }
+                None => resolved.is_dir(),
+            };
+            if is_dir {
                 walk_roots.push(resolved);
             } else {
-                explicit_files.push(path.to_path_buf());
+                // A commit's files are looked up by their absolute path.
+                let path = match tree {
+                    Some(_) => resolved.clone(),
+                    None => path.to_path_buf(),
+                };
+                match projects.project_for(&resolved, &mut stderr) {
+                    Ok(idx) => explicit_files.push((path, idx)),
+                    Err(msg) => {
+                        let diag = ToolDiagnostic::error(msg);
+                        let _ = writeln!(stderr, "{:?}", miette::Report::new(diag));
+                        return ExitCode::from(2);
+                    }
+                }
             }
         }
 
@@ -504,7 +866,7 @@ fn run_check(args: CheckArgs) -> ExitCode {
 
         if !walk_roots.is_empty() {
             let discover_start = Instant::now();
-            match discover::discover_files(&project_root, &walk_roots, &config) {
+            match projects.discover(&walk_roots, &mut stderr) {
                 Ok((files, walk_warnings)) => {
                     early_warnings.extend(walk_warnings);
                     if verbose && args.format == Format::Human {
@@ -519,8 +881,8 @@ fn run_check(args: CheckArgs) -> ExitCode {
                     }
                     explicit_files.extend(files);
                 }
-                Err(e) => {
-                    let diag = ToolDiagnostic::error(format!("failed to discover files: {e}"));
+                Err(msg) => {
+                    let diag = ToolDiagnostic::error(msg);
                     let _ = writeln!(stderr, "{:?}", miette::Report::new(diag));
                     return ExitCode::from(2);
                 }
@@ -530,8 +892,60 @@ fn run_check(args: CheckArgs) -> ExitCode {
         explicit_files
     };
 
-    if files_to_check.is_empty() && stdin_content.is_none() {
-        if args.format == Format::Human {
+    let schema_cache = match &tree {
+        Some(tree) => SchemaCache::for_tree(tree.clone()),
+        None => SchemaCache::new(),
+    };
+
+    let git_selection = if args.changed {
+        Some(git::Selection::Changed)
+    } else if args.staged {
+        Some(git::Selection::Staged)
+    } else {
+        args.since.clone().map(git::Selection::Since)
+    };
+    let files_to_check = match git_selection {
+        Some(selection) => {
+            let selected = match git::selected_files(&cwd, &selection) {
+                Ok(selected) => selected,
+                Err(msg) => {
+                    let diag = ToolDiagnostic::error(msg);
+                    let _ = writeln!(stderr, "{:?}", miette::Report::new(diag));
+                    return ExitCode::from(2);
+                }
+            };
+            let discovered = files_to_check.len();
+            let (files, schema_warnings) = select_files(
+                files_to_check,
+                &selected,
+                &projects,
+                schema_override_source.as_ref(),
+                &schema_cache,
+                args.no_cache,
+                tree.as_deref(),
+            );
+            early_warnings.extend(schema_warnings);
+            if verbose && args.format == Format::Human {
+                output::verbose_log(
+                    &mut stderr,
+                    &format!(
+                        "selected {} of {discovered} files by git status",
+                        files.len()
+                    ),
+                );
+            }
+            files
+        }
+        None => files_to_check,
+    };
+
+    // Machine-readable formats still get an empty report, so consumers
+    // don't have to handle missing output.
+    if files_to_check.is_empty()
+        && stdin_content.is_none()
+        && matches!(args.format, Format::Human | Format::Compact)
+    {
+        if !args.quiet {
             let diag = ToolDiagnostic::warning("no files to check".to_string());
             let _ = writeln!(stderr, "{:?}", miette::Report::new(diag));
         }
@@ -541,34 +955,65 @@ fn run_check(args: CheckArgs) -> ExitCode {
     // Read all file contents upfront, stripping BOM at read time so all
     // downstream byte offsets are consistent with the stored source.
     let mut has_file_read_error = false;
-    let mut file_contents: Vec<(String, String)> = Vec::new();
+    let mut file_contents: Vec<(String, String, usize)> = Vec::new();
 
-    // Inject stdin content first if present.
-    if let Some(entry) = stdin_content {
-        file_contents.push(entry);
+    // Inject stdin content first if present. It is checked with the root config.
+    if let Some((path, content)) = stdin_content {
+        file_contents.push((path, content, 0));
     }
 
-    for path in &files_to_check {
+    // A commit's files are reported by their paths in its tree.
+    let display_path = |path: &str| {
+        tree.as_ref()
+            .filter(|_| args.rev.is_some())
+            .and_then(|tree| tree.tree_path(Path::new(path)))
+            .unwrap_or_else(|| path.to_string())
+    };
+
+    // A tree reads every file with one git process.
+    let mut from_tree = tree.as_ref().map(|tree| {
+        let paths: Vec<&Path> = files_to_check.iter().map(|(p, _)| p.as_path()).collect();
+        tree.read_all(&paths).into_iter()
+    });
+    for (path, project) in &files_to_check {
         let path_str = path.display().to_string();
-        match std::fs::read_to_string(path) {
+        let content = match &mut from_tree {
+            Some(contents) => contents.next().expect("one result per file"),
+            None => std::fs::read_to_string(path),
+        };
+        match content {
             Ok(content) => {
                 let content = parse::strip_bom(&content).to_owned();
-                file_contents.push((path_str, content));
+                file_contents.push((path_str, content, *project));
             }
             Err(e) => {
-                let diag = ToolDiagnostic::error(format!("could not read {path_str}: {e}"));
+                let diag = ToolDiagnostic::error(format!(
+                    "could not read {}: {e}",
+                    display_path(&path_str)
+                ));
                 let _ = writeln!(stderr, "{:?}", miette::Report::new(diag));
                 has_file_read_error = true;
             }
         }
     }
 
-    let schema_cache = SchemaCache::new();
+    // Results are cached for the whole run under the root project, so nested
+    // projects share its cache. A commit's files aren't cached.
+    let root_project = &projects.list[0];
+    let result_cache = ((args.result_cache || root_project.config.result_cache) && tree.is_none())
+        .then(|| ResultCache::load(&root_project.root));
+    let cached_files = AtomicUsize::new(0);
 
-    // Build sources map that borrows from file_contents (no cloning)
-    let sources: HashMap<&str, &str> = file_contents
+    // Build sources map that borrows from file_contents (no cloning), keyed
+    // by the paths results are reported under.
+    let display_paths: Vec<String> = file_contents
+        .iter()
+        .map(|(p, _, _)| display_path(p))
+        .collect();
+    let sources: HashMap<&str, &str> = display_paths
         .iter()
-        .map(|(p, c)| (p.as_str(), c.as_str()))
+        .zip(&file_contents)
+        .map(|(p, (_, c, _))| (p.as_str(), c.as_str()))
         .collect();
 
     // Drop the stderr lock before entering the parallel section so that
@@ -576,46 +1021,88 @@ fn run_check(args: CheckArgs) -> ExitCode {
     drop(stderr);
 
     // Process files in parallel, collecting results via rayon's lock-free collect
-    let par_results: Vec<(FileResult, Vec<Warning>, Option<VerboseFileInfo>)> = file_contents
+    // With `--format jsonl`, each result is written as soon as it's final,
+    // which is when its task finishes unless cross-file rules apply to it.
+    // The flag records whether it was written.
+    let par_results: Vec<(FileResult, Vec<Warning>, Option<VerboseFileInfo>, bool)> = file_contents
         .par_iter()
-        .map(|(path, content)| {
+        .map(|(path, content, project)| {
             let file_start = if verbose { Some(Instant::now()) } else { None };
+            let project = &projects.list[*project];
 
             // Determine schema for this file
+            let relative = project.relative_path(path);
             let (effective_schema, schema_via) = if let Some(ref s) = schema_override_source {
                 (Some(s.clone()), "flag")
             } else {
-                let relative = std::fs::canonicalize(Path::new(path))
-                    .ok()
-                    .and_then(|abs| {
-                        abs.strip_prefix(&project_root)
-                            .ok()
-                            .map(|p| p.to_string_lossy().to_string())
-                    })
-                    .unwrap_or_else(|| path.clone());
-
-                match compiled_mappings.resolve(&relative, &project_root) {
+                match project.mappings.resolve(&relative, &project.root) {
                     Some(s) => (Some(s), "config"),
                     None => (None, ""),
                 }
             };
 
-            let (result, file_warnings, cache_outcome, timing) = validate::validate_file(
-                path,
-                content,
-                effective_schema.as_ref(),
-                &schema_cache,
-                args.no_cache,
-                strict,
-            );
+            let file_options = project.file_options(&relative);
+            let mut file_warnings = Vec::new();
+            let cache_key = result_cache.as_ref().and_then(|_| {
+                // Cross-file rules depend on other files, and code blocks in
+                // host files have schemas of their own.
+                if embed::is_host_file(Path::new(path)) || project.cross_file.applies_to(&relative)
+                {
+                    return None;
+                }
+                let schema = effective_schema.clone().or_else(|| {
+                    let base_dir = Path::new(path).parent().unwrap_or(Path::new("."));
+                    parse::extract_schema_field_from_str(content)
+                        .map(|s| schema::resolve_schema_ref(&s, base_dir))
+                })?;
+                // Only the first caller gets a schema's warnings, so keep them
+                // here rather than lose them when the result is cached.
+                let (_, warnings, _) = schema_cache
+                    .get_or_compile(&schema, &project.compile_options, args.no_cache)
+                    .ok()?;
+                file_warnings = warnings;
+                let fingerprint = schema_cache.fingerprint(&schema, &project.compile_options)?;
+                let settings = format!(
+                    "{schema} {file_options:?} {:?} {:?}",
+                    project.compile_options, project.config.severity
+                );
+                Some(result_cache::key(content, &fingerprint, &settings))
+            });
+            let cached = result_cache
+                .as_ref()
+                .zip(cache_key.as_ref())
+                .is_some_and(|(cache, key)| cache.is_valid(path, key));
+
+            let (mut result, cache_outcome, timing) = if cached {
+                cached_files.fetch_add(1, Ordering::Relaxed);
+                (FileResult::valid(path), None, None)
+            } else {
+                let (result, warnings, cache_outcome, timing) = validate::validate_file(
+                    path,
+                    content,
+                    effective_schema.as_ref(),
+                    &schema_cache,
+                    &project.compile_options,
+                    args.no_cache,
+                    file_options,
+                );
+                file_warnings.extend(warnings);
+                (result, cache_outcome, timing)
+            };
+            result.path = display_path(path);
+            result.apply_severities(&project.config.severity);
+            if let (Some(cache), Some(key)) = (&result_cache, cache_key) {
+                let passed = result.valid && result.errors.is_empty();
+                cache.record(path, passed.then_some(key));
+            }
 
             let verbose_info = if verbose {
                 let file_duration = file_start.unwrap().elapsed();
 
                 // Derive schema resolution in the caller:
                 // - If effective_schema was set above → schema_via is "flag" or "config"
-                // - If it was None but file wasn't skipped → inline $schema
-                // - If skipped → no schema
+                // - If it was None but file wasn't skipped and declares one → inline $schema
+                // - Otherwise (skipped, or only its syntax was checked) → no schema
                 let (schema_display, via) = if !schema_via.is_empty() {
                     // Schema was resolved from flag or config mapping
                     (
@@ -624,10 +1111,10 @@ fn run_check(args: CheckArgs) -> ExitCode {
                             .map_or_else(String::new, |s| s.to_string()),
                         schema_via.to_string(),
                     )
-                } else if !result.skipped {
+                } else if let Some(inline_ref) = parse::extract_schema_field_from_str(content)
+                    .filter(|_| !result.skipped)
+                {
                     // Schema was resolved via inline $schema field
-                    let inline_ref =
-                        parse::extract_schema_field_from_str(content).unwrap_or_default();
                     (inline_ref, "inline $schema".to_string())
                 } else {
                     (String::new(), String::new())
@@ -637,6 +1124,8 @@ fn run_check(args: CheckArgs) -> ExitCode {
                 if args.format == Format::Human {
                     let status = if result.skipped {
                         "skipped (no schema)"
+                    } else if cached {
+                        "valid (cached)"
                     } else if result.valid {
                         "valid"
                     } else if result.tool_error {
@@ -684,7 +1173,14 @@ fn run_check(args: CheckArgs) -> ExitCode {
                 None
             };
 
-            (result, file_warnings, verbose_info)
+            let streamed = args.format == Format::Jsonl
+                && cross_file_path(project, path, &result).is_none();
+            if streamed {
+                let mut stdout = std::io::stdout().lock();
+                output::render_jsonl_file(&result, verbose_info.as_ref(), &mut stdout);
+            }
+
+            (result, file_warnings, verbose_info, streamed)
         })
         .collect();
 
@@ -694,20 +1190,55 @@ fn run_check(args: CheckArgs) -> ExitCode {
     let mut results = Vec::with_capacity(par_results.len());
     let mut verbose_file_infos: Vec<Option<VerboseFileInfo>> = Vec::new();
     let mut warnings = early_warnings;
-    for (result, file_warnings, verbose_info) in par_results {
+    let mut streamed = Vec::with_capacity(par_results.len());
+    for (result, file_warnings, verbose_info, was_streamed) in par_results {
         results.push(result);
+        streamed.push(was_streamed);
         warnings.extend(file_warnings);
         if verbose {
             verbose_file_infos.push(verbose_info);
         }
     }
 
+    if let Some(cache) = result_cache
+        && let Err(e) = cache.save()
+    {
+        warnings.push(Warning {
+            code: "cache(write)".into(),
+            message: format!("failed to write the result cache: {e}"),
+        });
+    }
+
+    // Cross-file rules can only be checked against the whole project, so
+    // they run after every file has been validated on its own.
+    let mut cross_file_indexes: HashMap<usize, CrossFileIndex> = HashMap::new();
+    for (result, (path, content, project_idx)) in results.iter_mut().zip(&file_contents) {
+        let project = &projects.list[*project_idx];
+        let Some(relative) = cross_file_path(project, path, result) else {
+            continue;
+        };
+        let Some(parsed) = project.cross_file.parse(&relative, content) else {
+            continue;
+        };
+        let index = cross_file_indexes
+            .entry(*project_idx)
+            .or_insert_with(|| project.cross_file.index(&project.root, tree.as_deref()));
+        let errors = project.cross_file.check(index, &relative, &parsed);
+        if !errors.is_empty() {
+            result.errors.extend(errors);
+            result.valid = false;
+            result.skipped = false;
+            result.apply_severities(&project.config.severity);
+        }
+    }
+
     // Compute summary
     let checked = results.iter().filter(|r| !r.skipped).count();
     let skipped = results.iter().filter(|r| r.skipped).count();
     let invalid = results.iter().filter(|r| !r.valid && !r.skipped).count();
     let valid = checked - invalid;
-    let total_errors: usize = results.iter().map(|r| r.errors.len()).sum();
+    let total_errors: usize = results.iter().map(|r| r.error_count()).sum();
+    let file_warnings: usize = results.iter().map(|r| r.warning_count()).sum();
     let has_tool_error = results.iter().any(|r| r.tool_error) || has_file_read_error;
 
     let summary = Summary {
@@ -715,16 +1246,39 @@ fn run_check(args: CheckArgs) -> ExitCode {
         valid_files: valid,
         invalid_files: invalid,
         skipped_files: skipped,
+        cached_files: cached_files.into_inner(),
         total_errors,
-        total_warnings: warnings.len(),
+        total_warnings: warnings.len() + file_warnings,
         duration: start.elapsed(),
         jobs: args.jobs as usize,
         has_tool_error,
     };
 
+    let human_options = output::HumanOptions {
+        quiet: args.quiet,
+        max_diagnostics: args.max_diagnostics,
+    };
     match args.format {
         Format::Human => {
-            output::render_human(&results, &warnings, &summary, &sources, &mut stderr);
+            output::render_human(
+                &results,
+                &warnings,
+                &summary,
+                &sources,
+                &human_options,
+                &mut stderr,
+            );
+        }
+        Format::Compact => {
+            let mut stdout = std::io::stdout().lock();
+            output::render_compact(
+                &results,
+                &warnings,
+                &summary,
+                &human_options,
+                &mut stdout,
+                &mut stderr,
+            );
         }
         Format::Json => {
             let verbose_infos = if verbose {
@@ -735,6 +1289,28 @@ fn run_check(args: CheckArgs) -> ExitCode {
             let mut stdout = std::io::stdout().lock();
             output::render_json(&results, &warnings, &summary, verbose_infos, &mut stdout);
         }
+        Format::Jsonl => {
+            let mut stdout = std::io::stdout().lock();
+            for (i, result) in results.iter().enumerate() {
+                if !streamed[i] {
+                    let info = verbose_file_infos.get(i).and_then(Option::as_ref);
+                    output::render_jsonl_file(result, info, &mut stdout);
+                }
+            }
+            output::render_jsonl_summary(&warnings, &summary, &mut stdout);
+        }
+        Format::Junit => {
+            let mut stdout = std::io::stdout().lock();
+            output::render_junit(&results, &warnings, &summary, &mut stdout);
+        }
+        Format::Checkstyle => {
+            let mut stdout = std::io::stdout().lock();
+            output::render_checkstyle(&results, &mut stdout);
+        }
+        Format::Github => {
+            let mut stdout = std::io::stdout().lock();
+            output::render_github(&results, &warnings, &mut stdout);
+        }
     }
 
     // Exit code: 2 for tool errors, 1 for validation errors, 0 for all valid
@@ -747,34 +1323,508 @@ fn run_check(args: CheckArgs) -> ExitCode {
     }
 }
 
-/// Load config, returning an error if the config fails to parse.
+/// The path of a file relative to its project when the project's cross-file
+/// rules apply to it, so it must be checked again after every file has been
+/// validated.
+fn cross_file_path(project: &Project, path: &str, result: &FileResult) -> Option<String> {
+    if result.tool_error || embed::is_host_file(Path::new(path)) {
+        return None;
+    }
+    let relative = project.relative_path(path);
+    project.cross_file.applies_to(&relative).then_some(relative)
+}
+
+/// The files among `files` that git selected, and those whose schema it
+/// selected: by `--schema`, a config mapping, or their `$schema`. A schema
+/// counts as selected when any local file it or the documents it references
+/// were read from is. Files are read from `tree` if given.
+///
+/// Also returns the warnings of the schemas compiled along the way, which
+/// later compiles won't repeat.
+fn select_files(
+    files: Vec<(PathBuf, usize)>,
+    selected: &HashSet<PathBuf>,
+    projects: &Projects,
+    schema_override: Option<&schema::SchemaSource>,
+    schema_cache: &SchemaCache,
+    no_cache: bool,
+    tree: Option<&git::Tree>,
+) -> (Vec<(PathBuf, usize)>, Vec<Warning>) {
+    let warnings = std::sync::Mutex::new(Vec::new());
+    // Files only in the index aren't on disk to canonicalize.
+    let is_selected = |path: &Path| {
+        selected.contains(&std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()))
+    };
+    let selected_schema = |source: &schema::SchemaSource, options: &schema::CompileOptions| {
+        // Only compile URL schemas when a rewrite could map a document to a
+        // local file; otherwise nothing local can have changed.
+        let local = match source {
+            schema::SchemaSource::File(_) => true,
+            schema::SchemaSource::Url(_) => options
+                .url_rewrites
+                .iter()
+                .any(|r| matches!(r.target, schema::RewriteTarget::Directory(_))),
+        };
+        if !local {
+            return false;
+        }
+        if let Ok((_, compile_warnings, _)) = schema_cache.get_or_compile(source, options, no_cache)
+        {
+            let mut warnings = warnings.lock().unwrap_or_else(|e| e.into_inner());
+            warnings.extend(compile_warnings);
+        }
+        match schema_cache.local_files(source, options) {
+            Some(files) => files.iter().any(|path| is_selected(path)),
+            None => matches!(source, schema::SchemaSource::File(path) if is_selected(path)),
+        }
+    };
+    if schema_override.is_some_and(|s| selected_schema(s, &projects.list[0].compile_options)) {
+        return (
+            files,
+            warnings.into_inner().unwrap_or_else(|e| e.into_inner()),
+        );
+    }
+    let files = files
+        .into_par_iter()
+        .filter(|(path, idx)| {
+            if is_selected(path) {
+                return true;
+            }
+            if schema_override.is_some() {
+                return false;
+            }
+            let project = &projects.list[*idx];
+            let options = &project.compile_options;
+            let relative = project.relative_path(&path.display().to_string());
+            if let Some(source) = project.mappings.resolve(&relative, &project.root) {
+                return selected_schema(&source, options);
+            }
+            let base_dir = path.parent().unwrap_or(Path::new("."));
+            match tree {
+                Some(tree) => tree.read(path),
+                None => std::fs::read_to_string(path),
+            }
+            .ok()
+            .and_then(|content| parse::extract_schema_field_from_str(&content))
+            .is_some_and(|s| selected_schema(&schema::resolve_schema_ref(&s, base_dir), options))
+        })
+        .collect();
+    (
+        files,
+        warnings.into_inner().unwrap_or_else(|e| e.into_inner()),
+    )
+}
+
+/// Everything needed to check the files governed by one config file.
+struct Project {
+    /// Directory containing the config; globs and relative paths resolve
+    /// against it.
+    root: PathBuf,
+    config: Config,
+    /// The config file, or `None` when using the default config.
+    config_path: Option<PathBuf>,
+    mappings: CompiledSchemaMappings,
+    parsers: CompiledParsers,
+    lint: CompiledLint,
+    cross_file: CrossFileRules,
+    compile_options: schema::CompileOptions,
+    strict: bool,
+    check_syntax: bool,
+}
+
+impl Project {
+    /// Compile a config, applying CLI overrides.
+    fn new(
+        config: Config,
+        config_path: Option<PathBuf>,
+        root: PathBuf,
+        args: &ValidationArgs,
+    ) -> Result<Self, String> {
+        let mut compile_options = config
+            .compile_options(&root)
+            .map_err(|e| format!("invalid config: {e}"))?;
+        if args.default_draft.is_some() {
+            compile_options.default_draft = args.default_draft;
+        }
+        if args.validate_formats {
+            compile_options.validate_formats = Some(true);
+        } else if args.no_validate_formats {
+            compile_options.validate_formats = Some(false);
+        }
+        if args.deny_unknown_formats {
+            compile_options.ignore_unknown_formats = false;
+        }
+        if args.validate_content {
+            compile_options.validate_content = true;
+        }
+
+        let mappings = CompiledSchemaMappings::compile(&config)
+            .map_err(|e| format!("failed to compile schema mappings: {e}"))?;
+        let parsers = CompiledParsers::compile(&config)
+            .map_err(|e| format!("failed to compile parser patterns: {e}"))?;
+        let lint = CompiledLint::compile(&config)
+            .map_err(|e| format!("failed to compile lint patterns: {e}"))?;
+        let cross_file = CrossFileRules::compile(&config)
+            .map_err(|e| format!("failed to compile cross-file rules: {e}"))?;
+
+        Ok(Self {
+            root,
+            strict: args.strict || config.strict,
+            check_syntax: args.syntax || config.check_syntax == CheckSyntax::All,
+            config,
+            config_path,
+            mappings,
+            parsers,
+            lint,
+            cross_file,
+            compile_options,
+        })
+    }
+
+    /// How to parse the file at `relative`, and what to do if it has no
+    /// schema.
+    fn file_options(&self, relative: &str) -> validate::FileOptions {
+        validate::FileOptions {
+            dialect: self.parsers.resolve(relative),
+            strict: self.strict,
+            check_syntax: self.check_syntax,
+            lint: self.lint.resolve(relative),
+        }
+    }
+
+    /// `path` relative to the project root, as config globs see it. Falls
+    /// back to `path` itself when it lies outside the root. Paths that
+    /// aren't on disk, such as those of a commit's files, are used as given.
+    fn relative_path(&self, path: &str) -> String {
+        let abs = std::fs::canonicalize(Path::new(path)).unwrap_or_else(|_| PathBuf::from(path));
+        abs.strip_prefix(&self.root)
+            .ok()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string())
+    }
+}
+
+/// The root project plus any projects for config files nested below it.
+/// Each nested config governs its own subtree.
+struct Projects {
+    /// The root project comes first.
+    list: Vec<Project>,
+    /// Nested config path → index into `list`.
+    nested: HashMap<PathBuf, usize>,
+    overrides: ValidationArgs,
+    /// Log each nested config as it is loaded.
+    log_nested: bool,
+    /// The commit to read configs and files from, instead of the disk.
+    tree: Option<Arc<git::Tree>>,
+    /// Bypass the schema cache for remote configs that nested configs extend.
+    no_cache: bool,
+}
+
+impl Projects {
+    fn new(
+        loaded: Option<LoadedConfig>,
+        root: PathBuf,
+        overrides: &ValidationArgs,
+        log_nested: bool,
+        tree: Option<Arc<git::Tree>>,
+        no_cache: bool,
+    ) -> Result<Self, String> {
+        let (config, config_path) = match loaded {
+            Some(LoadedConfig {
+                config,
+                mut sources,
+            }) => (config, Some(sources.swap_remove(0))),
+            None => (Config::default_config(), None),
+        };
+        Ok(Self {
+            list: vec![Project::new(config, config_path, root, overrides)?],
+            nested: HashMap::new(),
+            overrides: overrides.clone(),
+            log_nested,
+            tree,
+            no_cache,
+        })
+    }
+
+    /// Load the project for a nested config, or return the already loaded one.
+    fn load_nested(
+        &mut self,
+        config_path: &Path,
+        stderr: &mut impl Write,
+    ) -> Result<usize, String> {
+        let config_path = std::fs::canonicalize(config_path).unwrap_or(config_path.to_path_buf());
+        if let Some(&idx) = self.nested.get(&config_path) {
+            return Ok(idx);
+        }
+        let (config, _) =
+            Config::load_from(&config_path, self.tree.as_deref(), self.no_cache, false)
+                .map_err(|e| format!("failed to load config: {e}"))?;
+        let root = config_path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        if self.log_nested {
+            output::verbose_log(
+                stderr,
+                &format!("config: {} (nested)", config_path.display()),
+            );
+        }
+        self.list.push(Project::new(
+            config,
+            Some(config_path.clone()),
+            root,
+            &self.overrides,
+        )?);
+        let idx = self.list.len() - 1;
+        self.nested.insert(config_path, idx);
+        Ok(idx)
+    }
+
+    /// The project governing `path`: the nearest config file strictly below
+    /// the root project's directory, or the root project.
+    fn project_for(&mut self, path: &Path, stderr: &mut impl Write) -> Result<usize, String> {
+        let path = std::fs::canonicalize(path).unwrap_or(path.to_path_buf());
+        let root = &self.list[0].root;
+        let config_path = match &self.tree {
+            Some(tree) => discover::find_tree_config_file(tree, &path),
+            None => discover::find_config_file(&path),
+        };
+        match config_path {
+            Some(config_path)
+                if config_path
+                    .parent()
+                    .is_some_and(|dir| dir != root && dir.starts_with(root)) =>
+            {
+                self.load_nested(&config_path, stderr)
+            }
+            _ => Ok(0),
+        }
+    }
+
+    /// Discover files under `walk_roots`, descending into nested projects.
+    /// Returns each file with the index of the project that governs it.
+    #[allow(clippy::type_complexity)]
+    fn discover(
+        &mut self,
+        walk_roots: &[PathBuf],
+        stderr: &mut impl Write,
+    ) -> Result<(Vec<(PathBuf, usize)>, Vec<Warning>), String> {
+        // Group the walk roots by the project they fall under.
+        let mut pending: Vec<(usize, Vec<PathBuf>)> = Vec::new();
+        for walk_root in walk_roots {
+            let idx = self.project_for(walk_root, stderr)?;
+            match pending.iter_mut().find(|(i, _)| *i == idx) {
+                Some((_, roots)) => roots.push(walk_root.clone()),
+                None => pending.push((idx, vec![walk_root.clone()])),
+            }
+        }
+
+        let mut files = Vec::new();
+        let mut warnings = Vec::new();
+        let mut next = 0;
+        while let Some((idx, roots)) = pending.get(next).cloned() {
+            next += 1;
+            let project = &self.list[idx];
+            let discovery = match &self.tree {
+                Some(tree) => {
+                    discover::discover_tree_files(tree, &project.root, &roots, &project.config)
+                }
+                None => discover::discover_files(&project.root, &roots, &project.config),
+            }
+            .map_err(|e| format!("failed to discover files: {e}"))?;
+            files.extend(discovery.files.into_iter().map(|f| (f, idx)));
+            warnings.extend(discovery.warnings);
+            for config_path in discovery.nested_configs {
+                let nested = self.load_nested(&config_path, stderr)?;
+                let root = self.list[nested].root.clone();
+                pending.push((nested, vec![root]));
+            }
+        }
+        Ok((files, warnings))
+    }
+}
+
+fn run_explain(args: ExplainArgs) -> ExitCode {
+    use jvl::explain::{CacheStatus, Report, SchemaOrigin};
+
+    let mut stderr = std::io::stderr().lock();
+    let fail = |stderr: &mut std::io::StderrLock, msg: String| {
+        let diag = ToolDiagnostic::error(msg);
+        let _ = writeln!(stderr, "{:?}", miette::Report::new(diag));
+        ExitCode::from(2)
+    };
+
+    if embed::is_host_file(&args.file) {
+        return fail(
+            &mut stderr,
+            format!(
+                "{} embeds JSON in Markdown; jvl explain only supports JSON files",
+                args.file.display()
+            ),
+        );
+    }
+    let pointer = match args.pointer.as_deref().map(jvl::explain::parse_pointer) {
+        None => None,
+        Some(Ok(p)) => Some(p),
+        Some(Err(e)) => return fail(&mut stderr, e),
+    };
+    let cwd = match std::env::current_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            return fail(
+                &mut stderr,
+                format!("cannot determine current directory: {e}"),
+            );
+        }
+    };
+    let (loaded_config, project_root) = match load_config(&args.config, &cwd, None, args.no_cache) {
+        Ok(result) => result,
+        Err(e) => return fail(&mut stderr, format!("failed to load config: {e}")),
+    };
+    let project_root = std::fs::canonicalize(&project_root).unwrap_or(project_root);
+    let mut projects = match Projects::new(
+        loaded_config,
+        project_root,
+        &args.validation,
+        false,
+        None,
+        args.no_cache,
+    ) {
+        Ok(p) => p,
+        Err(msg) => return fail(&mut stderr, msg),
+    };
+    let project = match projects.project_for(&args.file, &mut stderr) {
+        Ok(idx) => &projects.list[idx],
+        Err(msg) => return fail(&mut stderr, msg),
+    };
+
+    let path = args.file.display().to_string();
+    let content = match std::fs::read_to_string(&args.file) {
+        Ok(c) => parse::strip_bom(&c).to_owned(),
+        Err(e) => return fail(&mut stderr, format!("failed to read {path}: {e}")),
+    };
+    let relative = std::fs::canonicalize(&args.file)
+        .ok()
+        .and_then(|abs| {
+            abs.strip_prefix(&project.root)
+                .ok()
+                .map(|p| p.to_string_lossy().to_string())
+        })
+        .unwrap_or_else(|| path.clone());
+    let file_options = project.file_options(&relative);
+    let parsed_value = parse::parse_with_options(&content, &file_options.dialect.parse_options())
+        .ok()
+        .map(|p| p.value);
+
+    // Mirror `jvl check`: the flag, then config mappings, then `$schema`.
+    let (explicit, origin) = if let Some(s) = &args.schema {
+        let source = schema::resolve_schema_ref(s, &cwd);
+        (Some(source.clone()), Some((source, SchemaOrigin::Flag)))
+    } else if let Some(m) = project.mappings.resolve_match(&relative, &project.root) {
+        let origin = SchemaOrigin::Mapping {
+            config: project.config_path.clone(),
+            index: m.index,
+            pattern: m.pattern,
+        };
+        (Some(m.source.clone()), Some((m.source, origin)))
+    } else {
+        let inline = parsed_value
+            .as_ref()
+            .and_then(|v| schema::resolve_schema_from_value(v, &args.file));
+        (None, inline.map(|s| (s, SchemaOrigin::SchemaField)))
+    };
+
+    let schema_cache = SchemaCache::new();
+    let (mut result, _, cache_outcome, _) = validate::validate_file(
+        &path,
+        &content,
+        explicit.as_ref(),
+        &schema_cache,
+        &project.compile_options,
+        args.no_cache,
+        file_options,
+    );
+    result.apply_severities(&project.config.severity);
+    // Mirror `jvl check`: 2 for a schema that can't be loaded, 1 for errors.
+    let code = if result.tool_error {
+        ExitCode::from(2)
+    } else if !result.valid {
+        ExitCode::from(1)
+    } else {
+        ExitCode::SUCCESS
+    };
+    let cache = match (&origin, cache_outcome) {
+        (Some((schema::SchemaSource::File(_), _)), _) => Some(CacheStatus::LocalFile),
+        (_, Some(outcome)) => Some(CacheStatus::Url(outcome)),
+        _ => None,
+    };
+
+    let chain = match (&pointer, &origin, &parsed_value) {
+        (Some(pointer), Some((source, _)), Some(value)) => schema_cache
+            .get_schema_value(source, &project.compile_options)
+            .map(|root| {
+                jvl::explain::subschema_chain(
+                    &root,
+                    pointer,
+                    value,
+                    &project.compile_options,
+                    args.no_cache,
+                )
+            }),
+        _ => None,
+    };
+
+    let report = Report {
+        file: path,
+        schema: origin,
+        cache,
+        diagnostics: result.errors,
+        chain,
+    };
+    print!("{report}");
+    code
+}
+
+/// A config found by [`load_config`].
+struct LoadedConfig {
+    config: Config,
+    /// The config file, then every local file it extends.
+    sources: Vec<PathBuf>,
+}
+
+/// Load config, returning an error if the config fails to parse. Configs
+/// are read from `tree` if given, and remote configs they extend bypass the
+/// schema cache if `no_cache` is set.
 fn load_config(
     config_path: &Option<PathBuf>,
     cwd: &Path,
-) -> Result<(Option<Config>, PathBuf), discover::ConfigError> {
+    tree: Option<&git::Tree>,
+    no_cache: bool,
+) -> Result<(Option<LoadedConfig>, PathBuf), discover::ConfigError> {
     if let Some(path) = config_path {
         // Explicit --config: failure is a hard error
-        let cfg = Config::load(path)?;
         let abs_path = if path.is_absolute() {
             path.clone()
         } else {
             cwd.join(path)
         };
+        // Tree paths are absolute, so keep `path` as given only on disk.
+        let (config, sources) = match tree {
+            Some(tree) => Config::load_from(&abs_path, Some(tree), no_cache, false)?,
+            None => Config::load_from(path, None, no_cache, false)?,
+        };
         let root = abs_path.parent().unwrap_or(cwd).to_path_buf();
-        Ok((Some(cfg), root))
+        Ok((Some(LoadedConfig { config, sources }), root))
     } else {
-        // Auto-discover: failure is non-fatal (use defaults)
-        match discover::find_config_file(cwd) {
-            Some(path) => match Config::load(&path) {
-                Ok(cfg) => {
-                    let root = path.parent().unwrap_or(cwd).to_path_buf();
-                    Ok((Some(cfg), root))
-                }
-                Err(e) => {
-                    // Auto-discovered config failed to parse — treat as tool error
-                    Err(e)
-                }
-            },
+        // Auto-discover: an auto-discovered config that fails to parse is
+        // still a tool error.
+        let found = match tree {
+            Some(tree) => discover::find_tree_config_file(tree, cwd),
+            None => discover::find_config_file(cwd),
+        };
+        match found {
+            Some(path) => {
+                let (config, sources) = Config::load_from(&path, tree, no_cache, false)?;
+                let root = path.parent().unwrap_or(cwd).to_path_buf();
+                Ok((Some(LoadedConfig { config, sources }), root))
+            }
             None => Ok((None, cwd.to_path_buf())),
         }
     }
@@ -1,7 +1,10 @@
-use crate::diagnostic::{FileResult, Severity, ToolDiagnostic, Warning};
+use crate::diagnostic::{
+    FileDiagnostic, FileResult, Severity, SourceLocation, ToolDiagnostic, Warning,
+};
 use crate::schema::CacheOutcome;
 use owo_colors::Stream::Stderr;
 use owo_colors::{OwoColorize, Style};
+use schemars::JsonSchema;
 use serde::Serialize;
 use std::collections::HashMap;
 use std::io::Write;
@@ -13,6 +16,8 @@ pub struct Summary {
     pub valid_files: usize,
     pub invalid_files: usize,
     pub skipped_files: usize,
+    /// Checked files whose result came from the result cache.
+    pub cached_files: usize,
     pub total_errors: usize,
     pub total_warnings: usize,
     pub duration: Duration,
@@ -20,11 +25,90 @@ pub struct Summary {
     pub has_tool_error: bool,
 }
 
-/// Output format.
+/// Output format of `jvl check`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
 pub enum Format {
     Human,
+    /// One line per diagnostic: `path:line:col: severity code: message`.
+    Compact,
     Json,
+    /// JSON Lines: one record per file as it finishes, then a summary.
+    Jsonl,
+    /// JUnit XML, one testcase per file.
+    Junit,
+    /// Checkstyle XML.
+    Checkstyle,
+    /// GitHub Actions workflow commands, shown as annotations.
+    Github,
+}
+
+/// Output format of listings such as `jvl cache list`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ListFormat {
+    Human,
+    Json,
+}
+
+/// How much the human-readable formats print.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HumanOptions {
+    /// Print only the summary, and nothing when every file is valid.
+    pub quiet: bool,
+    /// Print at most this many diagnostics, then a count of the rest.
+    pub max_diagnostics: Option<usize>,
+}
+
+/// The diagnostics to print under [`HumanOptions`], with their files.
+struct Shown<'a> {
+    diagnostics: Vec<(&'a FileResult, &'a FileDiagnostic)>,
+    hidden_errors: usize,
+    hidden_warnings: usize,
+}
+
+impl<'a> Shown<'a> {
+    fn select(results: &'a [FileResult], options: &HumanOptions) -> Self {
+        let all = results
+            .iter()
+            .filter(|r| !r.skipped)
+            .flat_map(|r| r.errors.iter().map(move |d| (r, d)));
+        let limit = if options.quiet {
+            0
+        } else {
+            options.max_diagnostics.unwrap_or(usize::MAX)
+        };
+        let mut shown = Self {
+            diagnostics: Vec::new(),
+            hidden_errors: 0,
+            hidden_warnings: 0,
+        };
+        for (result, diag) in all {
+            if shown.diagnostics.len() < limit {
+                shown.diagnostics.push((result, diag));
+            } else if diag.severity == Severity::Error {
+                shown.hidden_errors += 1;
+            } else {
+                shown.hidden_warnings += 1;
+            }
+        }
+        shown
+    }
+
+    /// Footer such as "3 more errors not shown", when `--max-diagnostics`
+    /// hid anything. Quiet mode hides everything and says nothing.
+    fn footer(&self, options: &HumanOptions) -> Option<String> {
+        let hidden = match (self.hidden_errors, self.hidden_warnings) {
+            _ if options.quiet => return None,
+            (0, 0) => return None,
+            (errors, 0) => plural(errors, "more error", "more errors"),
+            (0, warnings) => plural(warnings, "more warning", "more warnings"),
+            (errors, warnings) => format!(
+                "{} and {}",
+                plural(errors, "more error", "more errors"),
+                plural(warnings, "warning", "warnings")
+            ),
+        };
+        Some(format!("… {hidden} not shown"))
+    }
 }
 
 /// Per-file verbose diagnostic info collected during processing.
@@ -87,36 +171,92 @@ pub fn verbose_log(stderr: &mut impl Write, msg: &str) {
     );
 }
 
+/// Render warnings not tied to a file through miette, unless quiet.
+fn render_tool_warnings(warnings: &[Warning], options: &HumanOptions, stderr: &mut impl Write) {
+    if options.quiet {
+        return;
+    }
+    for warning in warnings {
+        let diag = ToolDiagnostic::warning(format!("{}: {}", warning.code, warning.message));
+        let _ = writeln!(stderr, "{:?}", miette::Report::new(diag));
+    }
+}
+
 /// Render results in human format using miette.
 pub fn render_human(
     results: &[FileResult],
     warnings: &[Warning],
     summary: &Summary,
     sources: &HashMap<&str, &str>,
+    options: &HumanOptions,
     stderr: &mut impl Write,
 ) {
-    // Render warnings through miette
-    for warning in warnings {
-        let diag = ToolDiagnostic::warning(format!("{}: {}", warning.code, warning.message));
-        let _ = writeln!(stderr, "{:?}", miette::Report::new(diag));
-    }
+    render_tool_warnings(warnings, options, stderr);
 
-    // Render errors per file
-    for result in results {
-        if result.skipped || result.errors.is_empty() {
-            continue;
-        }
+    let shown = Shown::select(results, options);
+    for (result, diag) in &shown.diagnostics {
         let source = sources.get(result.path.as_str()).copied().unwrap_or("");
-        for diag in &result.errors {
-            let renderable = diag.to_renderable(&result.path, source);
-            let report = miette::Report::new(renderable);
-            let _ = writeln!(stderr, "{report:?}");
-        }
+        let renderable = diag.to_renderable(&result.path, source);
+        let report = miette::Report::new(renderable);
+        let _ = writeln!(stderr, "{report:?}");
+    }
+    if let Some(footer) = shown.footer(options) {
+        let _ = writeln!(stderr, "{footer}");
     }
 
-    // Summary
-    let _ = writeln!(stderr);
+    render_summary(results, summary, options, stderr);
+}
+
+/// Render results one line per diagnostic to `stdout`, in the form compilers
+/// use (`path:line:col: severity code: message`) so editors can jump to
+/// them. Everything else goes to `stderr`.
+pub fn render_compact(
+    results: &[FileResult],
+    warnings: &[Warning],
+    summary: &Summary,
+    options: &HumanOptions,
+    stdout: &mut impl Write,
+    stderr: &mut impl Write,
+) {
+    render_tool_warnings(warnings, options, stderr);
+
+    let shown = Shown::select(results, options);
+    for (result, diag) in &shown.diagnostics {
+        let position = match &diag.location {
+            Some(loc) => format!("{}:{}:{}", result.path, loc.line, loc.column),
+            None => result.path.clone(),
+        };
+        let _ = writeln!(
+            stdout,
+            "{position}: {} {}: {}",
+            severity_name(diag.severity),
+            diag.code,
+            diag.message
+        );
+    }
+    if let Some(footer) = shown.footer(options) {
+        let _ = writeln!(stderr, "{footer}");
+    }
+
+    render_summary(results, summary, options, stderr);
+}
+
+/// Render the closing summary lines. Quiet mode prints nothing when every
+/// file is valid.
+fn render_summary(
+    results: &[FileResult],
+    summary: &Summary,
+    options: &HumanOptions,
+    stderr: &mut impl Write,
+) {
+    if options.quiet && summary.invalid_files == 0 {
+        return;
+    }
+    if !options.quiet {
+        let _ = writeln!(stderr);
+    }
     let duration = format_duration(summary.duration);
+    let file_warnings: usize = results.iter().map(|r| r.warning_count()).sum();
     if summary.invalid_files == 0 {
         let msg = format!(
             "{} {} ({})",
@@ -148,6 +288,25 @@ pub fn render_human(
                 meta.if_supports_color(Stderr, |text| text.dimmed())
             );
         }
+        if summary.cached_files > 0 {
+            let meta = format!(
+                "  Reused {}",
+                plural(summary.cached_files, "cached result", "cached results"),
+            );
+            let _ = writeln!(
+                stderr,
+                "{}",
+                meta.if_supports_color(Stderr, |text| text.dimmed())
+            );
+        }
+        if file_warnings > 0 {
+            let meta = format!("  Found {}", plural(file_warnings, "warning", "warnings"));
+            let _ = writeln!(
+                stderr,
+                "{}",
+                meta.if_supports_color(Stderr, |text| text.dimmed())
+            );
+        }
     } else {
         let primary = format!(
             "{} Found {} in {}",
@@ -172,6 +331,18 @@ pub fn render_human(
                 plural(summary.skipped_files, "file", "files"),
             ));
         }
+        if summary.cached_files > 0 {
+            meta.push_str(&format!(
+                ", reused {}",
+                plural(summary.cached_files, "cached result", "cached results"),
+            ));
+        }
+        if file_warnings > 0 {
+            meta.push_str(&format!(
+                ", {}",
+                plural(file_warnings, "warning", "warnings")
+            ));
+        }
         meta.push_str(&format!(" ({duration})"));
         let _ = writeln!(
             stderr,
@@ -183,7 +354,7 @@ pub fn render_human(
 
 // --- Typed JSON output structures ---
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 struct JsonOutput<'a> {
     version: u32,
     valid: bool,
@@ -192,7 +363,7 @@ struct JsonOutput<'a> {
     summary: JsonSummary,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 struct JsonFileResult {
     path: String,
     valid: bool,
@@ -211,7 +382,7 @@ struct JsonFileResult {
     errors: Vec<JsonError>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 struct JsonError {
     code: String,
     message: String,
@@ -220,9 +391,21 @@ struct JsonError {
     location: Option<JsonLocation>,
     #[serde(skip_serializing_if = "Option::is_none")]
     schema_path: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[schemars(default)]
+    related: Vec<JsonRelated>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
+struct JsonRelated {
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    location: Option<JsonLocation>,
+}
+
+#[derive(Serialize, JsonSchema)]
 struct JsonLocation {
     line: usize,
     column: usize,
@@ -230,12 +413,24 @@ struct JsonLocation {
     length: usize,
 }
 
-#[derive(Serialize)]
+impl From<&SourceLocation> for JsonLocation {
+    fn from(loc: &SourceLocation) -> Self {
+        Self {
+            line: loc.line,
+            column: loc.column,
+            offset: loc.offset,
+            length: loc.length,
+        }
+    }
+}
+
+#[derive(Serialize, JsonSchema)]
 struct JsonSummary {
     checked_files: usize,
     valid_files: usize,
     invalid_files: usize,
     skipped_files: usize,
+    cached_files: usize,
     errors: usize,
     warnings: usize,
     duration_ms: u64,
@@ -268,73 +463,8 @@ fn build_json_output<'a>(
         .enumerate()
         .filter(|(_, r)| !r.skipped)
         .map(|(i, r)| {
-            let errors: Vec<JsonError> = r
-                .errors
-                .iter()
-                .map(|e| {
-                    let location = e.location.as_ref().map(|loc| JsonLocation {
-                        line: loc.line,
-                        column: loc.column,
-                        offset: loc.offset,
-                        length: loc.length,
-                    });
-
-                    JsonError {
-                        code: e.code.clone(),
-                        message: e.message.clone(),
-                        severity: e.severity,
-                        location,
-                        schema_path: e.schema_path.clone(),
-                    }
-                })
-                .collect();
-
-            let (schema, schema_via, cache, duration_ms, compile_ms, validate_ms) =
-                if let Some(infos) = verbose_infos
-                    && let Some(Some(info)) = infos.get(i)
-                {
-                    let schema = if info.schema.is_empty() {
-                        None
-                    } else {
-                        Some(info.schema.clone())
-                    };
-                    let schema_via = if info.schema_via.is_empty() {
-                        None
-                    } else {
-                        Some(info.schema_via.clone())
-                    };
-                    let cache = info.cache.map(|c| c.as_str().to_string());
-                    let duration_ms =
-                        Some(u64::try_from(info.duration.as_millis()).unwrap_or(u64::MAX));
-                    let compile_ms = info
-                        .compile_duration
-                        .map(|d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX));
-                    let validate_ms = info
-                        .validate_duration
-                        .map(|d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX));
-                    (
-                        schema,
-                        schema_via,
-                        cache,
-                        duration_ms,
-                        compile_ms,
-                        validate_ms,
-                    )
-                } else {
-                    (None, None, None, None, None, None)
-                };
-
-            JsonFileResult {
-                path: r.path.clone(),
-                valid: r.valid,
-                schema,
-                schema_via,
-                cache,
-                duration_ms,
-                compile_ms,
-                validate_ms,
-                errors,
-            }
+            let info = verbose_infos.and_then(|infos| infos.get(i)?.as_ref());
+            json_file_result(r, info)
         })
         .collect();
 
@@ -343,14 +473,332 @@ fn build_json_output<'a>(
         valid: summary.invalid_files == 0 && !summary.has_tool_error,
         warnings,
         files,
-        summary: JsonSummary {
-            checked_files: summary.checked_files,
-            valid_files: summary.valid_files,
-            invalid_files: summary.invalid_files,
-            skipped_files: summary.skipped_files,
-            errors: summary.total_errors,
-            warnings: summary.total_warnings,
-            duration_ms: u64::try_from(summary.duration.as_millis()).unwrap_or(u64::MAX),
+        summary: json_summary(summary),
+    }
+}
+
+fn millis(d: Duration) -> u64 {
+    u64::try_from(d.as_millis()).unwrap_or(u64::MAX)
+}
+
+fn json_file_result(r: &FileResult, info: Option<&VerboseFileInfo>) -> JsonFileResult {
+    let errors: Vec<JsonError> = r
+        .errors
+        .iter()
+        .map(|e| JsonError {
+            code: e.code.clone(),
+            message: e.message.clone(),
+            severity: e.severity,
+            location: e.location.as_ref().map(JsonLocation::from),
+            schema_path: e.schema_path.clone(),
+            related: e
+                .related
+                .iter()
+                .map(|r| JsonRelated {
+                    message: r.message.clone(),
+                    path: r.path.clone(),
+                    location: r.location.as_ref().map(JsonLocation::from),
+                })
+                .collect(),
+        })
+        .collect();
+
+    let non_empty = |s: &String| (!s.is_empty()).then(|| s.clone());
+    JsonFileResult {
+        path: r.path.clone(),
+        valid: r.valid,
+        schema: info.and_then(|i| non_empty(&i.schema)),
+        schema_via: info.and_then(|i| non_empty(&i.schema_via)),
+        cache: info.and_then(|i| i.cache).map(|c| c.as_str().to_string()),
+        duration_ms: info.map(|i| millis(i.duration)),
+        compile_ms: info.and_then(|i| i.compile_duration).map(millis),
+        validate_ms: info.and_then(|i| i.validate_duration).map(millis),
+        errors,
+    }
+}
+
+fn json_summary(summary: &Summary) -> JsonSummary {
+    JsonSummary {
+        checked_files: summary.checked_files,
+        valid_files: summary.valid_files,
+        invalid_files: summary.invalid_files,
+        skipped_files: summary.skipped_files,
+        cached_files: summary.cached_files,
+        errors: summary.total_errors,
+        warnings: summary.total_warnings,
+        duration_ms: millis(summary.duration),
+    }
+}
+
+// --- JSON Lines output ---
+
+/// A line of `--format jsonl` output.
+#[derive(Serialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum JsonlRecord<'a> {
+    /// The result of one checked file, written as soon as it is known.
+    File(JsonFileResult),
+    /// The last line, written once every file is checked.
+    Summary {
+        version: u32,
+        valid: bool,
+        warnings: &'a [Warning],
+        summary: JsonSummary,
+    },
+}
+
+fn write_jsonl(record: &JsonlRecord, stdout: &mut impl Write) {
+    let line = serde_json::to_string(record).unwrap();
+    let _ = writeln!(stdout, "{line}");
+}
+
+/// Write the JSON Lines record of one file. Skipped files aren't written,
+/// as in the JSON format; the summary counts them.
+pub fn render_jsonl_file(
+    result: &FileResult,
+    verbose_info: Option<&VerboseFileInfo>,
+    stdout: &mut impl Write,
+) {
+    if !result.skipped {
+        write_jsonl(
+            &JsonlRecord::File(json_file_result(result, verbose_info)),
+            stdout,
+        );
+    }
+}
+
+/// Write the closing JSON Lines summary record.
+pub fn render_jsonl_summary(warnings: &[Warning], summary: &Summary, stdout: &mut impl Write) {
+    write_jsonl(
+        &JsonlRecord::Summary {
+            version: 1,
+            valid: summary.invalid_files == 0 && !summary.has_tool_error,
+            warnings,
+            summary: json_summary(summary),
         },
+        stdout,
+    );
+}
+
+/// The JSON Schema of `--format json` output, or of one line of
+/// `--format jsonl` output.
+pub fn output_schema(jsonl: bool) -> schemars::schema::RootSchema {
+    if jsonl {
+        schemars::schema_for!(JsonlRecord)
+    } else {
+        schemars::schema_for!(JsonOutput)
+    }
+}
+
+// --- Report formats for CI systems ---
+
+/// Escape text for an XML attribute value or element content, dropping
+/// characters XML 1.0 can't represent.
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\t' | '\r' => escaped.push(c),
+            c if (c as u32) < 0x20 => escaped.push('\u{FFFD}'),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    }
+}
+
+/// One line describing a diagnostic: `line:column: severity code: message`,
+/// followed by the schema path when there is one.
+fn describe(diag: &FileDiagnostic) -> String {
+    let mut line = match &diag.location {
+        Some(loc) => format!("{}:{}: ", loc.line, loc.column),
+        None => String::new(),
+    };
+    line.push_str(&format!(
+        "{} {}: {}",
+        severity_name(diag.severity),
+        diag.code,
+        diag.message
+    ));
+    if let Some(schema_path) = &diag.schema_path {
+        line.push_str(&format!(" (schema path: {schema_path})"));
+    }
+    line
+}
+
+/// Render results as JUnit XML: one testcase per file, failing with every
+/// error in the file. Skipped files are reported as skipped testcases.
+pub fn render_junit(
+    results: &[FileResult],
+    warnings: &[Warning],
+    summary: &Summary,
+    stdout: &mut impl Write,
+) {
+    let failures = results
+        .iter()
+        .filter(|r| !r.valid && !r.skipped && !r.tool_error)
+        .count();
+    let errors = results.iter().filter(|r| r.tool_error).count();
+    let counts = format!(
+        r#"tests="{}" failures="{failures}" errors="{errors}" skipped="{}" time="{:.3}""#,
+        results.len(),
+        summary.skipped_files,
+        summary.duration.as_secs_f64(),
+    );
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!("<testsuites name=\"jvl\" {counts}>\n"));
+    out.push_str(&format!("  <testsuite name=\"jvl\" {counts}>\n"));
+    for result in results {
+        let name = xml_escape(&result.path);
+        let testcase = format!("    <testcase name=\"{name}\" classname=\"{name}\"");
+        if result.skipped {
+            out.push_str(&testcase);
+            out.push_str(">\n      <skipped message=\"no schema\"/>\n    </testcase>\n");
+            continue;
+        }
+        if result.errors.is_empty() {
+            out.push_str(&testcase);
+            out.push_str("/>\n");
+            continue;
+        }
+        out.push_str(&testcase);
+        out.push_str(">\n");
+        if !result.valid {
+            let element = if result.tool_error {
+                "error"
+            } else {
+                "failure"
+            };
+            let failing: Vec<&FileDiagnostic> = result
+                .errors
+                .iter()
+                .filter(|d| d.severity == Severity::Error)
+                .collect();
+            let message = match failing.as_slice() {
+                [only] => format!("{}: {}", only.code, only.message),
+                _ => plural(failing.len(), "error", "errors"),
+            };
+            let body: Vec<String> = result.errors.iter().map(describe).collect();
+            out.push_str(&format!(
+                "      <{element} message=\"{}\" type=\"{}\">{}</{element}>\n",
+                xml_escape(&message),
+                xml_escape(failing.first().map_or("", |d| d.code.as_str())),
+                xml_escape(&body.join("\n")),
+            ));
+        } else {
+            let body: Vec<String> = result.errors.iter().map(describe).collect();
+            out.push_str(&format!(
+                "      <system-out>{}</system-out>\n",
+                xml_escape(&body.join("\n"))
+            ));
+        }
+        out.push_str("    </testcase>\n");
+    }
+    if !warnings.is_empty() {
+        let lines: Vec<String> = warnings
+            .iter()
+            .map(|w| format!("warning {}: {}", w.code, w.message))
+            .collect();
+        out.push_str(&format!(
+            "    <system-err>{}</system-err>\n",
+            xml_escape(&lines.join("\n"))
+        ));
+    }
+    out.push_str("  </testsuite>\n</testsuites>\n");
+    let _ = stdout.write_all(out.as_bytes());
+}
+
+/// Render results as Checkstyle XML, listing every checked file. Diagnostics
+/// without a location are reported at line 1.
+pub fn render_checkstyle(results: &[FileResult], stdout: &mut impl Write) {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<checkstyle version=\"4.3\">\n");
+    for result in results.iter().filter(|r| !r.skipped) {
+        let name = xml_escape(&result.path);
+        if result.errors.is_empty() {
+            out.push_str(&format!("  <file name=\"{name}\"/>\n"));
+            continue;
+        }
+        out.push_str(&format!("  <file name=\"{name}\">\n"));
+        for diag in &result.errors {
+            let position = match &diag.location {
+                Some(loc) => format!(r#"line="{}" column="{}""#, loc.line, loc.column),
+                None => r#"line="1""#.to_string(),
+            };
+            out.push_str(&format!(
+                "    <error {position} severity=\"{}\" message=\"{}\" source=\"jvl.{}\"/>\n",
+                severity_name(diag.severity),
+                xml_escape(&diag.message),
+                xml_escape(&diag.code),
+            ));
+        }
+        out.push_str("  </file>\n");
+    }
+    out.push_str("</checkstyle>\n");
+    let _ = stdout.write_all(out.as_bytes());
+}
+
+/// Escape the message of a GitHub workflow command.
+fn github_escape_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a property value of a GitHub workflow command.
+fn github_escape_property(text: &str) -> String {
+    github_escape_data(text)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+/// Render results as GitHub Actions workflow commands, which the runner
+/// shows as annotations on the changed lines. Paths under the current
+/// directory are made relative to it, as GitHub expects repository paths.
+pub fn render_github(results: &[FileResult], warnings: &[Warning], stdout: &mut impl Write) {
+    let cwd = std::env::current_dir().ok();
+    for warning in warnings {
+        let _ = writeln!(
+            stdout,
+            "::warning title={}::{}",
+            github_escape_property(&warning.code),
+            github_escape_data(&warning.message)
+        );
+    }
+    for result in results.iter().filter(|r| !r.skipped) {
+        let path = cwd
+            .as_deref()
+            .and_then(|cwd| std::path::Path::new(&result.path).strip_prefix(cwd).ok())
+            .map_or_else(|| result.path.clone(), |p| p.display().to_string());
+        for diag in &result.errors {
+            let mut properties = format!("file={}", github_escape_property(&path));
+            if let Some(loc) = &diag.location {
+                properties.push_str(&format!(",line={},col={}", loc.line, loc.column));
+            }
+            properties.push_str(&format!(",title={}", github_escape_property(&diag.code)));
+            let mut message = diag.message.clone();
+            if let Some(help) = &diag.help {
+                message.push_str(&format!("\n{help}"));
+            }
+            let _ = writeln!(
+                stdout,
+                "::{} {properties}::{}",
+                severity_name(diag.severity),
+                github_escape_data(&message)
+            );
+        }
     }
 }
@@ -1,6 +1,7 @@
 use jsonc_parser::ParseOptions;
 use jsonc_parser::ast::Value as AstValue;
 use jsonc_parser::common::Ranged;
+use jsonc_parser::errors::ParseErrorKind;
 use jsonc_parser::parse_to_ast;
 use jsonschema::paths::LocationSegment;
 use std::ops::Range;
@@ -45,17 +46,65 @@ impl<'a> ParsedFile<'a> {
     }
 }
 
+/// The syntax a document is parsed with.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    schemars::JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Dialect {
+    /// Strict JSON: no comments or trailing commas.
+    Json,
+    /// JSON with comments and trailing commas.
+    #[default]
+    Jsonc,
+    /// JSONC plus unquoted keys, single-quoted strings, hexadecimal numbers,
+    /// and leading `+` signs.
+    Json5,
+}
+
+impl Dialect {
+    /// The parser flags for this dialect.
+    pub fn parse_options(self) -> ParseOptions {
+        let jsonc = self != Self::Json;
+        let json5 = self == Self::Json5;
+        ParseOptions {
+            allow_comments: jsonc,
+            allow_trailing_commas: jsonc,
+            allow_loose_object_property_names: json5,
+            allow_single_quoted_strings: json5,
+            allow_hexadecimal_numbers: json5,
+            allow_missing_commas: false,
+            allow_unary_plus_numbers: json5,
+        }
+    }
+}
+
+impl std::fmt::Display for Dialect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Json => "JSON",
+            Self::Jsonc => "JSONC",
+            Self::Json5 => "JSON5",
+        })
+    }
+}
+
 /// Standard parse options: comments + trailing commas allowed.
 pub fn parse_options() -> ParseOptions {
-    ParseOptions {
-        allow_comments: true,
-        allow_trailing_commas: true,
-        allow_loose_object_property_names: false,
-        allow_single_quoted_strings: false,
-        allow_hexadecimal_numbers: false,
-        allow_missing_commas: false,
-        allow_unary_plus_numbers: false,
-    }
+    Dialect::Jsonc.parse_options()
+}
+
+/// Strict JSON parse options: no comments or trailing commas.
+pub fn strict_parse_options() -> ParseOptions {
+    Dialect::Json.parse_options()
 }
 
 /// Strip UTF-8 BOM if present.
@@ -67,8 +116,16 @@ pub fn strip_bom(source: &str) -> &str {
 ///
 /// Returns `Ok(ParsedFile)` on success, or `Err` with parse error diagnostics.
 pub fn parse_jsonc(source: &str) -> Result<ParsedFile<'_>, Vec<ParseError>> {
+    parse_with_options(source, &parse_options())
+}
+
+/// Parse a source string into a `ParsedFile` with the given syntax options.
+pub fn parse_with_options<'a>(
+    source: &'a str,
+    options: &ParseOptions,
+) -> Result<ParsedFile<'a>, Vec<ParseError>> {
     let source = strip_bom(source);
-    let result = parse_to_ast(source, &Default::default(), &parse_options());
+    let result = parse_to_ast(source, &Default::default(), options);
     match result {
         Ok(result) => match result.value {
             Some(ast) => {
@@ -82,6 +139,7 @@ pub fn parse_jsonc(source: &str) -> Result<ParsedFile<'_>, Vec<ParseError>> {
                 })
             }
             None => Err(vec![ParseError {
+                code: "syntax",
                 message: "File contains no JSON value".into(),
                 range: None,
             }]),
@@ -89,6 +147,7 @@ pub fn parse_jsonc(source: &str) -> Result<ParsedFile<'_>, Vec<ParseError>> {
         Err(err) => {
             let range = err.range();
             Err(vec![ParseError {
+                code: error_code(err.kind()),
                 message: err.to_string(),
                 range: Some(range.start..range.end),
             }])
@@ -96,9 +155,26 @@ pub fn parse_jsonc(source: &str) -> Result<ParsedFile<'_>, Vec<ParseError>> {
     }
 }
 
+/// The [`ParseError::code`] for an error from the parser.
+fn error_code(kind: &ParseErrorKind) -> &'static str {
+    match kind {
+        ParseErrorKind::CommentsNotAllowed => "comment-not-allowed",
+        ParseErrorKind::TrailingCommasNotAllowed => "trailing-comma-not-allowed",
+        ParseErrorKind::SingleQuotedStringsNotAllowed => "single-quote-not-allowed",
+        ParseErrorKind::HexadecimalNumbersNotAllowed => "hex-number-not-allowed",
+        ParseErrorKind::UnaryPlusNumbersNotAllowed => "unary-plus-not-allowed",
+        // Only raised for unquoted keys the options don't allow.
+        ParseErrorKind::ExpectedStringObjectProperty => "unquoted-key-not-allowed",
+        _ => "syntax",
+    }
+}
+
 /// A parse error with an optional source range.
 #[derive(Debug, Clone)]
 pub struct ParseError {
+    /// `syntax`, or the feature the dialect doesn't allow, e.g.
+    /// `comment-not-allowed`. Reported as `parse(<code>)`.
+    pub code: &'static str,
     pub message: String,
     pub range: Option<Range<usize>>,
 }
@@ -201,6 +277,110 @@ pub fn offset_to_line_col(line_starts: &[usize], offset: usize) -> (usize, usize
     (line + 1, col + 1)
 }
 
+/// Lexical state at a byte offset, as tracked by [`is_trivia_edit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LexState {
+    Code,
+    /// Inside a string delimited by the given quote byte.
+    Str(u8),
+    LineComment,
+    BlockComment,
+}
+
+/// Scan `bytes[..end]` and return the lexical state at `end`.
+///
+/// Two-byte tokens (`//`, `/*`, `*/`) are only recognized when both bytes lie
+/// before `end`; callers check the bytes around `end` themselves.
+fn lex_state_at(bytes: &[u8], end: usize) -> LexState {
+    let mut state = LexState::Code;
+    let mut i = 0;
+    while i < end {
+        let b = bytes[i];
+        let next = (i + 1 < end).then(|| bytes[i + 1]);
+        match state {
+            LexState::Code => match (b, next) {
+                (b'"' | b'\'', _) => state = LexState::Str(b),
+                (b'/', Some(b'/')) => {
+                    state = LexState::LineComment;
+                    i += 1;
+                }
+                (b'/', Some(b'*')) => {
+                    state = LexState::BlockComment;
+                    i += 1;
+                }
+                _ => {}
+            },
+            LexState::Str(quote) => {
+                if b == b'\\' {
+                    i += 1;
+                } else if b == quote {
+                    state = LexState::Code;
+                }
+            }
+            LexState::LineComment => {
+                if b == b'\n' {
+                    state = LexState::Code;
+                }
+            }
+            LexState::BlockComment => {
+                if b == b'*' && next == Some(b'/') {
+                    state = LexState::Code;
+                    i += 1;
+                }
+            }
+        }
+        i += 1;
+    }
+    state
+}
+
+fn is_json_whitespace(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+/// A byte that is part of a literal, number, string, or comment delimiter —
+/// i.e. one that would merge with an adjacent token byte if the whitespace
+/// between them were removed.
+fn is_token_byte(b: u8) -> bool {
+    !is_json_whitespace(b) && !matches!(b, b'{' | b'}' | b'[' | b']' | b',' | b':')
+}
+
+/// Returns true if replacing `range` in `source` with `inserted` only changes
+/// whitespace between tokens or the text of a comment, so the parsed value is
+/// unchanged.
+///
+/// The check is conservative: edits inside strings, edits that could open or
+/// close a comment, and edits that join or split adjacent tokens all return
+/// false.
+pub fn is_trivia_edit(source: &str, range: Range<usize>, inserted: &str) -> bool {
+    let bytes = source.as_bytes();
+    let Some(removed) = source.get(range.clone()) else {
+        return false;
+    };
+    let prev = range.start.checked_sub(1).map(|i| bytes[i]);
+    let next = bytes.get(range.end).copied();
+
+    match lex_state_at(bytes, range.start) {
+        LexState::Code => {
+            let only_whitespace = |s: &str| s.bytes().all(is_json_whitespace);
+            only_whitespace(removed)
+                && only_whitespace(inserted)
+                && !(prev.is_some_and(is_token_byte) && next.is_some_and(is_token_byte))
+        }
+        LexState::Str(_) => false,
+        LexState::LineComment => {
+            let single_line = |s: &str| !s.bytes().any(|b| b == b'\n' || b == b'\r');
+            single_line(removed) && single_line(inserted)
+        }
+        LexState::BlockComment => {
+            let no_delimiters = |s: &str| !s.bytes().any(|b| b == b'*' || b == b'/');
+            no_delimiters(removed)
+                && no_delimiters(inserted)
+                && !(prev == Some(b'*') && next == Some(b'/'))
+        }
+    }
+}
+
 /// Find the JSON pointer path for the node at a given byte offset.
 ///
 /// Walks the AST to find which key or value contains `offset`. Returns the JSON
@@ -896,4 +1076,51 @@ mod tests {
             })
         );
     }
+
+    /// Apply `is_trivia_edit` to the first occurrence of `needle` in `source`.
+    fn trivia(source: &str, needle: &str, inserted: &str) -> bool {
+        let start = source.find(needle).unwrap();
+        is_trivia_edit(source, start..start + needle.len(), inserted)
+    }
+
+    #[test]
+    fn trivia_edit_whitespace_between_tokens() {
+        let source = "{\"a\": 1,  \"b\": true}";
+        assert!(trivia(source, "  ", "\n    "));
+        assert!(trivia(source, " ", ""));
+        assert!(is_trivia_edit(source, 0..0, "\n"));
+        // Whitespace that separates nothing but a key from its colon is
+        // structural padding; removing it doesn't join tokens.
+        assert!(is_trivia_edit("{\"a\" : 1}", 4..5, ""));
+    }
+
+    #[test]
+    fn trivia_edit_rejects_token_changes() {
+        let source = "{\"a\": 12, \"b\": true}";
+        assert!(!trivia(source, "12", "13"));
+        assert!(!trivia(source, "true", "false"));
+        // Splitting a literal or number.
+        assert!(!is_trivia_edit(source, 7..7, " "));
+        // Inside a string, even whitespace is significant.
+        assert!(!is_trivia_edit(source, 2..2, " "));
+        // Joining two tokens by removing the space between them.
+        assert!(!is_trivia_edit("[1 2]", 2..3, ""));
+    }
+
+    #[test]
+    fn trivia_edit_comments() {
+        let source = "{\n  // line comment\n  /* block */ \"a\": 1\n}";
+        assert!(trivia(source, "line", "edited"));
+        assert!(trivia(source, "block", "changed text"));
+        assert!(!trivia(source, "line", "x\n\"b\": 2"));
+        assert!(!trivia(source, "block", "x */"));
+        // Joining `*` and `/` would close the comment early.
+        assert!(!is_trivia_edit("/* a * b/ */ 1", 6..8, ""));
+        // Splitting the `*/` terminator.
+        assert!(!is_trivia_edit("/* a */ 1", 6..6, " "));
+        // Inserting whitespace between the slashes of `//`.
+        assert!(!is_trivia_edit("1 // x", 3..3, " "));
+        // A comment opener inside a string is just string content.
+        assert!(!trivia("{\"a\": \"// x\"}", "x", "y"));
+    }
 }
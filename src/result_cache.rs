@@ -0,0 +1,119 @@
+//! On-disk cache of files that passed validation, so unchanged files can be
+//! skipped on the next run.
+//!
+//! Stored under `.jvl/cache` in the project root. An entry records the key a
+//! file passed with; the key hashes everything the outcome depends on (the
+//! file's content, the schema and every document it references, the settings
+//! applied to the file, and the jvl version), so any change makes the entry
+//! miss rather than go stale.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use sha2::{Digest, Sha256};
+
+/// The cache directory, relative to the project root.
+pub const DIR: &str = ".jvl/cache";
+
+const RESULTS_FILE: &str = "results";
+
+/// Results of earlier runs, and the outcomes of this one.
+pub struct ResultCache {
+    dir: PathBuf,
+    entries: HashMap<String, String>,
+    /// Entries to change on [`save`](Self::save): a new key, or `None` to
+    /// forget the file.
+    updates: Mutex<HashMap<String, Option<String>>>,
+}
+
+impl ResultCache {
+    /// Load the cache under `root`. A missing or unreadable cache is empty.
+    pub fn load(root: &Path) -> Self {
+        let dir = root.join(DIR);
+        let entries = fs::read_to_string(dir.join(RESULTS_FILE))
+            .map(|content| {
+                content
+                    .lines()
+                    .filter_map(|line| line.split_once('\t'))
+                    .map(|(key, path)| (path.to_string(), key.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            dir,
+            entries,
+            updates: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether the file at `path` passed validation with this `key` before.
+    pub fn is_valid(&self, path: &str, key: &str) -> bool {
+        self.entries.get(path).is_some_and(|k| k == key)
+    }
+
+    /// Record that the file at `path` passed with `key`, or, given `None`,
+    /// that it did not.
+    pub fn record(&self, path: &str, key: Option<String>) {
+        let mut updates = self.updates.lock().unwrap_or_else(|e| e.into_inner());
+        updates.insert(path.to_string(), key);
+    }
+
+    /// Write the cache back to disk, if anything changed.
+    pub fn save(self) -> std::io::Result<()> {
+        let mut entries = self.entries;
+        let mut changed = false;
+        for (path, key) in self.updates.into_inner().unwrap_or_else(|e| e.into_inner()) {
+            changed |= match key {
+                Some(key) => entries.insert(path, key.clone()).as_ref() != Some(&key),
+                None => entries.remove(&path).is_some(),
+            };
+        }
+        if !changed {
+            return Ok(());
+        }
+
+        fs::create_dir_all(&self.dir)?;
+        if fs::symlink_metadata(&self.dir)?.file_type().is_symlink() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "result cache directory is a symlink; refusing to write",
+            ));
+        }
+        let gitignore = self.dir.join(".gitignore");
+        if !gitignore.exists() {
+            fs::write(gitignore, "*\n")?;
+        }
+
+        let mut lines: Vec<String> = entries
+            .into_iter()
+            .map(|(path, key)| format!("{key}\t{path}\n"))
+            .collect();
+        lines.sort_unstable();
+        // Write to a temporary file first so a concurrent run never reads a
+        // partial cache.
+        let tmp = self
+            .dir
+            .join(format!("{RESULTS_FILE}.{}.tmp", std::process::id()));
+        fs::write(&tmp, lines.concat())?;
+        fs::rename(tmp, self.dir.join(RESULTS_FILE))
+    }
+}
+
+/// The key a file's result is cached under: a SHA-256 hex digest of its
+/// content, the schema's [fingerprint](crate::schema::SchemaCache::fingerprint),
+/// a description of the settings that apply to it, and the jvl version.
+pub fn key(content: &str, schema_fingerprint: &str, settings: &str) -> String {
+    let mut hasher = Sha256::new();
+    for part in [
+        env!("CARGO_PKG_VERSION"),
+        content,
+        schema_fingerprint,
+        settings,
+    ] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    format!("{:x}", hasher.finalize())
+}
@@ -1,5 +1,5 @@
 use sha2::{Digest, Sha256};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -8,11 +8,30 @@ use std::time::Duration;
 use thiserror::Error;
 
 use crate::diagnostic::Warning;
+use crate::formats::{self, CustomFormat};
+use crate::git::Tree;
+use crate::keywords::ExtensionKeyword;
 use crate::parse;
 
+/// Documents a [`CachingRetriever`] fetched while compiling a schema.
+#[derive(Default)]
+struct RetrievedDocuments {
+    /// Keyed by URI.
+    documents: BTreeMap<String, serde_json::Value>,
+    /// Files documents were read from, through a rewrite to a local
+    /// directory.
+    local_files: Vec<PathBuf>,
+}
+
+type Retrieved = Arc<Mutex<RetrievedDocuments>>;
+
 /// Custom retriever that routes `$ref` fetches through jvl's disk cache.
 struct CachingRetriever {
     no_cache: bool,
+    offline: bool,
+    url_rewrites: Vec<UrlRewrite>,
+    retrieved: Option<Retrieved>,
+    tree: Option<Arc<Tree>>,
 }
 
 impl jsonschema::Retrieve for CachingRetriever {
@@ -21,12 +40,165 @@ impl jsonschema::Retrieve for CachingRetriever {
         uri: &jsonschema::Uri<String>,
     ) -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>> {
         let url = uri.as_str();
-        let (content, _warnings, _outcome) = load_url_schema(url, self.no_cache)?;
+        let (content, _warnings, _outcome) = load_url_schema(
+            url,
+            &self.url_rewrites,
+            self.no_cache,
+            self.offline,
+            self.tree.as_deref(),
+        )?;
         let value: serde_json::Value = serde_json::from_str(&content)?;
+        if let Some(retrieved) = &self.retrieved {
+            let mut retrieved = retrieved.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(Ok(RewrittenUrl::File(path))) = rewrite_url(url, &self.url_rewrites) {
+                retrieved.local_files.push(path);
+            }
+            retrieved.documents.insert(url.to_string(), value.clone());
+        }
         Ok(value)
     }
 }
 
+/// Settings that affect how a schema is loaded and compiled.
+///
+/// Part of the in-memory cache key: the same source compiled with different
+/// options produces a separate validator.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct CompileOptions {
+    /// URL prefix rewrites applied before fetching remote schemas.
+    pub url_rewrites: Vec<UrlRewrite>,
+    /// Draft used for schemas without a `$schema` keyword. `None` means the
+    /// library default (2020-12).
+    pub default_draft: Option<SchemaDraft>,
+    /// Whether `format` is asserted. `None` follows the draft's default.
+    pub validate_formats: Option<bool>,
+    /// When false, unknown `format` values fail schema compilation.
+    pub ignore_unknown_formats: bool,
+    /// Custom `format` validators: format name → regex the value must match.
+    pub custom_formats: BTreeMap<String, CustomFormat>,
+    /// Extension keywords registered with the compiler.
+    pub keywords: Vec<ExtensionKeyword>,
+    /// Whether JSON documents embedded in strings are checked against the
+    /// content vocabulary (`contentMediaType`, `contentEncoding`,
+    /// `contentSchema`) after validation.
+    pub validate_content: bool,
+    /// Never fetch over the network: URL schemas must come from the disk
+    /// cache (stale entries are used as-is) or a local URL rewrite.
+    pub offline: bool,
+}
+
+impl Default for CompileOptions {
+    fn default() -> Self {
+        Self {
+            url_rewrites: vec![],
+            default_draft: None,
+            validate_formats: None,
+            ignore_unknown_formats: true,
+            custom_formats: BTreeMap::new(),
+            keywords: vec![],
+            validate_content: false,
+            offline: false,
+        }
+    }
+}
+
+/// A JSON Schema draft that can be selected as the default.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Hash,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    schemars::JsonSchema,
+    clap::ValueEnum,
+)]
+pub enum SchemaDraft {
+    #[serde(rename = "draft-04")]
+    #[value(name = "draft-04")]
+    Draft4,
+    #[serde(rename = "draft-06")]
+    #[value(name = "draft-06")]
+    Draft6,
+    #[serde(rename = "draft-07")]
+    #[value(name = "draft-07")]
+    Draft7,
+    #[serde(rename = "2019-09")]
+    #[value(name = "2019-09")]
+    Draft201909,
+    #[serde(rename = "2020-12")]
+    #[value(name = "2020-12")]
+    Draft202012,
+}
+
+impl From<SchemaDraft> for jsonschema::Draft {
+    fn from(draft: SchemaDraft) -> Self {
+        match draft {
+            SchemaDraft::Draft4 => jsonschema::Draft::Draft4,
+            SchemaDraft::Draft6 => jsonschema::Draft::Draft6,
+            SchemaDraft::Draft7 => jsonschema::Draft::Draft7,
+            SchemaDraft::Draft201909 => jsonschema::Draft::Draft201909,
+            SchemaDraft::Draft202012 => jsonschema::Draft::Draft202012,
+        }
+    }
+}
+
+/// A URL prefix rewrite applied before a remote schema is fetched.
+///
+/// The original URL remains the disk-cache key and the reported schema
+/// location; only the fetch target changes.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct UrlRewrite {
+    /// URL prefix to match (e.g. `https://raw.githubusercontent.com/`).
+    pub prefix: String,
+    /// Where matching URLs are redirected.
+    pub target: RewriteTarget,
+}
+
+/// Destination of a [`UrlRewrite`].
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub enum RewriteTarget {
+    /// Replace the prefix with another URL prefix.
+    Url(String),
+    /// Replace the prefix with a local directory (absolute).
+    Directory(PathBuf),
+}
+
+/// The fetch location of a URL after applying rewrite rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RewrittenUrl {
+    Url(String),
+    File(PathBuf),
+}
+
+/// Apply the first matching rewrite rule to `url`.
+///
+/// Returns `None` if no rule matches. For directory targets, the query string
+/// and fragment are dropped and the remainder is joined onto the directory;
+/// paths that would escape the directory are rejected with a `FileRead` error.
+fn rewrite_url(url: &str, rewrites: &[UrlRewrite]) -> Option<Result<RewrittenUrl, SchemaError>> {
+    let (rule, rest) = rewrites
+        .iter()
+        .find_map(|r| url.strip_prefix(r.prefix.as_str()).map(|rest| (r, rest)))?;
+    Some(match &rule.target {
+        RewriteTarget::Url(base) => Ok(RewrittenUrl::Url(format!("{base}{rest}"))),
+        RewriteTarget::Directory(dir) => {
+            let rest = rest.split(['?', '#']).next().unwrap_or_default();
+            let path = normalize_lexical(&dir.join(rest.trim_start_matches('/')));
+            if path.starts_with(dir) {
+                Ok(RewrittenUrl::File(path))
+            } else {
+                Err(SchemaError::FileRead {
+                    path: path.display().to_string(),
+                    reason: format!("rewritten path for {url} escapes {}", dir.display()),
+                })
+            }
+        }
+    })
+}
+
 /// Describes how a URL schema was resolved from cache.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CacheOutcome {
@@ -227,28 +399,63 @@ const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
 /// `cache_outcome` is `None` for file-based schemas (no caching involved).
 fn load_schema_content(
     source: &SchemaSource,
+    options: &CompileOptions,
     no_cache: bool,
+    tree: Option<&Tree>,
 ) -> Result<(String, Vec<Warning>, Option<CacheOutcome>), SchemaError> {
     match source {
-        SchemaSource::File(path) => {
-            let content = fs::read_to_string(path).map_err(|e| SchemaError::FileRead {
-                path: path.display().to_string(),
-                reason: e.to_string(),
-            })?;
-            Ok((content, vec![], None))
+        SchemaSource::File(path) => Ok((read_schema_file(path, tree)?, vec![], None)),
+        SchemaSource::Url(url) => {
+            load_url_schema(url, &options.url_rewrites, no_cache, options.offline, tree)
         }
-        SchemaSource::Url(url) => load_url_schema(url, no_cache),
     }
 }
 
-fn load_url_schema(
+/// Read a local schema from `tree` if given, or else from disk.
+fn read_schema_file(path: &Path, tree: Option<&Tree>) -> Result<String, SchemaError> {
+    match tree {
+        Some(tree) => tree.read(path),
+        None => fs::read_to_string(path),
+    }
+    .map_err(|e| SchemaError::FileRead {
+        path: path.display().to_string(),
+        reason: e.to_string(),
+    })
+}
+
+/// Load a URL schema, applying rewrite rules and the disk cache.
+///
+/// The disk cache is always keyed by the original `url`, even when the
+/// content is fetched from a rewritten mirror URL. Schemas rewritten to a
+/// local directory bypass the disk cache entirely.
+///
+/// When `offline` is set, nothing is fetched: a cached copy is used
+/// regardless of its age, and a missing one is a fetch error.
+pub(crate) fn load_url_schema(
     url: &str,
+    url_rewrites: &[UrlRewrite],
     no_cache: bool,
+    offline: bool,
+    tree: Option<&Tree>,
 ) -> Result<(String, Vec<Warning>, Option<CacheOutcome>), SchemaError> {
+    let fetch_target = match rewrite_url(url, url_rewrites).transpose()? {
+        Some(RewrittenUrl::File(path)) => {
+            return Ok((read_schema_file(&path, tree)?, vec![], None));
+        }
+        Some(RewrittenUrl::Url(mirror)) => mirror,
+        None => url.to_string(),
+    };
     let hash = url_hash(url);
+    let offline_error = || SchemaError::FetchError {
+        url: url.to_string(),
+        reason: "not in the schema cache and offline mode is enabled".into(),
+    };
 
     if no_cache {
-        let content = fetch_url(url)?;
+        if offline {
+            return Err(offline_error());
+        }
+        let content = fetch_url(&fetch_target)?;
         return Ok((content, vec![], Some(CacheOutcome::Bypassed)));
     }
 
@@ -266,10 +473,13 @@ fn load_url_schema(
                 if is_within_ttl(&meta_path) {
                     return Ok((content, vec![], Some(CacheOutcome::Hit)));
                 }
+                if offline {
+                    return Ok((content, vec![], Some(CacheOutcome::Stale)));
+                }
 
                 // Stale: attempt re-fetch. Use fresh content if successful,
                 // fall back to stale content on failure.
-                match fetch_url(url) {
+                match fetch_url(&fetch_target) {
                     Ok(fresh) => {
                         let _ = write_cache(base, &hash, url, &fresh);
                         return Ok((fresh, vec![], Some(CacheOutcome::Stale)));
@@ -288,8 +498,12 @@ fn load_url_schema(
         }
     }
 
+    if offline {
+        return Err(offline_error());
+    }
+
     // No cache hit — fetch synchronously
-    let content = fetch_url(url)?;
+    let content = fetch_url(&fetch_target)?;
 
     // Write to cache
     if let Some(ref base) = cache_base {
@@ -377,6 +591,104 @@ fn write_cache(base: &Path, hash: &str, url: &str, content: &str) -> Result<(),
     Ok(())
 }
 
+/// SHA-256 hex digest of a schema's content and the documents it references.
+fn fingerprint(content: &str, documents: &BTreeMap<String, serde_json::Value>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    for (uri, document) in documents {
+        hasher.update([0]);
+        hasher.update(uri.as_bytes());
+        hasher.update([0]);
+        hasher.update(document.to_string().as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// A schema's resolved `$ref` graph, saved after compiling it so later runs
+/// register every document it references up front and never retrieve one.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct Bundle {
+    url: String,
+    created_at: String,
+    /// [`fingerprint`] of the schema and `documents`. A bundle whose content
+    /// no longer hashes to it is ignored.
+    fingerprint: String,
+    /// Every document the schema references, directly or through other
+    /// documents, keyed by URI.
+    documents: BTreeMap<String, serde_json::Value>,
+    /// Local files some of `documents` were read from, through a rewrite to
+    /// a local directory, with the SHA-256 of their content at the time.
+    local_files: BTreeMap<PathBuf, String>,
+}
+
+/// Directory for [`Bundle`]s, inside the schema cache so `jvl cache clear`
+/// removes them too.
+fn bundle_dir() -> Option<PathBuf> {
+    cache_dir().map(|d| d.join("bundles"))
+}
+
+/// Where a schema's bundle is stored: a hash of the jvl version, the
+/// schema's source and content, and the URL rewrites that decide where its
+/// `$ref`s come from.
+fn bundle_key(source: &SchemaSource, content: &str, options: &CompileOptions) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+    hasher.update([0]);
+    hasher.update(source.to_string().as_bytes());
+    hasher.update([0]);
+    hasher.update(content.as_bytes());
+    hasher.update([0]);
+    hasher.update(format!("{:?}", options.url_rewrites).as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// SHA-256 hex digest of a local file, read from `tree` if given.
+fn file_hash(path: &Path, tree: Option<&Tree>) -> Option<String> {
+    let content = read_schema_file(path, tree).ok()?;
+    Some(format!("{:x}", Sha256::digest(content.as_bytes())))
+}
+
+/// Read the bundle stored under `key` for a schema with `content`.
+///
+/// Returns `None` unless it is younger than the cache TTL (or of any age,
+/// when `offline`), its documents still hash to its fingerprint, and none of
+/// the local files they came from has changed since.
+fn read_bundle(key: &str, content: &str, offline: bool, tree: Option<&Tree>) -> Option<Bundle> {
+    let path = bundle_dir()?.join(format!("{key}.json"));
+    let bundle: Bundle = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    let created_at = bundle.created_at.parse::<jiff::Timestamp>().ok()?;
+    let fresh = created_at.duration_until(jiff::Timestamp::now()).as_secs() < CACHE_TTL_SECS;
+    let intact = fingerprint(content, &bundle.documents) == bundle.fingerprint;
+    let unchanged = bundle
+        .local_files
+        .iter()
+        .all(|(path, hash)| file_hash(path, tree).as_ref() == Some(hash));
+    ((fresh || offline) && intact && unchanged).then_some(bundle)
+}
+
+fn write_bundle(key: &str, bundle: &Bundle) -> Result<(), std::io::Error> {
+    let dir = bundle_dir().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "cannot determine cache directory",
+        )
+    })?;
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{key}.json"));
+    if let Ok(m) = fs::symlink_metadata(&path)
+        && m.file_type().is_symlink()
+    {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "cache file is a symlink; refusing to write: {}",
+                path.display()
+            ),
+        ));
+    }
+    fs::write(path, serde_json::to_string(bundle)?)
+}
+
 /// List all cached schemas from the disk cache.
 ///
 /// Returns entries sorted by URL. Entries with corrupt or unreadable `.meta`
@@ -486,9 +798,12 @@ pub fn clear_cache() -> Result<CacheClearResult, std::io::Error> {
 ///
 /// Uses per-slot `OnceLock` to ensure each schema is fetched and compiled
 /// exactly once, even under concurrent access from multiple rayon threads.
+/// Slots are keyed by source and [`CompileOptions`].
 #[derive(Default)]
 pub struct SchemaCache {
-    slots: Mutex<HashMap<SchemaSource, Arc<SchemaSlot>>>,
+    slots: Mutex<HashMap<(SchemaSource, CompileOptions), Arc<SchemaSlot>>>,
+    /// Where local schema files are read from, when not the filesystem.
+    tree: Option<Arc<Tree>>,
 }
 
 struct SchemaSlot {
@@ -502,6 +817,46 @@ struct SlotResult {
     schema_value: Option<Arc<serde_json::Value>>,
     warnings: Vec<Warning>,
     cache_outcome: Option<CacheOutcome>,
+    /// Hash of the schema and every document it references, once compiled.
+    fingerprint: Option<String>,
+    /// Local files the schema and the documents it references were read
+    /// from, once compiled.
+    local_files: Option<Vec<PathBuf>>,
+    /// The annotation keywords they use, once compiled.
+    keywords: Option<AnnotationKeywords>,
+    /// The documents it references, keyed by URI, once compiled.
+    documents: Option<Arc<BTreeMap<String, serde_json::Value>>>,
+}
+
+/// Annotation keywords a schema uses, so validation only collects
+/// annotations when they can have something to report.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AnnotationKeywords {
+    /// `deprecated` appears somewhere.
+    pub deprecated: bool,
+    /// `contentMediaType` appears somewhere.
+    pub content: bool,
+}
+
+impl AnnotationKeywords {
+    fn find<'a>(schemas: impl IntoIterator<Item = &'a serde_json::Value>) -> Self {
+        fn visit(value: &serde_json::Value, found: &mut AnnotationKeywords) {
+            match value {
+                serde_json::Value::Object(obj) => {
+                    found.deprecated |= obj.contains_key("deprecated");
+                    found.content |= obj.contains_key("contentMediaType");
+                    obj.values().for_each(|v| visit(v, found));
+                }
+                serde_json::Value::Array(items) => items.iter().for_each(|v| visit(v, found)),
+                _ => {}
+            }
+        }
+        let mut found = Self::default();
+        for schema in schemas {
+            visit(schema, &mut found);
+        }
+        found
+    }
 }
 
 impl SchemaCache {
@@ -509,42 +864,113 @@ impl SchemaCache {
         Self::default()
     }
 
+    /// A cache that reads local schema files from `tree` rather than from
+    /// disk.
+    pub fn for_tree(tree: Arc<Tree>) -> Self {
+        Self {
+            tree: Some(tree),
+            ..Self::default()
+        }
+    }
+
     /// Return all `SchemaSource::File` paths currently in the cache.
     pub fn cached_file_paths(&self) -> Vec<PathBuf> {
         self.slots
             .lock()
             .unwrap_or_else(|e| e.into_inner())
             .keys()
-            .filter_map(|source| match source {
+            .filter_map(|(source, _)| match source {
                 SchemaSource::File(p) => Some(p.clone()),
                 _ => None,
             })
+            .collect::<HashSet<_>>()
+            .into_iter()
             .collect()
     }
 
-    /// Evict a cached schema slot, forcing recompilation on the next
-    /// [`get_or_compile`](Self::get_or_compile) call for this source.
+    /// Evict all cached slots for a source (under any compile options),
+    /// forcing recompilation on the next [`get_or_compile`](Self::get_or_compile)
+    /// call for this source.
     ///
     /// Returns `true` if the source was present in the cache.
     pub fn evict(&self, source: &SchemaSource) -> bool {
-        self.slots
-            .lock()
-            .unwrap_or_else(|e| e.into_inner())
-            .remove(source)
-            .is_some()
+        let mut slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+        let before = slots.len();
+        slots.retain(|(s, _), _| s != source);
+        slots.len() != before
     }
 
     /// Retrieve the raw schema JSON for a previously compiled source.
     ///
     /// Returns `None` if the source has not been compiled yet or if compilation
     /// failed before the schema could be parsed.
-    pub fn get_schema_value(&self, source: &SchemaSource) -> Option<Arc<serde_json::Value>> {
+    pub fn get_schema_value(
+        &self,
+        source: &SchemaSource,
+        options: &CompileOptions,
+    ) -> Option<Arc<serde_json::Value>> {
         let slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
-        let slot = slots.get(source)?;
+        let slot = slots.get(&(source.clone(), options.clone()))?;
         let result = slot.compiled.get()?;
         result.schema_value.clone()
     }
 
+    /// A hash of a previously compiled schema's content and of every document
+    /// it references, which changes whenever any of them does.
+    ///
+    /// Returns `None` if the source has not been compiled yet or failed to
+    /// compile.
+    pub fn fingerprint(&self, source: &SchemaSource, options: &CompileOptions) -> Option<String> {
+        let slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+        let slot = slots.get(&(source.clone(), options.clone()))?;
+        slot.compiled.get()?.fingerprint.clone()
+    }
+
+    /// The local files a previously compiled schema was read from: its own
+    /// file, or the one a rewrite maps its URL to, and those of the
+    /// documents it references.
+    ///
+    /// Returns `None` if the source has not been compiled yet or failed to
+    /// compile.
+    pub fn local_files(
+        &self,
+        source: &SchemaSource,
+        options: &CompileOptions,
+    ) -> Option<Vec<PathBuf>> {
+        let slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+        let slot = slots.get(&(source.clone(), options.clone()))?;
+        slot.compiled.get()?.local_files.clone()
+    }
+
+    /// The annotation keywords a previously compiled schema and the documents
+    /// it references use.
+    ///
+    /// Returns `None` if the source has not been compiled yet or failed to
+    /// compile.
+    pub fn annotation_keywords(
+        &self,
+        source: &SchemaSource,
+        options: &CompileOptions,
+    ) -> Option<AnnotationKeywords> {
+        let slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+        let slot = slots.get(&(source.clone(), options.clone()))?;
+        slot.compiled.get()?.keywords
+    }
+
+    /// The documents a previously compiled schema references, keyed by URI.
+    ///
+    /// Returns `None` if the source has not been compiled yet or failed to
+    /// compile.
+    pub fn documents(
+        &self,
+        source: &SchemaSource,
+        options: &CompileOptions,
+    ) -> Option<Arc<BTreeMap<String, serde_json::Value>>> {
+        let slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+        let slot = slots.get(&(source.clone(), options.clone()))?;
+        slot.compiled.get()?.documents.clone()
+    }
+
     /// Get or compile the schema and return the raw schema JSON value.
     ///
     /// Combines [`get_or_compile`](Self::get_or_compile) and
@@ -552,10 +978,11 @@ impl SchemaCache {
     pub fn get_or_compile_with_value(
         &self,
         source: &SchemaSource,
+        options: &CompileOptions,
         no_cache: bool,
     ) -> Result<Option<Arc<serde_json::Value>>, SchemaError> {
-        let _ = self.get_or_compile(source, no_cache)?;
-        Ok(self.get_schema_value(source))
+        let _ = self.get_or_compile(source, options, no_cache)?;
+        Ok(self.get_schema_value(source, options))
     }
 
     /// Get or load+compile a schema validator.
@@ -568,12 +995,13 @@ impl SchemaCache {
     pub fn get_or_compile(
         &self,
         source: &SchemaSource,
+        options: &CompileOptions,
         no_cache: bool,
     ) -> Result<CompileResult, SchemaError> {
         let slot = {
             let mut slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
             slots
-                .entry(source.clone())
+                .entry((source.clone(), options.clone()))
                 .or_insert_with(|| {
                     Arc::new(SchemaSlot {
                         compiled: OnceLock::new(),
@@ -586,17 +1014,22 @@ impl SchemaCache {
         // OnceLock::get_or_init guarantees exactly one thread runs the closure.
         // Other threads calling concurrently will block until init completes.
         let result = slot.compiled.get_or_init(|| {
-            let (content, warnings, cache_outcome) = match load_schema_content(source, no_cache) {
-                Ok(r) => r,
-                Err(e) => {
-                    return SlotResult {
-                        validator: Err(e),
-                        schema_value: None,
-                        warnings: vec![],
-                        cache_outcome: None,
-                    };
-                }
-            };
+            let (content, warnings, cache_outcome) =
+                match load_schema_content(source, options, no_cache, self.tree.as_deref()) {
+                    Ok(r) => r,
+                    Err(e) => {
+                        return SlotResult {
+                            validator: Err(e),
+                            schema_value: None,
+                            warnings: vec![],
+                            cache_outcome: None,
+                            fingerprint: None,
+                            local_files: None,
+                            keywords: None,
+                            documents: None,
+                        };
+                    }
+                };
 
             let schema_value: serde_json::Value = match serde_json::from_str(&content) {
                 Ok(v) => v,
@@ -609,16 +1042,43 @@ impl SchemaCache {
                         schema_value: None,
                         warnings,
                         cache_outcome,
+                        fingerprint: None,
+                        local_files: None,
+                        keywords: None,
+                        documents: None,
                     };
                 }
             };
 
             let schema_value = Arc::new(schema_value);
 
-            let validator = match jsonschema::options()
-                .with_retriever(CachingRetriever { no_cache })
-                .build(&schema_value)
+            // A bundle from an earlier run holds every document the schema
+            // references, so none has to be retrieved again.
+            let bundle_key = (!no_cache).then(|| bundle_key(source, &content, options));
+            let bundle = bundle_key
+                .as_deref()
+                .and_then(|key| read_bundle(key, &content, options.offline, self.tree.as_deref()));
+            let retrieved = Retrieved::default();
+
+            let mut builder =
+                validation_options(options, no_cache, Some(&retrieved), self.tree.as_ref());
+            // An explicit draft overrides `$schema` detection, so only apply
+            // the default when the schema doesn't declare one.
+            if let Some(draft) = options.default_draft
+                && parse::extract_schema_field(&schema_value).is_none()
             {
+                builder = builder.with_draft(draft.into());
+            }
+            if let Some(bundle) = &bundle {
+                builder = builder.with_resources(bundle.documents.iter().map(|(uri, document)| {
+                    (
+                        uri.clone(),
+                        jsonschema::Resource::from_contents(document.clone()),
+                    )
+                }));
+            }
+
+            let validator = match builder.build(&schema_value) {
                 Ok(v) => v,
                 Err(e) => {
                     return SlotResult {
@@ -626,15 +1086,70 @@ impl SchemaCache {
                         schema_value: Some(Arc::clone(&schema_value)),
                         warnings,
                         cache_outcome,
+                        fingerprint: None,
+                        local_files: None,
+                        keywords: None,
+                        documents: None,
                     };
                 }
             };
 
+            let mut retrieved =
+                std::mem::take(&mut *retrieved.lock().unwrap_or_else(|e| e.into_inner()));
+            let retrieved_any = !retrieved.documents.is_empty();
+            let mut bundled_files = BTreeMap::new();
+            if let Some(bundle) = bundle {
+                bundled_files = bundle.local_files;
+                for (uri, document) in bundle.documents {
+                    retrieved.documents.entry(uri).or_insert(document);
+                }
+            }
+            let fingerprint = fingerprint(&content, &retrieved.documents);
+            if let Some(key) = bundle_key
+                && retrieved_any
+            {
+                let local_files = retrieved
+                    .local_files
+                    .iter()
+                    .filter_map(|path| Some((path.clone(), file_hash(path, self.tree.as_deref())?)))
+                    .chain(bundled_files.clone())
+                    .collect();
+                let _ = write_bundle(
+                    &key,
+                    &Bundle {
+                        url: source.to_string(),
+                        created_at: jiff::Timestamp::now().to_string(),
+                        fingerprint: fingerprint.clone(),
+                        documents: retrieved.documents.clone(),
+                        local_files,
+                    },
+                );
+            }
+            retrieved.local_files.extend(bundled_files.into_keys());
+            let keywords = AnnotationKeywords::find(
+                std::iter::once(schema_value.as_ref()).chain(retrieved.documents.values()),
+            );
+            let local_files = match source {
+                SchemaSource::File(path) => Some(path.clone()),
+                SchemaSource::Url(url) => match rewrite_url(url, &options.url_rewrites) {
+                    Some(Ok(RewrittenUrl::File(path))) => Some(path),
+                    _ => None,
+                },
+            }
+            .into_iter()
+            .chain(retrieved.local_files)
+            .collect();
+            let documents = retrieved.documents;
+
             SlotResult {
                 validator: Ok(Arc::new(validator)),
                 schema_value: Some(schema_value),
                 warnings,
                 cache_outcome,
+                fingerprint: Some(fingerprint),
+                local_files: Some(local_files),
+                keywords: Some(keywords),
+                documents: Some(Arc::new(documents)),
             }
         });
 
@@ -657,7 +1172,143 @@ impl SchemaCache {
     }
 }
 
+/// Validator options shared by every schema compiled with `options`.
+///
+/// Documents the retriever fetches are recorded in `retrieved`, if given.
+fn validation_options(
+    options: &CompileOptions,
+    no_cache: bool,
+    retrieved: Option<&Retrieved>,
+    tree: Option<&Arc<Tree>>,
+) -> jsonschema::ValidationOptions {
+    let mut builder = jsonschema::options()
+        .with_retriever(CachingRetriever {
+            no_cache,
+            offline: options.offline,
+            url_rewrites: options.url_rewrites.clone(),
+            retrieved: retrieved.cloned(),
+            tree: tree.cloned(),
+        })
+        .with_format("semver", formats::is_semver)
+        .with_format("duration", formats::is_duration)
+        .should_ignore_unknown_formats(options.ignore_unknown_formats);
+    for (name, format) in &options.custom_formats {
+        let format = format.clone();
+        builder = builder.with_format(name.clone(), move |value| format.is_match(value));
+    }
+    for keyword in &options.keywords {
+        builder = keyword.register(builder);
+    }
+    if let Some(validate) = options.validate_formats {
+        builder = builder.should_validate_formats(validate);
+    }
+    builder
+}
+
+/// The base URI jsonschema gives `root` when compiling it on its own: its
+/// `$id` (or draft 4 `id`), resolved against the default base.
+fn root_base_uri(root: &serde_json::Value) -> String {
+    const DEFAULT_BASE_URI: &str = "json-schema:///";
+    let id = ["$id", "id"]
+        .iter()
+        .find_map(|key| root.get(key)?.as_str())
+        .map(|id| id.split('#').next().unwrap_or_default())
+        .filter(|id| !id.is_empty());
+    match id {
+        Some(id) if id.contains(':') => id.to_string(),
+        Some(id) => format!("{DEFAULT_BASE_URI}{}", id.trim_start_matches('/')),
+        None => DEFAULT_BASE_URI.to_string(),
+    }
+}
+
+/// Percent-encode a JSON pointer for use as a URI fragment.
+fn pointer_fragment(pointer: &str) -> String {
+    let mut fragment = String::with_capacity(pointer.len());
+    for byte in pointer.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@/?".contains(&byte) {
+            fragment.push(byte as char);
+        } else {
+            fragment.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    fragment
+}
+
+/// Compile the subschema at `location` (a JSON pointer into `root`), with
+/// `$ref`s resolved against the whole document as when compiling `root`:
+/// `root` is registered under its own base URI and the subschema compiled
+/// as a reference into it.
+pub fn compile_subschema(
+    root: &serde_json::Value,
+    location: &str,
+    options: &CompileOptions,
+    no_cache: bool,
+) -> Result<jsonschema::Validator, SchemaError> {
+    let reference = format!("{}#{}", root_base_uri(root), pointer_fragment(location));
+    compile_reference(root, &BTreeMap::new(), &reference, options, no_cache)
+}
+
+/// Compile the subschema at `location` (a JSON pointer) in the document at
+/// `uri`, one of the `documents` that `root` references, with `$ref`s
+/// resolved as when compiling `root`.
+pub fn compile_document_subschema(
+    root: &serde_json::Value,
+    documents: &BTreeMap<String, serde_json::Value>,
+    uri: &str,
+    location: &str,
+    options: &CompileOptions,
+    no_cache: bool,
+) -> Result<jsonschema::Validator, SchemaError> {
+    let reference = format!("{uri}#{}", pointer_fragment(location));
+    compile_reference(root, documents, &reference, options, no_cache)
+}
+
+/// Compile a schema that is just a `$ref` to `reference`, with `root` and
+/// `documents` registered under their URIs.
+fn compile_reference(
+    root: &serde_json::Value,
+    documents: &BTreeMap<String, serde_json::Value>,
+    reference: &str,
+    options: &CompileOptions,
+    no_cache: bool,
+) -> Result<jsonschema::Validator, SchemaError> {
+    // Keeps the wrapper schema from taking the default base URI, which the
+    // root may be registered under.
+    const WRAPPER_URI: &str = "urn:jvl:subschema";
+    let mut builder = validation_options(options, no_cache, None, None);
+    if let Some(draft) = options.default_draft
+        && parse::extract_schema_field(root).is_none()
+    {
+        builder = builder.with_draft(draft.into());
+    }
+    let resources = documents
+        .iter()
+        .map(|(uri, document)| (uri.clone(), document))
+        .chain([(root_base_uri(root), root)])
+        .map(|(uri, document)| (uri, jsonschema::Resource::from_contents(document.clone())));
+    builder
+        .with_resources(resources)
+        .with_base_uri(WRAPPER_URI)
+        .build(&serde_json::json!({ "$ref": reference }))
+        .map_err(|e| SchemaError::CompileError(e.to_string()))
+}
+
+/// Whether `instance` is valid against the subschema at `location` (a JSON
+/// pointer into `root`), with `$ref`s resolved against the whole document.
+/// Returns `None` if the subschema can't be compiled.
+pub fn subschema_is_valid(
+    root: &serde_json::Value,
+    location: &str,
+    instance: &serde_json::Value,
+    options: &CompileOptions,
+    no_cache: bool,
+) -> Option<bool> {
+    let validator = compile_subschema(root, location, options, no_cache).ok()?;
+    Some(validator.is_valid(instance))
+}
+
 /// Schema annotations (title/description) for a JSON path.
+#[derive(Debug, Clone)]
 pub struct SchemaAnnotation {
     pub title: Option<String>,
     pub description: Option<String>,
@@ -749,15 +1400,17 @@ pub struct PropertyInfo {
     pub required: bool,
     pub description: Option<String>,
     pub schema_type: Option<String>,
+    /// Whether the property's schema is annotated `deprecated: true`.
+    pub deprecated: bool,
 }
 
 /// Possible value suggestions for a property.
 #[derive(Debug, Clone, PartialEq)]
 pub enum ValueSuggestion {
-    /// A value from a schema `enum` array.
-    Enum(serde_json::Value),
-    /// A value from a schema `const`.
-    Const(serde_json::Value),
+    /// A value from a schema `enum` array, and whether it is deprecated.
+    Enum(serde_json::Value, bool),
+    /// A value from a schema `const`, and whether it is deprecated.
+    Const(serde_json::Value, bool),
     /// Suggests `true` and `false`.
     Boolean,
     /// Suggests `null`.
@@ -772,8 +1425,8 @@ impl ValueSuggestion {
     /// using the raw `&str` directly.
     fn dedup_key(&self) -> String {
         match self {
-            Self::Const(v) | Self::Enum(v) => {
-                let prefix = if matches!(self, Self::Const(_)) {
+            Self::Const(v, _) | Self::Enum(v, _) => {
+                let prefix = if matches!(self, Self::Const(..)) {
                     "c:"
                 } else {
                     "e:"
@@ -854,6 +1507,7 @@ fn collect_properties_from(
                 required: required.contains(name.as_str()),
                 description,
                 schema_type,
+                deprecated: is_deprecated(resolved_prop),
             });
         }
     }
@@ -868,6 +1522,11 @@ fn collect_properties_from(
     }
 }
 
+/// Whether a schema node is annotated `deprecated: true`.
+fn is_deprecated(schema: &serde_json::Value) -> bool {
+    schema.get("deprecated") == Some(&serde_json::Value::Bool(true))
+}
+
 /// Format title and description from a schema node into a single string.
 fn format_annotation(schema: &serde_json::Value) -> Option<String> {
     let title = schema.get("title").and_then(|v| v.as_str());
@@ -941,7 +1600,7 @@ fn collect_array_items_from(
     };
 
     if let Some(items) = schema.get("items") {
-        collect_values_from(root, items, suggestions, seen, depth + 1);
+        collect_values_from(root, items, suggestions, seen, depth + 1, false);
     }
 
     for keyword in COMPOSITION_KEYWORDS {
@@ -978,7 +1637,7 @@ fn collect_values_at_property(
         .get("properties")
         .and_then(|p| p.get(property_name))
     {
-        collect_values_from(root, prop_schema, suggestions, seen, depth + 1);
+        collect_values_from(root, prop_schema, suggestions, seen, depth + 1, false);
     }
 
     // Walk composition branches at the parent level.
@@ -1006,6 +1665,7 @@ fn collect_values_from(
     suggestions: &mut Vec<ValueSuggestion>,
     seen: &mut HashSet<String>,
     depth: usize,
+    deprecated: bool,
 ) {
     if depth > MAX_SCHEMA_DEPTH {
         return;
@@ -1016,10 +1676,12 @@ fn collect_values_from(
     let Some(schema) = follow_ref(root, schema, &mut visited) else {
         return;
     };
+    // A deprecated node deprecates every value suggested beneath it.
+    let deprecated = deprecated || is_deprecated(schema);
 
     // Check for const (local early return — only this branch).
     if let Some(const_val) = schema.get("const") {
-        let suggestion = ValueSuggestion::Const(const_val.clone());
+        let suggestion = ValueSuggestion::Const(const_val.clone(), deprecated);
         if seen.insert(suggestion.dedup_key()) {
             suggestions.push(suggestion);
         }
@@ -1029,7 +1691,7 @@ fn collect_values_from(
     // Check for enum (local early return — only this branch).
     if let Some(enum_vals) = schema.get("enum").and_then(|v| v.as_array()) {
         for val in enum_vals {
-            let suggestion = ValueSuggestion::Enum(val.clone());
+            let suggestion = ValueSuggestion::Enum(val.clone(), deprecated);
             if seen.insert(suggestion.dedup_key()) {
                 suggestions.push(suggestion);
             }
@@ -1045,15 +1707,11 @@ fn collect_values_from(
     };
     for t in &types {
         match *t {
-            "boolean" => {
-                if seen.insert(ValueSuggestion::Boolean.dedup_key()) {
-                    suggestions.push(ValueSuggestion::Boolean);
-                }
+            "boolean" if seen.insert(ValueSuggestion::Boolean.dedup_key()) => {
+                suggestions.push(ValueSuggestion::Boolean);
             }
-            "null" => {
-                if seen.insert(ValueSuggestion::Null.dedup_key()) {
-                    suggestions.push(ValueSuggestion::Null);
-                }
+            "null" if seen.insert(ValueSuggestion::Null.dedup_key()) => {
+                suggestions.push(ValueSuggestion::Null);
             }
             _ => {}
         }
@@ -1063,7 +1721,7 @@ fn collect_values_from(
     for keyword in COMPOSITION_KEYWORDS {
         if let Some(branches) = schema.get(*keyword).and_then(|v| v.as_array()) {
             for branch in branches {
-                collect_values_from(root, branch, suggestions, seen, depth + 1);
+                collect_values_from(root, branch, suggestions, seen, depth + 1, deprecated);
             }
         }
     }
@@ -1186,7 +1844,9 @@ mod tests {
         let source = SchemaSource::File(schema_path.clone());
 
         // First compile should succeed and cache the validator.
-        let (validator_v1, _, _) = cache.get_or_compile(&source, true).unwrap();
+        let (validator_v1, _, _) = cache
+            .get_or_compile(&source, &CompileOptions::default(), true)
+            .unwrap();
 
         // Valid doc passes.
         let doc: serde_json::Value = serde_json::from_str(r#"{"name":"alice"}"#).unwrap();
@@ -1201,7 +1861,9 @@ mod tests {
         drop(f);
 
         // Without eviction, the cache still returns the old validator.
-        let (validator_stale, _, _) = cache.get_or_compile(&source, true).unwrap();
+        let (validator_stale, _, _) = cache
+            .get_or_compile(&source, &CompileOptions::default(), true)
+            .unwrap();
         assert!(
             validator_stale.is_valid(&doc),
             "stale validator should still accept old doc"
@@ -1209,7 +1871,9 @@ mod tests {
 
         // Evict and recompile — now the new schema should be used.
         assert!(cache.evict(&source));
-        let (validator_v2, _, _) = cache.get_or_compile(&source, true).unwrap();
+        let (validator_v2, _, _) = cache
+            .get_or_compile(&source, &CompileOptions::default(), true)
+            .unwrap();
 
         // Old doc is now invalid (missing "count").
         assert!(!validator_v2.is_valid(&doc));
@@ -1219,6 +1883,52 @@ mod tests {
         assert!(validator_v2.is_valid(&new_doc));
     }
 
+    #[test]
+    fn rewrite_url_to_mirror_url() {
+        let rewrites = vec![UrlRewrite {
+            prefix: "https://raw.githubusercontent.com/".into(),
+            target: RewriteTarget::Url("https://mirror.internal/gh/".into()),
+        }];
+        let result = rewrite_url("https://raw.githubusercontent.com/a/b.json", &rewrites);
+        assert_eq!(
+            result.unwrap().unwrap(),
+            RewrittenUrl::Url("https://mirror.internal/gh/a/b.json".into())
+        );
+        assert!(rewrite_url("https://example.com/a.json", &rewrites).is_none());
+    }
+
+    #[test]
+    fn rewrite_url_to_directory_strips_query() {
+        let rewrites = vec![UrlRewrite {
+            prefix: "https://example.com/".into(),
+            target: RewriteTarget::Directory(PathBuf::from("/mirror")),
+        }];
+        let result = rewrite_url("https://example.com/x/y.json?v=2#frag", &rewrites);
+        assert_eq!(
+            result.unwrap().unwrap(),
+            RewrittenUrl::File(PathBuf::from("/mirror/x/y.json"))
+        );
+    }
+
+    #[test]
+    fn rewrite_url_first_match_wins() {
+        let rewrites = vec![
+            UrlRewrite {
+                prefix: "https://example.com/".into(),
+                target: RewriteTarget::Url("https://first/".into()),
+            },
+            UrlRewrite {
+                prefix: "https://example.com/x/".into(),
+                target: RewriteTarget::Url("https://second/".into()),
+            },
+        ];
+        let result = rewrite_url("https://example.com/x/y.json", &rewrites);
+        assert_eq!(
+            result.unwrap().unwrap(),
+            RewrittenUrl::Url("https://first/x/y.json".into())
+        );
+    }
+
     #[test]
     fn evict_nonexistent_returns_false() {
         let cache = SchemaCache::new();
@@ -1461,6 +2171,26 @@ mod tests {
         assert!(!age_prop.required);
     }
 
+    #[test]
+    fn collect_properties_deprecated() {
+        let schema = serde_json::json!({
+            "$defs": { "old": { "type": "string", "deprecated": true } },
+            "properties": {
+                "name": { "type": "string" },
+                "legacy": { "$ref": "#/$defs/old" }
+            }
+        });
+        let props = collect_properties(&schema, &[]);
+        assert!(!props.iter().find(|p| p.name == "name").unwrap().deprecated);
+        assert!(
+            props
+                .iter()
+                .find(|p| p.name == "legacy")
+                .unwrap()
+                .deprecated
+        );
+    }
+
     #[test]
     fn collect_properties_allof_merge() {
         let schema = serde_json::json!({
@@ -1537,7 +2267,7 @@ mod tests {
         });
         let values = collect_values(&schema, &[], "mode");
         assert_eq!(values.len(), 3);
-        assert!(matches!(&values[0], ValueSuggestion::Enum(v) if v == "dark"));
+        assert!(matches!(&values[0], ValueSuggestion::Enum(v, _) if v == "dark"));
     }
 
     #[test]
@@ -1549,7 +2279,7 @@ mod tests {
         });
         let values = collect_values(&schema, &[], "version");
         assert_eq!(values.len(), 1);
-        assert!(matches!(&values[0], ValueSuggestion::Const(v) if v == &serde_json::json!(2)));
+        assert!(matches!(&values[0], ValueSuggestion::Const(v, _) if v == &serde_json::json!(2)));
     }
 
     #[test]
@@ -1575,6 +2305,25 @@ mod tests {
         assert!(values.is_empty());
     }
 
+    #[test]
+    fn collect_values_deprecated_branch() {
+        let schema = serde_json::json!({
+            "properties": {
+                "mode": {
+                    "oneOf": [
+                        { "const": "new" },
+                        { "deprecated": true, "enum": ["old", "older"] }
+                    ]
+                }
+            }
+        });
+        let values = collect_values(&schema, &[], "mode");
+        assert_eq!(values.len(), 3);
+        assert!(matches!(&values[0], ValueSuggestion::Const(v, false) if v == "new"));
+        assert!(matches!(&values[1], ValueSuggestion::Enum(v, true) if v == "old"));
+        assert!(matches!(&values[2], ValueSuggestion::Enum(v, true) if v == "older"));
+    }
+
     #[test]
     fn collect_values_oneof_enums() {
         let schema = serde_json::json!({
@@ -1589,10 +2338,10 @@ mod tests {
         });
         let values = collect_values(&schema, &[], "mode");
         assert_eq!(values.len(), 4);
-        assert!(matches!(&values[0], ValueSuggestion::Enum(v) if v == "a"));
-        assert!(matches!(&values[1], ValueSuggestion::Enum(v) if v == "b"));
-        assert!(matches!(&values[2], ValueSuggestion::Enum(v) if v == "c"));
-        assert!(matches!(&values[3], ValueSuggestion::Enum(v) if v == "d"));
+        assert!(matches!(&values[0], ValueSuggestion::Enum(v, _) if v == "a"));
+        assert!(matches!(&values[1], ValueSuggestion::Enum(v, _) if v == "b"));
+        assert!(matches!(&values[2], ValueSuggestion::Enum(v, _) if v == "c"));
+        assert!(matches!(&values[3], ValueSuggestion::Enum(v, _) if v == "d"));
     }
 
     #[test]
@@ -1609,7 +2358,7 @@ mod tests {
         });
         let values = collect_values(&schema, &[], "value");
         assert_eq!(values.len(), 2);
-        assert!(matches!(&values[0], ValueSuggestion::Enum(v) if v == "x"));
+        assert!(matches!(&values[0], ValueSuggestion::Enum(v, _) if v == "x"));
         assert!(matches!(&values[1], ValueSuggestion::Boolean));
     }
 
@@ -1627,9 +2376,9 @@ mod tests {
         });
         let values = collect_values(&schema, &[], "mode");
         assert_eq!(values.len(), 3, "expected deduped union, got: {values:?}");
-        assert!(matches!(&values[0], ValueSuggestion::Enum(v) if v == "a"));
-        assert!(matches!(&values[1], ValueSuggestion::Enum(v) if v == "b"));
-        assert!(matches!(&values[2], ValueSuggestion::Enum(v) if v == "c"));
+        assert!(matches!(&values[0], ValueSuggestion::Enum(v, _) if v == "a"));
+        assert!(matches!(&values[1], ValueSuggestion::Enum(v, _) if v == "b"));
+        assert!(matches!(&values[2], ValueSuggestion::Enum(v, _) if v == "c"));
     }
 
     #[test]
@@ -1670,8 +2419,8 @@ mod tests {
         });
         let values = collect_values(&schema, &[], "value");
         assert_eq!(values.len(), 2);
-        assert!(matches!(&values[0], ValueSuggestion::Enum(v) if v == "a"));
-        assert!(matches!(&values[1], ValueSuggestion::Const(v) if v == "b"));
+        assert!(matches!(&values[0], ValueSuggestion::Enum(v, _) if v == "a"));
+        assert!(matches!(&values[1], ValueSuggestion::Const(v, _) if v == "b"));
     }
 
     #[test]
@@ -1691,8 +2440,8 @@ mod tests {
         });
         let values = collect_values(&schema, &[], "mode");
         assert_eq!(values.len(), 3);
-        assert!(matches!(&values[0], ValueSuggestion::Enum(v) if v == "fast"));
-        assert!(matches!(&values[1], ValueSuggestion::Enum(v) if v == "slow"));
+        assert!(matches!(&values[0], ValueSuggestion::Enum(v, _) if v == "fast"));
+        assert!(matches!(&values[1], ValueSuggestion::Enum(v, _) if v == "slow"));
         assert!(matches!(&values[2], ValueSuggestion::Null));
     }
 
@@ -1715,9 +2464,9 @@ mod tests {
         });
         let values = collect_values(&schema, &[], "status");
         assert_eq!(values.len(), 3);
-        assert!(matches!(&values[0], ValueSuggestion::Enum(v) if v == "active"));
-        assert!(matches!(&values[1], ValueSuggestion::Enum(v) if v == "inactive"));
-        assert!(matches!(&values[2], ValueSuggestion::Enum(v) if v == "pending"));
+        assert!(matches!(&values[0], ValueSuggestion::Enum(v, _) if v == "active"));
+        assert!(matches!(&values[1], ValueSuggestion::Enum(v, _) if v == "inactive"));
+        assert!(matches!(&values[2], ValueSuggestion::Enum(v, _) if v == "pending"));
     }
 
     #[test]
@@ -1748,9 +2497,9 @@ mod tests {
         });
         let values = collect_array_item_values(&schema, &["tags".to_string()]);
         assert_eq!(values.len(), 3);
-        assert!(matches!(&values[0], ValueSuggestion::Enum(v) if v == "frontend"));
-        assert!(matches!(&values[1], ValueSuggestion::Enum(v) if v == "backend"));
-        assert!(matches!(&values[2], ValueSuggestion::Enum(v) if v == "devops"));
+        assert!(matches!(&values[0], ValueSuggestion::Enum(v, _) if v == "frontend"));
+        assert!(matches!(&values[1], ValueSuggestion::Enum(v, _) if v == "backend"));
+        assert!(matches!(&values[2], ValueSuggestion::Enum(v, _) if v == "devops"));
     }
 
     #[test]
@@ -1765,7 +2514,7 @@ mod tests {
         });
         let values = collect_array_item_values(&schema, &["values".to_string()]);
         assert_eq!(values.len(), 1);
-        assert!(matches!(&values[0], ValueSuggestion::Const(v) if v == &serde_json::json!(42)));
+        assert!(matches!(&values[0], ValueSuggestion::Const(v, _) if v == &serde_json::json!(42)));
     }
 
     #[test]
@@ -1827,8 +2576,8 @@ mod tests {
         });
         let values = collect_array_item_values(&schema, &["tags".to_string()]);
         assert_eq!(values.len(), 2);
-        assert!(matches!(&values[0], ValueSuggestion::Enum(v) if v == "a"));
-        assert!(matches!(&values[1], ValueSuggestion::Enum(v) if v == "b"));
+        assert!(matches!(&values[0], ValueSuggestion::Enum(v, _) if v == "a"));
+        assert!(matches!(&values[1], ValueSuggestion::Enum(v, _) if v == "b"));
     }
 
     #[test]
@@ -1847,8 +2596,8 @@ mod tests {
         // pointer ["matrix", "0"] resolves through properties.matrix -> items
         let values = collect_array_item_values(&schema, &["matrix".to_string(), "0".to_string()]);
         assert_eq!(values.len(), 2);
-        assert!(matches!(&values[0], ValueSuggestion::Enum(v) if v == "x"));
-        assert!(matches!(&values[1], ValueSuggestion::Enum(v) if v == "y"));
+        assert!(matches!(&values[0], ValueSuggestion::Enum(v, _) if v == "x"));
+        assert!(matches!(&values[1], ValueSuggestion::Enum(v, _) if v == "y"));
     }
 
     #[test]
@@ -1859,8 +2608,8 @@ mod tests {
         });
         let values = collect_array_item_values(&schema, &[]);
         assert_eq!(values.len(), 2);
-        assert!(matches!(&values[0], ValueSuggestion::Enum(v) if v == "a"));
-        assert!(matches!(&values[1], ValueSuggestion::Enum(v) if v == "b"));
+        assert!(matches!(&values[0], ValueSuggestion::Enum(v, _) if v == "a"));
+        assert!(matches!(&values[1], ValueSuggestion::Enum(v, _) if v == "b"));
     }
 
     #[test]
@@ -1878,8 +2627,8 @@ mod tests {
         });
         let values = collect_array_item_values(&schema, &["tags".to_string()]);
         assert_eq!(values.len(), 2);
-        assert!(matches!(&values[0], ValueSuggestion::Enum(v) if v == "alpha"));
-        assert!(matches!(&values[1], ValueSuggestion::Enum(v) if v == "beta"));
+        assert!(matches!(&values[0], ValueSuggestion::Enum(v, _) if v == "alpha"));
+        assert!(matches!(&values[1], ValueSuggestion::Enum(v, _) if v == "beta"));
     }
 
     #[test]
@@ -1926,6 +2675,33 @@ mod tests {
         assert!(props.iter().any(|p| p.name == "port"));
     }
 
+    #[test]
+    fn annotation_keywords_include_referenced_documents() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("schema.json"),
+            r#"{"$id": "https://schemas.invalid/schema.json",
+                "properties": {"a": {"$ref": "defs.json"}}}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("defs.json"), r#"{"deprecated": true}"#).unwrap();
+
+        let cache = SchemaCache::new();
+        let options = CompileOptions {
+            url_rewrites: vec![UrlRewrite {
+                prefix: "https://schemas.invalid/".into(),
+                target: RewriteTarget::Directory(dir.path().to_path_buf()),
+            }],
+            ..CompileOptions::default()
+        };
+        let source = SchemaSource::File(dir.path().join("schema.json"));
+        assert!(cache.annotation_keywords(&source, &options).is_none());
+        cache.get_or_compile(&source, &options, true).unwrap();
+        let keywords = cache.annotation_keywords(&source, &options).unwrap();
+        assert!(keywords.deprecated);
+        assert!(!keywords.content);
+    }
+
     #[test]
     fn hover_truncate_multibyte_boundary() {
         // Place a 4-byte emoji (U+1F600) right at the 10,000-byte boundary.
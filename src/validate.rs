@@ -1,12 +1,20 @@
 use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Range;
 use std::path::Path;
 use std::time::{Duration, Instant};
 
 use jsonschema::paths::LocationSegment;
 
-use crate::diagnostic::{FileDiagnostic, FileResult, Severity, SourceLocation, Warning};
+use crate::diagnostic::{
+    FileDiagnostic, FileResult, RelatedInfo, Severity, SourceLocation, Warning,
+};
+use crate::embed::{self, RegionKind};
+use crate::keywords::ExtensionKeyword;
+use crate::lint::{self, LintOptions};
 use crate::parse::{self, ParsedFile};
-use crate::schema::{CacheOutcome, SchemaCache, SchemaError, SchemaSource};
+use crate::schema::{self, CacheOutcome, CompileOptions, SchemaCache, SchemaError, SchemaSource};
+use crate::yaml;
 
 /// Timing breakdown for schema compilation and validation.
 #[derive(Debug, Clone, Copy)]
@@ -17,67 +25,310 @@ pub struct TimingBreakdown {
     pub validate: Duration,
 }
 
+/// How [`validate_file`] parses a file and treats it when no schema applies.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileOptions {
+    /// The syntax the file is parsed with. Documents embedded in Markdown
+    /// are always parsed as JSONC.
+    pub dialect: parse::Dialect,
+    /// Report a `no-schema` error instead of skipping the file.
+    pub strict: bool,
+    /// Report the file as checked, for its syntax, instead of skipping it.
+    pub check_syntax: bool,
+    /// Lint rules to run. Documents embedded in Markdown are only checked
+    /// for duplicate keys.
+    pub lint: LintOptions,
+}
+
+/// Everything [`validate_file`] reports for a file.
+type Validation = (
+    FileResult,
+    Vec<Warning>,
+    Option<CacheOutcome>,
+    Option<TimingBreakdown>,
+);
+
 /// Validate a single file against a resolved schema.
 ///
 /// Returns `(file_result, warnings, cache_outcome, timing)`. The `cache_outcome`
 /// is `None` for file-based schemas, skipped files, or when the compiled schema
 /// was already cached in memory by another thread. `timing` is `None` when the
 /// file is skipped or has parse errors.
+///
+/// For Markdown files, the embedded JSON regions are validated instead (see
+/// [`validate_embedded`]).
 pub fn validate_file(
     file_path: &str,
     source: &str,
     schema_source: Option<&SchemaSource>,
     schema_cache: &SchemaCache,
+    compile_options: &CompileOptions,
     no_cache: bool,
-    strict: bool,
-) -> (
-    FileResult,
-    Vec<Warning>,
-    Option<CacheOutcome>,
-    Option<TimingBreakdown>,
-) {
+    options: FileOptions,
+) -> Validation {
+    if embed::is_host_file(Path::new(file_path)) {
+        return validate_embedded(
+            file_path,
+            source,
+            schema_source,
+            schema_cache,
+            compile_options,
+            no_cache,
+            options,
+        );
+    }
+    validate_document(
+        file_path,
+        source,
+        schema_source,
+        schema_cache,
+        compile_options,
+        no_cache,
+        options,
+    )
+}
+
+/// Validate the JSON regions embedded in a Markdown file, reporting every
+/// diagnostic at its position in the host file.
+///
+/// Front matter uses `schema_source` (the `--schema` flag or a config mapping
+/// for the host file), falling back to its own `$schema`; YAML front matter is
+/// converted to JSON first. Code blocks use the
+/// `schema=` attribute of their info string or their own `$schema`; code
+/// blocks with neither are plain examples and aren't checked, even in strict
+/// mode. The file is skipped when no region is checked.
+fn validate_embedded(
+    file_path: &str,
+    source: &str,
+    schema_source: Option<&SchemaSource>,
+    schema_cache: &SchemaCache,
+    compile_options: &CompileOptions,
+    no_cache: bool,
+    options: FileOptions,
+) -> Validation {
+    let line_starts = parse::compute_line_starts(source);
+    let base_dir = Path::new(file_path).parent().unwrap_or(Path::new("."));
+
+    let mut result = FileResult::skipped(file_path);
     let mut warnings = Vec::new();
+    let mut cache_outcome = None;
+    let mut timing: Option<TimingBreakdown> = None;
+    for region in embed::extract_regions(source) {
+        let text = region.text(source);
+        let (region_schema, region_options) = match (region.kind, &region.schema) {
+            (RegionKind::FrontMatter | RegionKind::YamlFrontMatter, _) => (
+                schema_source.cloned(),
+                FileOptions {
+                    dialect: parse::Dialect::Jsonc,
+                    lint: LintOptions::default(),
+                    ..options
+                },
+            ),
+            (RegionKind::CodeBlock, Some(reference)) => (
+                Some(schema::resolve_schema_ref(reference, base_dir)),
+                FileOptions::default(),
+            ),
+            (RegionKind::CodeBlock, None) => {
+                if parse::extract_schema_field_from_str(&text).is_none() {
+                    continue;
+                }
+                (None, FileOptions::default())
+            }
+        };
+
+        // YAML front matter is validated as the JSON it converts to.
+        let yaml = (region.kind == RegionKind::YamlFrontMatter).then(|| yaml::to_json(&text));
+        let validate = |text: &str| {
+            validate_document(
+                file_path,
+                text,
+                region_schema.as_ref(),
+                schema_cache,
+                compile_options,
+                no_cache,
+                region_options,
+            )
+        };
+        let (region_result, region_warnings, region_cache, region_timing) = match &yaml {
+            Some(Ok(converted)) => validate(&converted.json),
+            Some(Err(error)) => {
+                let diag = parse_diagnostic(error.clone(), &line_starts, parse::Dialect::Jsonc);
+                (
+                    FileResult::invalid(file_path, vec![diag]),
+                    vec![],
+                    None,
+                    None,
+                )
+            }
+            None => validate(&text),
+        };
+        warnings.extend(region_warnings);
+        cache_outcome = cache_outcome.or(region_cache);
+        if let Some(t) = region_timing {
+            timing = Some(match timing {
+                Some(total) => TimingBreakdown {
+                    compile: total.compile + t.compile,
+                    validate: total.validate + t.validate,
+                },
+                None => t,
+            });
+        }
+        if region_result.skipped {
+            continue;
+        }
+
+        result.skipped = false;
+        result.valid &= region_result.valid;
+        result.tool_error |= region_result.tool_error;
+        for mut diag in region_result.errors {
+            // Diagnostics without a span of their own point at the opening
+            // line, so it's clear which region they belong to.
+            remap_diagnostic(&mut diag, &line_starts, &region.opener, |span| {
+                let span = match &yaml {
+                    Some(Ok(converted)) => converted.source_span(span)?,
+                    _ => span.clone(),
+                };
+                Some(region.host_span(&span))
+            });
+            result.errors.push(diag);
+        }
+    }
+    (result, warnings, cache_outcome, timing)
+}
 
+/// Move a diagnostic of an inner document into its host, recomputing
+/// locations from the host's `line_starts`. `map` translates an inner span to
+/// a host span; spans it can't translate, and a missing primary span, become
+/// `fallback`.
+fn remap_diagnostic(
+    diag: &mut FileDiagnostic,
+    line_starts: &[usize],
+    fallback: &Range<usize>,
+    map: impl Fn(&Range<usize>) -> Option<Range<usize>>,
+) {
+    let locate = |span: &Range<usize>| {
+        let (line, column) = parse::offset_to_line_col(line_starts, span.start);
+        SourceLocation {
+            line,
+            column,
+            offset: span.start,
+            length: span.len(),
+        }
+    };
+    let span = diag
+        .span
+        .as_ref()
+        .and_then(&map)
+        .unwrap_or_else(|| fallback.clone());
+    diag.location = Some(locate(&span));
+    diag.span = Some(span);
+    for related in &mut diag.related {
+        if let Some(span) = &related.span {
+            let span = map(span).unwrap_or_else(|| fallback.clone());
+            related.location = Some(locate(&span));
+            related.span = Some(span);
+        }
+    }
+}
+
+/// Validate a JSON document: the whole of a JSON file, or one region of a
+/// host file. Lint findings come after the validation errors, and make a
+/// file without a schema count as checked.
+fn validate_document(
+    file_path: &str,
+    source: &str,
+    schema_source: Option<&SchemaSource>,
+    schema_cache: &SchemaCache,
+    compile_options: &CompileOptions,
+    no_cache: bool,
+    options: FileOptions,
+) -> Validation {
     // Parse the file
-    let parsed = match parse::parse_jsonc(source) {
+    let parsed = match parse::parse_with_options(source, &options.dialect.parse_options()) {
         Ok(p) => p,
         Err(parse_errors) => {
             let line_starts = parse::compute_line_starts(source);
             let errors: Vec<FileDiagnostic> = parse_errors
                 .into_iter()
-                .map(|e| {
-                    let location = e.range.as_ref().map(|r| {
-                        let (line, col) = parse::offset_to_line_col(&line_starts, r.start);
-                        SourceLocation {
-                            line,
-                            column: col,
-                            offset: r.start,
-                            length: r.len(),
-                        }
-                    });
-                    FileDiagnostic {
-                        code: "parse(syntax)".into(),
-                        message: e.message,
-                        severity: Severity::Error,
-                        span: e.range,
-                        location,
-                        label: Some("syntax error".into()),
-                        help: None,
-                        schema_path: None,
-                    }
-                })
+                .map(|e| parse_diagnostic(e, &line_starts, options.dialect))
                 .collect();
-            return (FileResult::invalid(file_path, errors), warnings, None, None);
+            return (FileResult::invalid(file_path, errors), vec![], None, None);
         }
     };
 
+    let lints = lint::lint(&parsed, &options.lint);
+    let (mut result, warnings, cache_outcome, timing) = validate_parsed(
+        file_path,
+        &parsed,
+        schema_source,
+        schema_cache,
+        compile_options,
+        no_cache,
+        options,
+    );
+    if !lints.is_empty() {
+        result.skipped = false;
+        result.valid &= lints.iter().all(|d| d.severity != Severity::Error);
+        result.errors.extend(lints);
+    }
+    (result, warnings, cache_outcome, timing)
+}
+
+/// The diagnostic for an error parsing a document in `dialect`.
+fn parse_diagnostic(
+    e: parse::ParseError,
+    line_starts: &[usize],
+    dialect: parse::Dialect,
+) -> FileDiagnostic {
+    let location = e.range.as_ref().map(|r| {
+        let (line, col) = parse::offset_to_line_col(line_starts, r.start);
+        SourceLocation {
+            line,
+            column: col,
+            offset: r.start,
+            length: r.len(),
+        }
+    });
+    let (label, help) = if e.code == "syntax" {
+        ("syntax error".to_string(), None)
+    } else {
+        (
+            format!("not allowed in {dialect}"),
+            Some("Choose another dialect for this file with \"parsers\" in jvl.json.".to_string()),
+        )
+    };
+    FileDiagnostic {
+        code: format!("parse({})", e.code),
+        message: e.message,
+        severity: Severity::Error,
+        span: e.range,
+        location,
+        label: Some(label),
+        help,
+        schema_path: None,
+        related: vec![],
+    }
+}
+
+/// Validate a parsed document against its schema.
+fn validate_parsed(
+    file_path: &str,
+    parsed: &ParsedFile,
+    schema_source: Option<&SchemaSource>,
+    schema_cache: &SchemaCache,
+    compile_options: &CompileOptions,
+    no_cache: bool,
+    options: FileOptions,
+) -> Validation {
+    let mut warnings = Vec::new();
+
     // Determine schema source: explicit override > $schema field in file > config mapping
     let effective_schema = schema_source
         .cloned()
         .or_else(|| crate::schema::resolve_schema_from_value(&parsed.value, Path::new(file_path)));
 
     let Some(effective_schema) = effective_schema else {
-        if strict {
+        if options.strict {
             return (
                 FileResult::invalid(
                     file_path,
@@ -94,6 +345,7 @@ pub fn validate_file(
                                 .into(),
                         ),
                         schema_path: None,
+                        related: vec![],
                     }],
                 ),
                 warnings,
@@ -101,13 +353,18 @@ pub fn validate_file(
                 None,
             );
         }
-        return (FileResult::skipped(file_path), warnings, None, None);
+        let result = if options.check_syntax {
+            FileResult::valid(file_path)
+        } else {
+            FileResult::skipped(file_path)
+        };
+        return (result, warnings, None, None);
     };
 
     // Load schema and get/compile the validator
     let compile_start = Instant::now();
     let (validator, schema_warnings, cache_outcome) =
-        match schema_cache.get_or_compile(&effective_schema, no_cache) {
+        match schema_cache.get_or_compile(&effective_schema, compile_options, no_cache) {
             Ok(result) => result,
             Err(e) => {
                 let category = match &e {
@@ -152,6 +409,7 @@ pub fn validate_file(
                             label,
                             help: None,
                             schema_path: None,
+                            related: vec![],
                         }],
                     ),
                     warnings,
@@ -166,6 +424,34 @@ pub fn validate_file(
     // Validate
     let validate_start = Instant::now();
     let validation_errors: Vec<_> = validator.iter_errors(&parsed.value).collect();
+    // Deprecations and embedded content both come from annotations: collect
+    // them in one pass, and only when the schema uses their keywords.
+    let keywords = schema_cache
+        .annotation_keywords(&effective_schema, compile_options)
+        .unwrap_or_default();
+    let content_root = schema_cache
+        .get_schema_value(&effective_schema, compile_options)
+        .filter(|_| compile_options.validate_content && keywords.content);
+    let evaluation =
+        (keywords.deprecated || content_root.is_some()).then(|| validator.evaluate(&parsed.value));
+    let deprecations = match &evaluation {
+        Some(evaluation) if keywords.deprecated => collect_deprecations(parsed, evaluation),
+        _ => vec![],
+    };
+    let content_errors = match (&evaluation, content_root) {
+        (Some(evaluation), Some(root)) => collect_content_errors(
+            parsed,
+            evaluation,
+            &root,
+            &schema_cache
+                .documents(&effective_schema, compile_options)
+                .unwrap_or_default(),
+            compile_options,
+            no_cache,
+            &validation_errors,
+        ),
+        _ => vec![],
+    };
     let validate_duration = validate_start.elapsed();
 
     let timing = Some(TimingBreakdown {
@@ -173,22 +459,400 @@ pub fn validate_file(
         validate: validate_duration,
     });
 
-    if validation_errors.is_empty() {
-        return (
-            FileResult::valid(file_path),
-            warnings,
-            cache_outcome,
-            timing,
-        );
+    if validation_errors.is_empty() && content_errors.is_empty() {
+        let mut result = FileResult::valid(file_path);
+        result.errors = deprecations;
+        return (result, warnings, cache_outcome, timing);
     }
 
-    let errors = map_validation_errors(&parsed, &validation_errors);
-    (
-        FileResult::invalid(file_path, errors),
-        warnings,
-        cache_outcome,
-        timing,
-    )
+    let mut errors = map_validation_errors(parsed, &validation_errors);
+    errors.extend(content_errors);
+    errors.extend(deprecations);
+    let mut result = FileResult::invalid(file_path, errors);
+    // `x-deprecated` fails validation, but only with warnings.
+    result.valid = result.error_count() == 0;
+    (result, warnings, cache_outcome, timing)
+}
+
+/// Keywords whose array entries are alternative or combined subschemas.
+const COMPOSITION_KEYWORDS: &[&str] = &["allOf", "anyOf", "oneOf"];
+
+/// Report instance locations that use subschemas annotated with
+/// `deprecated: true`.
+///
+/// Uses annotation collection, so only subschemas that actually applied to
+/// the instance contribute — a deprecated `oneOf` branch is reported only when
+/// it is the one that matched. A deprecated composition branch marks the value
+/// (reported at the value); any other deprecated subschema under an object
+/// member marks the property (reported at its key).
+fn collect_deprecations(
+    parsed: &ParsedFile,
+    evaluation: &jsonschema::Evaluation,
+) -> Vec<FileDiagnostic> {
+    let mut seen = std::collections::HashSet::new();
+    let mut result = Vec::new();
+
+    for entry in evaluation.iter_annotations() {
+        let Some(annotations) = entry.annotations.value().as_object() else {
+            continue;
+        };
+        if annotations.get("deprecated") != Some(&serde_json::Value::Bool(true)) {
+            continue;
+        }
+        let instance_path = entry.instance_location;
+        if !seen.insert(instance_path.as_str().to_string()) {
+            continue;
+        }
+
+        let mut schema_segments = entry.schema_location.rsplit('/');
+        let is_branch = schema_segments
+            .next()
+            .is_some_and(|s| s.parse::<usize>().is_ok())
+            && schema_segments
+                .next()
+                .is_some_and(|s| COMPOSITION_KEYWORDS.contains(&s));
+        let property = match instance_path.iter().last() {
+            Some(LocationSegment::Property(name)) if !is_branch => Some(name.to_string()),
+            _ => None,
+        };
+
+        let (span, message, label) = match &property {
+            Some(name) => (
+                parsed.resolve_pointer_key(instance_path.iter()),
+                format!("property '{name}' is deprecated"),
+                "deprecated property",
+            ),
+            None => {
+                let value = parsed.value.pointer(instance_path.as_str());
+                let message = match value {
+                    Some(v) if !v.is_object() && !v.is_array() => {
+                        format!("value {v} is deprecated")
+                    }
+                    _ => "value is deprecated".to_string(),
+                };
+                (
+                    parsed.resolve_pointer(instance_path.iter()),
+                    message,
+                    "deprecated value",
+                )
+            }
+        };
+        let location = span.as_ref().map(|r| {
+            let (line, col) = parsed.offset_to_line_col(r.start);
+            SourceLocation {
+                line,
+                column: col,
+                offset: r.start,
+                length: r.len(),
+            }
+        });
+
+        result.push(FileDiagnostic {
+            code: "schema(deprecated)".into(),
+            message,
+            severity: Severity::Warning,
+            span,
+            location,
+            label: Some(label.into()),
+            help: annotations
+                .get("description")
+                .and_then(|d| d.as_str())
+                .map(str::to_string),
+            schema_path: Some(entry.schema_location.to_string()),
+            related: vec![],
+        });
+    }
+
+    // Annotations come out in schema order; report in document order.
+    result.sort_by_key(|d| d.span.as_ref().map(|r| r.start));
+    result
+}
+
+/// Whether a `contentMediaType` is JSON: `application/json` or a `+json`
+/// structured syntax suffix, ignoring parameters.
+fn is_json_media_type(media_type: &str) -> bool {
+    let essence = media_type.split(';').next().unwrap_or_default().trim();
+    essence.eq_ignore_ascii_case("application/json")
+        || essence.to_ascii_lowercase().ends_with("+json")
+}
+
+/// Validate JSON documents embedded in string values, as described by the
+/// content vocabulary: decode per `contentEncoding` (only `base64` is
+/// supported), parse when `contentMediaType` is JSON, and validate against
+/// `contentSchema`.
+///
+/// Like [`collect_deprecations`], this reads annotations, so only strings
+/// whose subschemas applied are checked. Inner diagnostics land on the
+/// matching characters of the escaped string in the outer file, or on the
+/// whole string when it is encoded.
+fn collect_content_errors(
+    parsed: &ParsedFile,
+    evaluation: &jsonschema::Evaluation,
+    root: &serde_json::Value,
+    documents: &BTreeMap<String, serde_json::Value>,
+    options: &CompileOptions,
+    no_cache: bool,
+    validation_errors: &[jsonschema::ValidationError],
+) -> Vec<FileDiagnostic> {
+    use base64::Engine as _;
+    use jsonschema::error::ValidationErrorKind;
+
+    let line_starts = parse::compute_line_starts(parsed.source);
+    let mut compiled: HashMap<(Option<&str>, String), Result<jsonschema::Validator, SchemaError>> =
+        HashMap::new();
+    let mut seen = HashSet::new();
+    let mut result = Vec::new();
+
+    for entry in evaluation.iter_annotations() {
+        let Some(annotations) = entry.annotations.value().as_object() else {
+            continue;
+        };
+        // Locate the schema object declaring the `contentSchema`, to compile
+        // it with `$ref`s resolved. The evaluation path is that location
+        // unless a `$ref` was followed on the way; then search by value, in
+        // the root and then in the documents it references (by URI).
+        let content_schema = annotations.get("contentSchema");
+        let declaring = content_schema.and_then(|schema| {
+            let location = entry.schema_location;
+            match root.pointer(location).and_then(|v| v.as_object()) {
+                Some(obj) if obj.get("contentSchema") == Some(schema) => {
+                    Some((None, location.to_string(), obj))
+                }
+                _ => find_content_schema(root, schema, &mut String::new())
+                    .map(|(pointer, obj)| (None, pointer, obj))
+                    .or_else(|| {
+                        documents.iter().find_map(|(uri, document)| {
+                            let (pointer, obj) =
+                                find_content_schema(document, schema, &mut String::new())?;
+                            Some((Some(uri.as_str()), pointer, obj))
+                        })
+                    }),
+            }
+        });
+        // Drafts 6 and 7 assert `contentMediaType`/`contentEncoding` rather
+        // than annotate them; read those from the declaring schema object.
+        let keywords = if annotations.contains_key("contentMediaType") {
+            Some(annotations)
+        } else {
+            declaring.as_ref().map(|(_, _, obj)| *obj)
+        };
+        let keyword = |name: &str| keywords?.get(name)?.as_str();
+        if !keyword("contentMediaType").is_some_and(is_json_media_type) {
+            continue;
+        }
+        let encoding = keyword("contentEncoding");
+        let schema_location = declaring
+            .as_ref()
+            .map(|(uri, p, _)| (*uri, format!("{p}/contentSchema")));
+
+        let instance_path = entry.instance_location;
+        let Some(serde_json::Value::String(value)) = parsed.value.pointer(instance_path.as_str())
+        else {
+            continue;
+        };
+        if !seen.insert((instance_path.as_str().to_string(), schema_location.clone())) {
+            continue;
+        }
+        let Some(span) = parsed.resolve_pointer(instance_path.iter()) else {
+            continue;
+        };
+        // Don't repeat media type and encoding failures the validator
+        // already reported.
+        let asserted = validation_errors.iter().any(|e| {
+            e.instance_path().as_str() == instance_path.as_str()
+                && matches!(
+                    e.kind(),
+                    ValidationErrorKind::ContentMediaType { .. }
+                        | ValidationErrorKind::ContentEncoding { .. }
+                        | ValidationErrorKind::FromUtf8 { .. }
+                )
+        });
+        let content_error = |code: &str, message: String, label: &str| {
+            let (line, column) = parse::offset_to_line_col(&line_starts, span.start);
+            FileDiagnostic {
+                code: code.into(),
+                message,
+                severity: Severity::Error,
+                span: Some(span.clone()),
+                location: Some(SourceLocation {
+                    line,
+                    column,
+                    offset: span.start,
+                    length: span.len(),
+                }),
+                label: Some(label.into()),
+                help: None,
+                schema_path: Some(entry.schema_location.to_string()),
+                related: vec![],
+            }
+        };
+
+        let (text, offsets) = match encoding {
+            None => (
+                value.clone(),
+                unescape_offsets(&parsed.source[span.clone()])
+                    .filter(|o| o.len() == value.len() + 1),
+            ),
+            Some(e) if e.eq_ignore_ascii_case("base64") => {
+                let decoded = base64::engine::general_purpose::STANDARD
+                    .decode(value)
+                    .map_err(|e| format!("invalid base64 content: {e}"))
+                    .and_then(|bytes| {
+                        String::from_utf8(bytes)
+                            .map_err(|_| "decoded content is not valid UTF-8".to_string())
+                    });
+                match decoded {
+                    Ok(text) => (text, None),
+                    Err(message) => {
+                        if !asserted {
+                            result.push(content_error(
+                                "content(encoding)",
+                                message,
+                                "invalid content encoding",
+                            ));
+                        }
+                        continue;
+                    }
+                }
+            }
+            Some(_) => continue,
+        };
+        let map = |inner: &Range<usize>| {
+            let offsets = offsets.as_ref()?;
+            Some(span.start + offsets.get(inner.start)?..span.start + offsets.get(inner.end)?)
+        };
+
+        let inner = match parse::parse_with_options(&text, &parse::strict_parse_options()) {
+            Ok(inner) => inner,
+            Err(parse_errors) => {
+                if !asserted {
+                    for e in parse_errors {
+                        let mut diag =
+                            content_error("content(parse)", e.message, "invalid embedded JSON");
+                        diag.span = e.range;
+                        remap_diagnostic(&mut diag, &line_starts, &span, map);
+                        result.push(diag);
+                    }
+                }
+                continue;
+            }
+        };
+
+        let Some(schema_location) = schema_location else {
+            if content_schema.is_some() {
+                result.push(content_error(
+                    "schema(compile)",
+                    "could not locate the contentSchema in the schema or the documents \
+                     it references"
+                        .to_string(),
+                    "content schema applies here",
+                ));
+            }
+            continue;
+        };
+        let content_validator = compiled.entry(schema_location.clone()).or_insert_with(|| {
+            let (uri, location) = &schema_location;
+            match uri {
+                Some(uri) => schema::compile_document_subschema(
+                    root, documents, uri, location, options, no_cache,
+                ),
+                None => schema::compile_subschema(root, location, options, no_cache),
+            }
+        });
+        let content_validator = match content_validator {
+            Ok(v) => v,
+            Err(e) => {
+                result.push(content_error(
+                    "schema(compile)",
+                    format!("invalid contentSchema: {e}"),
+                    "content schema applies here",
+                ));
+                continue;
+            }
+        };
+        let inner_errors: Vec<_> = content_validator.iter_errors(&inner.value).collect();
+        for mut diag in map_validation_errors(&inner, &inner_errors) {
+            remap_diagnostic(&mut diag, &line_starts, &span, map);
+            result.push(diag);
+        }
+    }
+    result
+}
+
+/// Find the schema object whose `contentSchema` is `content_schema`,
+/// returning its JSON pointer. `pointer` is the location of `value`.
+fn find_content_schema<'a>(
+    value: &'a serde_json::Value,
+    content_schema: &serde_json::Value,
+    pointer: &mut String,
+) -> Option<(String, &'a serde_json::Map<String, serde_json::Value>)> {
+    let len = pointer.len();
+    let found = match value {
+        serde_json::Value::Object(obj) => {
+            if obj.get("contentSchema") == Some(content_schema) {
+                return Some((pointer.clone(), obj));
+            }
+            obj.iter().find_map(|(key, child)| {
+                pointer.truncate(len);
+                pointer.push('/');
+                pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                find_content_schema(child, content_schema, pointer)
+            })
+        }
+        serde_json::Value::Array(items) => items.iter().enumerate().find_map(|(i, child)| {
+            pointer.truncate(len);
+            pointer.push_str(&format!("/{i}"));
+            find_content_schema(child, content_schema, pointer)
+        }),
+        _ => None,
+    };
+    pointer.truncate(len);
+    found
+}
+
+/// For a JSON string literal (quotes included), the offset in the literal
+/// of each byte of its unescaped value, plus a final entry for the closing
+/// quote. Every byte an escape sequence produces maps to its backslash.
+fn unescape_offsets(literal: &str) -> Option<Vec<usize>> {
+    let bytes = literal.as_bytes();
+    let end = literal.len().checked_sub(1).filter(|&end| end > 0)?;
+    if bytes[0] != b'"' || bytes[end] != b'"' {
+        return None;
+    }
+    let hex = |at: usize| {
+        literal
+            .get(at..at + 4)
+            .and_then(|h| u32::from_str_radix(h, 16).ok())
+    };
+    let mut offsets = Vec::with_capacity(literal.len());
+    let mut i = 1;
+    while i < end {
+        if bytes[i] != b'\\' {
+            offsets.push(i);
+            i += 1;
+            continue;
+        }
+        let (escape_len, produced) = match bytes.get(i + 1)? {
+            b'u' => {
+                let high = hex(i + 2)?;
+                let low = (literal.get(i + 6..i + 8) == Some("\\u"))
+                    .then(|| hex(i + 8))
+                    .flatten()
+                    .filter(|_| (0xD800..0xDC00).contains(&high));
+                match low {
+                    // A surrogate pair encodes one 4-byte character.
+                    Some(_) => (12, 4),
+                    // Lone surrogates decode to U+FFFD.
+                    None => (6, char::from_u32(high).map_or(3, char::len_utf8)),
+                }
+            }
+            _ => (2, 1),
+        };
+        offsets.extend(std::iter::repeat_n(i, produced));
+        i += escape_len;
+    }
+    offsets.push(end);
+    Some(offsets)
 }
 
 /// Map jsonschema validation errors to our diagnostic format.
@@ -258,6 +922,7 @@ fn map_validation_errors(
                             "Remove the property, or check for typos in the property name.".into(),
                         ),
                         schema_path: Some(schema_path.clone()),
+                        related: vec![],
                     });
                 }
             }
@@ -291,6 +956,7 @@ fn map_validation_errors(
                             "Remove the extra items, or update the schema to allow more.".into(),
                         ),
                         schema_path: Some(schema_path.clone()),
+                        related: vec![],
                     });
                     idx += 1;
                     any_emitted = true;
@@ -301,6 +967,15 @@ fn map_validation_errors(
                 }
             }
 
+            // Report the closest alternative's own errors instead of a bare
+            // "not valid under any of the schemas".
+            ValidationErrorKind::AnyOf { context }
+            | ValidationErrorKind::OneOfNotValid { context }
+                if !context.is_empty() =>
+            {
+                result.extend(explain_union_failure(parsed, err, context, &schema_path));
+            }
+
             _ => result.push(make_diagnostic(parsed, err, &schema_path)),
         }
     }
@@ -308,6 +983,94 @@ fn map_validation_errors(
     result
 }
 
+/// Explain a failed `anyOf`/`oneOf` by reporting the closest branch's errors,
+/// the first of them with one related note per remaining branch.
+///
+/// Branches are ranked by, in order: whether a discriminator matched (no
+/// `const`/`enum` failure on a direct property of the value), whether the
+/// value's type matched, and the number of errors. When even the best branch
+/// fails on its discriminator or type, no branch is meaningfully closer, so
+/// the union's own diagnostic is kept and every branch is listed as related.
+fn explain_union_failure(
+    parsed: &ParsedFile,
+    err: &jsonschema::ValidationError,
+    context: &[Vec<jsonschema::ValidationError<'static>>],
+    schema_path: &str,
+) -> Vec<FileDiagnostic> {
+    use jsonschema::error::ValidationErrorKind;
+
+    let keyword = err.kind().keyword();
+    let base = err.instance_path().as_str();
+    let rank = |errors: &[jsonschema::ValidationError]| {
+        let discriminator_mismatch = errors.iter().any(|e| {
+            matches!(
+                e.kind(),
+                ValidationErrorKind::Constant { .. } | ValidationErrorKind::Enum { .. }
+            ) && e
+                .instance_path()
+                .as_str()
+                .strip_prefix(base)
+                .and_then(|rest| rest.strip_prefix('/'))
+                .is_some_and(|rest| !rest.is_empty() && !rest.contains('/'))
+        });
+        let type_mismatch = errors.iter().any(|e| {
+            matches!(e.kind(), ValidationErrorKind::Type { .. })
+                && e.instance_path().as_str() == base
+        });
+        (discriminator_mismatch, type_mismatch, errors.len())
+    };
+    let best = (0..context.len())
+        .min_by_key(|&i| rank(&context[i]))
+        .filter(|&i| {
+            let (discriminator_mismatch, type_mismatch, _) = rank(&context[i]);
+            !discriminator_mismatch && !type_mismatch
+        });
+
+    let related: Vec<RelatedInfo> = context
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| Some(*i) != best)
+        .map(|(i, errors)| {
+            let first = errors.first();
+            let message = match first {
+                Some(e) if errors.len() > 1 => {
+                    format!("{keyword}/{i}: {e} (and {} more)", errors.len() - 1)
+                }
+                Some(e) => format!("{keyword}/{i}: {e}"),
+                None => format!("{keyword}/{i}: matched"),
+            };
+            let span = first.and_then(|e| parsed.resolve_pointer(e.instance_path().iter()));
+            let location = span.as_ref().map(|r| {
+                let (line, col) = parsed.offset_to_line_col(r.start);
+                SourceLocation {
+                    line,
+                    column: col,
+                    offset: r.start,
+                    length: r.len(),
+                }
+            });
+            RelatedInfo {
+                message,
+                path: None,
+                span,
+                location,
+            }
+        })
+        .collect();
+
+    let mut diagnostics = match best {
+        Some(i) => map_validation_errors(parsed, &context[i]),
+        None => vec![],
+    };
+    if diagnostics.is_empty() {
+        diagnostics.push(make_diagnostic(parsed, err, schema_path));
+    }
+    // The notes are about the union as a whole, so they go once, on the
+    // diagnostic that leads its report, not on every error of the branch.
+    diagnostics[0].related.extend(related);
+    diagnostics
+}
+
 /// Build a single `FileDiagnostic` from a validation error using the standard
 /// instance_path → span resolution.
 fn make_diagnostic(
@@ -330,12 +1093,13 @@ fn make_diagnostic(
     FileDiagnostic {
         code: format!("schema({keyword})"),
         message: err.to_string(),
-        severity: Severity::Error,
+        severity: ExtensionKeyword::from_name(keyword).map_or(Severity::Error, |k| k.severity()),
         span,
         location,
         label: Some(format_validation_label(err)),
         help: format_validation_help(err),
         schema_path: Some(schema_path.to_string()),
+        related: vec![],
     }
 }
 
@@ -0,0 +1,386 @@
+//! YAML front matter, converted to JSON for validation.
+//!
+//! The conversion keeps a map from spans of the JSON text back to the YAML
+//! nodes they came from, so diagnostics for the JSON can point into the YAML.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use saphyr_parser::{Event, Parser, ScalarStyle, Span, Tag};
+use serde_json::Value;
+
+use crate::parse::ParseError;
+
+/// A YAML document converted to JSON text.
+#[derive(Debug)]
+pub struct Converted {
+    pub json: String,
+    /// Spans of the JSON text and the YAML spans they were converted from:
+    /// one per node, and one per mapping key.
+    spans: Vec<(Range<usize>, Range<usize>)>,
+}
+
+impl Converted {
+    /// The YAML span of the innermost node or key whose JSON contains `span`.
+    pub fn source_span(&self, span: &Range<usize>) -> Option<Range<usize>> {
+        self.spans
+            .iter()
+            .filter(|(json, _)| json.start <= span.start && span.end <= json.end)
+            .min_by_key(|(json, _)| json.len())
+            .map(|(_, yaml)| yaml.clone())
+    }
+}
+
+/// Convert the first document of a YAML `source` to JSON, typing plain
+/// scalars by the YAML 1.2 core schema. An empty document is `null`.
+pub fn to_json(source: &str) -> Result<Converted, ParseError> {
+    let mut converter = Converter::new(source);
+    let mut parser = Parser::new_from_str(source);
+    while let Some(event) = parser.next_event() {
+        let (event, span) = event.map_err(|e| {
+            let start = converter.offset(e.marker().index());
+            syntax_error(e.info().to_string(), start..start)
+        })?;
+        match event {
+            Event::DocumentEnd => break,
+            Event::Scalar(text, style, anchor, tag) => {
+                let value = scalar_value(&text, style, tag.as_deref());
+                let range = converter.scalar_range(span, style);
+                converter.scalar(value, range, anchor)?;
+            }
+            Event::Alias(anchor) => {
+                let range = converter.range(span);
+                converter.alias(anchor, range)?;
+            }
+            Event::MappingStart(anchor, _) | Event::SequenceStart(anchor, _) => {
+                let mapping = matches!(event, Event::MappingStart(..));
+                let range = converter.range(span);
+                converter.start(mapping, range, anchor)?;
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                let range = converter.range(span);
+                converter.end(range);
+            }
+            _ => {}
+        }
+    }
+    if converter.json.is_empty() {
+        converter.json.push_str("null");
+    }
+    Ok(Converted {
+        json: converter.json,
+        spans: converter.spans,
+    })
+}
+
+fn syntax_error(message: String, range: Range<usize>) -> ParseError {
+    ParseError {
+        code: "syntax",
+        message,
+        range: Some(range),
+    }
+}
+
+/// An open mapping or sequence.
+struct Collection {
+    mapping: bool,
+    /// Entries written so far.
+    len: usize,
+    /// For a mapping, whether the next node is a key.
+    expects_key: bool,
+    anchor: usize,
+    json_start: usize,
+    yaml_start: usize,
+}
+
+struct Converter<'s> {
+    source: &'s str,
+    /// Byte offset of each char of `source`; the parser reports char indices.
+    char_offsets: Vec<usize>,
+    json: String,
+    spans: Vec<(Range<usize>, Range<usize>)>,
+    stack: Vec<Collection>,
+    /// The JSON of each anchored node, for its aliases.
+    anchors: HashMap<usize, String>,
+    /// End of the YAML node converted last.
+    last_end: usize,
+}
+
+impl<'s> Converter<'s> {
+    fn new(source: &'s str) -> Self {
+        Self {
+            source,
+            char_offsets: source.char_indices().map(|(i, _)| i).collect(),
+            json: String::new(),
+            spans: Vec::new(),
+            stack: Vec::new(),
+            anchors: HashMap::new(),
+            last_end: 0,
+        }
+    }
+
+    /// Byte offset of a char index.
+    fn offset(&self, index: usize) -> usize {
+        self.char_offsets
+            .get(index)
+            .copied()
+            .unwrap_or(self.source.len())
+    }
+
+    fn range(&self, span: Span) -> Range<usize> {
+        self.offset(span.start.index())..self.offset(span.end.index())
+    }
+
+    /// The parser's spans of quoted and block scalars run on over the
+    /// whitespace and comments after them, so find their ends in the source.
+    fn scalar_range(&self, span: Span, style: ScalarStyle) -> Range<usize> {
+        let range = self.range(span);
+        let text = &self.source[range.clone()];
+        let len = match style {
+            ScalarStyle::Plain => text.len(),
+            ScalarStyle::SingleQuoted => quoted_len(text, b'\''),
+            ScalarStyle::DoubleQuoted => quoted_len(text, b'"'),
+            ScalarStyle::Literal | ScalarStyle::Folded => text.trim_end().len(),
+        };
+        range.start..range.start + len
+    }
+
+    /// Write the separator before a node, and for a mapping value, its key.
+    /// Returns whether the node is a mapping key.
+    fn begin(&mut self) -> bool {
+        let Some(parent) = self.stack.last_mut() else {
+            return false;
+        };
+        let is_key = parent.mapping && parent.expects_key;
+        if (is_key || !parent.mapping) && parent.len > 0 {
+            self.json.push(',');
+        }
+        if is_key || !parent.mapping {
+            parent.len += 1;
+        }
+        is_key
+    }
+
+    /// Record a converted node, and make the next node of its mapping a key.
+    fn finish(&mut self, json_start: usize, yaml: Range<usize>, anchor: usize) {
+        self.last_end = yaml.end;
+        self.spans.push((json_start..self.json.len(), yaml));
+        if anchor > 0 {
+            self.anchors
+                .insert(anchor, self.json[json_start..].to_string());
+        }
+        if let Some(parent) = self.stack.last_mut()
+            && parent.mapping
+        {
+            parent.expects_key = true;
+        }
+    }
+
+    fn scalar(
+        &mut self,
+        value: Value,
+        yaml: Range<usize>,
+        anchor: usize,
+    ) -> Result<(), ParseError> {
+        let text = value.to_string();
+        if anchor > 0 {
+            self.anchors.insert(anchor, text.clone());
+        }
+        self.node(text, yaml)
+    }
+
+    fn alias(&mut self, anchor: usize, yaml: Range<usize>) -> Result<(), ParseError> {
+        let text = self
+            .anchors
+            .get(&anchor)
+            .cloned()
+            .unwrap_or_else(|| "null".into());
+        self.node(text, yaml)
+    }
+
+    /// Write a scalar or alias node, converted to `json`.
+    fn node(&mut self, json: String, yaml: Range<usize>) -> Result<(), ParseError> {
+        if !self.begin() {
+            let start = self.json.len();
+            self.json.push_str(&json);
+            self.finish(start, yaml, 0);
+            return Ok(());
+        }
+        // JSON keys are strings: other scalars become their JSON text.
+        let key = match serde_json::from_str(&json) {
+            Ok(Value::String(key)) => key,
+            Ok(Value::Array(_) | Value::Object(_)) | Err(_) => {
+                return Err(syntax_error(
+                    "Only scalar mapping keys can be converted to JSON".into(),
+                    yaml,
+                ));
+            }
+            Ok(other) => other.to_string(),
+        };
+        let start = self.json.len();
+        self.json.push_str(&Value::String(key).to_string());
+        self.spans.push((start..self.json.len(), yaml.clone()));
+        self.json.push(':');
+        self.last_end = yaml.end;
+        if let Some(parent) = self.stack.last_mut() {
+            parent.expects_key = false;
+        }
+        Ok(())
+    }
+
+    fn start(
+        &mut self,
+        mapping: bool,
+        yaml: Range<usize>,
+        anchor: usize,
+    ) -> Result<(), ParseError> {
+        if self.begin() {
+            return Err(syntax_error(
+                "Only scalar mapping keys can be converted to JSON".into(),
+                yaml,
+            ));
+        }
+        self.stack.push(Collection {
+            mapping,
+            len: 0,
+            expects_key: true,
+            anchor,
+            json_start: self.json.len(),
+            yaml_start: yaml.start,
+        });
+        self.json.push(if mapping { '{' } else { '[' });
+        self.last_end = yaml.end;
+        Ok(())
+    }
+
+    fn end(&mut self, yaml: Range<usize>) {
+        let Some(collection) = self.stack.pop() else {
+            return;
+        };
+        self.json.push(if collection.mapping { '}' } else { ']' });
+        // A flow collection ends at its bracket; a block collection's end
+        // event comes where the next node starts, so it ends with its last
+        // entry instead.
+        let end = if yaml.is_empty() {
+            self.last_end
+        } else {
+            yaml.end
+        };
+        self.finish(
+            collection.json_start,
+            collection.yaml_start..end.max(collection.yaml_start),
+            collection.anchor,
+        );
+    }
+}
+
+/// Length of the quoted scalar starting `text`, through its closing `quote`.
+fn quoted_len(text: &str, quote: u8) -> usize {
+    let bytes = text.as_bytes();
+    let mut i = 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            // `\"` in double quotes, `''` in single quotes.
+            b'\\' if quote == b'"' => i += 1,
+            b'\'' if quote == b'\'' && bytes.get(i + 1) == Some(&b'\'') => i += 1,
+            b if b == quote => return i + 1,
+            _ => {}
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+/// The JSON value of a scalar. Quoted and block scalars, and scalars tagged
+/// `!!str`, are strings; plain scalars may be null, booleans or numbers.
+fn scalar_value(text: &str, style: ScalarStyle, tag: Option<&Tag>) -> Value {
+    let is_str = tag.is_some_and(|t| t.is_yaml_core_schema() && t.suffix == "str");
+    if style != ScalarStyle::Plain || is_str {
+        return Value::String(text.to_string());
+    }
+    match text {
+        "" | "~" | "null" | "Null" | "NULL" => Value::Null,
+        "true" | "True" | "TRUE" => Value::Bool(true),
+        "false" | "False" | "FALSE" => Value::Bool(false),
+        _ => number(text).unwrap_or_else(|| Value::String(text.to_string())),
+    }
+}
+
+/// A plain scalar as a number, if it is an integer (decimal, `0o` octal or
+/// `0x` hexadecimal) or a finite float.
+fn number(text: &str) -> Option<Value> {
+    if let Some(octal) = text.strip_prefix("0o") {
+        return u64::from_str_radix(octal, 8).ok().map(Value::from);
+    }
+    if let Some(hex) = text.strip_prefix("0x") {
+        return u64::from_str_radix(hex, 16).ok().map(Value::from);
+    }
+    let bytes = text.as_bytes();
+    let numeric = bytes.iter().any(u8::is_ascii_digit)
+        && bytes
+            .iter()
+            .all(|b| b.is_ascii_digit() || b"+-.eE".contains(b));
+    if !numeric {
+        return None;
+    }
+    if let Ok(int) = text.parse::<i64>() {
+        return Some(Value::from(int));
+    }
+    text.parse::<f64>()
+        .ok()
+        .and_then(serde_json::Number::from_f64)
+        .map(Value::Number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_to_json_by_the_core_schema() {
+        let source = "title: Hello\ndraft: false\nweight: 0x10\nratio: 1.5e1\n\
+                      empty:\ntags: [a, 'b', \"1\"]\nn: &n 3\nm: *n\nq: !!str 7\n\
+                      body: |\n  text\n";
+        let converted = to_json(source).unwrap();
+        let value: Value = serde_json::from_str(&converted.json).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "title": "Hello",
+                "draft": false,
+                "weight": 16,
+                "ratio": 15.0,
+                "empty": null,
+                "tags": ["a", "b", "1"],
+                "n": 3,
+                "m": 3,
+                "q": "7",
+                "body": "text\n",
+            })
+        );
+        assert_eq!(to_json("").unwrap().json, "null");
+    }
+
+    #[test]
+    fn maps_json_spans_to_yaml_spans() {
+        let source = "title: Hello\ntags:\n  - 'a b'  # first\n  - c\nflow: {x: 1}\n";
+        let converted = to_json(source).unwrap();
+        let yaml = |json: &str| {
+            let start = converted.json.find(json).unwrap();
+            let span = converted.source_span(&(start..start + json.len())).unwrap();
+            &source[span]
+        };
+        assert_eq!(yaml("\"Hello\""), "Hello");
+        assert_eq!(yaml("\"tags\""), "tags");
+        assert_eq!(yaml("\"a b\""), "'a b'");
+        assert_eq!(yaml("[\"a b\",\"c\"]"), "- 'a b'  # first\n  - c");
+        assert_eq!(yaml("{\"x\":1}"), "{x: 1}");
+    }
+
+    #[test]
+    fn reports_syntax_errors_at_their_offset() {
+        let error = to_json("é: [1\nb: 2\n").unwrap_err();
+        assert_eq!(error.code, "syntax");
+        assert_eq!(error.range, Some(8..8));
+    }
+}
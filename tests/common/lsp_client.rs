@@ -7,6 +7,9 @@ use tower_lsp_server::{LspService, Server};
 
 use jvl::lsp::Backend;
 
+/// An incremental edit: `((start_line, start_char), (end_line, end_char), text)`.
+pub type RangeEdit<'a> = ((u32, u32), (u32, u32), &'a str);
+
 /// In-process LSP test client backed by `tokio::io::duplex`.
 ///
 /// The server runs in a background task on the same tokio runtime. Time-control
@@ -90,16 +93,38 @@ impl TestClient {
         &mut self,
         capabilities: serde_json::Value,
     ) -> serde_json::Value {
+        self.initialize_raw(serde_json::json!({
+            "capabilities": capabilities,
+            "processId": null,
+            "rootUri": null
+        }))
+        .await
+    }
+
+    /// Like `initialize` but with a workspace root and `initializationOptions`.
+    pub async fn initialize_with_options(
+        &mut self,
+        root_uri: &str,
+        options: serde_json::Value,
+    ) -> serde_json::Value {
+        self.initialize_raw(serde_json::json!({
+            "capabilities": {},
+            "processId": null,
+            "rootUri": root_uri,
+            "initializationOptions": options
+        }))
+        .await
+    }
+
+    /// Send `initialize` with the given params and `initialized`; return the
+    /// `InitializeResult`.
+    pub async fn initialize_raw(&mut self, params: serde_json::Value) -> serde_json::Value {
         let id = self.next_id.fetch_add(1, Ordering::Relaxed);
         self.send(serde_json::json!({
             "jsonrpc": "2.0",
             "id": id,
             "method": "initialize",
-            "params": {
-                "capabilities": capabilities,
-                "processId": null,
-                "rootUri": null
-            }
+            "params": params
         }))
         .await;
 
@@ -154,6 +179,35 @@ impl TestClient {
         .await;
     }
 
+    /// Send `textDocument/didChange` with incremental range edits, applied
+    /// in order.
+    pub async fn did_change_ranges(&mut self, uri: &str, version: i32, changes: &[RangeEdit<'_>]) {
+        let changes: Vec<serde_json::Value> = changes
+            .iter()
+            .map(|((start_line, start_char), (end_line, end_char), text)| {
+                serde_json::json!({
+                    "range": {
+                        "start": {"line": start_line, "character": start_char},
+                        "end": {"line": end_line, "character": end_char}
+                    },
+                    "text": text
+                })
+            })
+            .collect();
+        self.send(serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didChange",
+            "params": {
+                "textDocument": {
+                    "uri": uri,
+                    "version": version
+                },
+                "contentChanges": changes
+            }
+        }))
+        .await;
+    }
+
     /// Send `textDocument/didClose`.
     pub async fn did_close(&mut self, uri: &str) {
         self.send(serde_json::json!({
@@ -180,6 +234,16 @@ impl TestClient {
         .await;
     }
 
+    /// Send `workspace/didChangeConfiguration` with pushed settings.
+    pub async fn did_change_configuration(&mut self, settings: serde_json::Value) {
+        self.send(serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "workspace/didChangeConfiguration",
+            "params": { "settings": settings }
+        }))
+        .await;
+    }
+
     /// Send `textDocument/hover` request and return the result.
     pub async fn hover(&mut self, uri: &str, line: u32, character: u32) -> serde_json::Value {
         let id = self.next_id.fetch_add(1, Ordering::Relaxed);
@@ -1,5 +1,6 @@
 pub mod lsp_client;
 
+use std::path::Path;
 use std::process::Command;
 
 pub fn jvl() -> Command {
@@ -11,6 +12,33 @@ pub fn fixture(name: &str) -> String {
     format!("{}/tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR"))
 }
 
+/// Write `content` to `rel` under `root`, creating its directories.
+#[allow(dead_code)]
+pub fn write(root: &Path, rel: &str, content: &str) {
+    let path = root.join(rel);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+}
+
+/// Run `jvl check --format json` in `dir` and parse the output.
+#[allow(dead_code)]
+pub fn check_json(dir: &Path, args: &[&str]) -> (Option<i32>, serde_json::Value) {
+    let output = jvl()
+        .args(["check", "--format", "json"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run jvl");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json = serde_json::from_str(&stdout).unwrap_or_else(|e| {
+        panic!(
+            "invalid JSON: {e}\nstdout: {stdout}\nstderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )
+    });
+    (output.status.code(), json)
+}
+
 /// Run jvl with --format json and parse the output.
 #[allow(dead_code)]
 pub fn jvl_json(args: &[&str]) -> (serde_json::Value, i32) {
@@ -0,0 +1,217 @@
+mod common;
+
+use std::path::Path;
+
+use common::{check_json, jvl, write};
+
+/// Requires `{ name: string }`.
+const NAME_SCHEMA: &str = r#"{ "type": "object", "required": ["name"] }"#;
+/// Requires `{ port: number }`.
+const PORT_SCHEMA: &str = r#"{ "type": "object", "required": ["port"] }"#;
+
+/// File paths reported in the JSON output relative to `root`, sorted.
+fn checked_paths(json: &serde_json::Value, root: &Path) -> Vec<String> {
+    let root = format!("{}/", std::fs::canonicalize(root).unwrap().display());
+    let mut paths: Vec<String> = json["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| {
+            let path = f["path"].as_str().unwrap();
+            path.strip_prefix(&root).unwrap_or(path).replace('\\', "/")
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// A package config extends a shared base:
+///   project/
+///     base/jvl.json          (port schema for *.app.json, strict)
+///     base/port.schema.json
+///     pkg/jvl.json           (extends ../base/jvl.json, name schema for special.app.json)
+///     pkg/name.schema.json
+///     pkg/a.app.json         (missing port)
+///     pkg/special.app.json   (missing name)
+///     pkg/other.json         (no schema → error under inherited strict)
+fn setup_extends() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    write(
+        root,
+        "base/jvl.json",
+        r#"{
+  "files": ["**/*.json", "!**/*.schema.json"],
+  "strict": true,
+  "schemas": [{ "path": "port.schema.json", "files": ["**/*.app.json"] }]
+}"#,
+    );
+    write(root, "base/port.schema.json", PORT_SCHEMA);
+    write(
+        root,
+        "pkg/jvl.json",
+        r#"{
+  "extends": "../base/jvl.json",
+  "files": ["!jvl.json"],
+  "schemas": [{ "path": "name.schema.json", "files": ["special.app.json"] }]
+}"#,
+    );
+    write(root, "pkg/name.schema.json", NAME_SCHEMA);
+    write(root, "pkg/a.app.json", r#"{ "name": "a" }"#);
+    write(root, "pkg/special.app.json", r#"{ "port": 1 }"#);
+    write(root, "pkg/other.json", "{}");
+    dir
+}
+
+#[test]
+fn extends_merges_files_schemas_and_strict() {
+    let dir = setup_extends();
+    let (code, json) = check_json(&dir.path().join("pkg"), &[]);
+    assert_eq!(code, Some(1));
+
+    // Base `files` patterns apply first, then the package's own.
+    assert_eq!(
+        checked_paths(&json, &dir.path().join("pkg")),
+        ["a.app.json", "other.json", "special.app.json"]
+    );
+
+    let files = json["files"].as_array().unwrap();
+    let file = |name: &str| {
+        files
+            .iter()
+            .find(|f| f["path"].as_str().unwrap().ends_with(name))
+            .unwrap()
+    };
+    // The inherited mapping's path resolves against the base config's directory.
+    assert!(
+        file("a.app.json")["errors"][0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("\"port\"")
+    );
+    // The package's own mapping wins over the inherited one.
+    assert!(
+        file("special.app.json")["errors"][0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("\"name\"")
+    );
+    // `strict` is inherited.
+    assert_eq!(file("other.json")["errors"][0]["code"], "no-schema");
+}
+
+#[test]
+fn extends_override_replaces_scalar_settings() {
+    let dir = setup_extends();
+    write(
+        dir.path(),
+        "pkg/jvl.json",
+        r#"{ "extends": ["../base/jvl.json"], "files": ["!jvl.json"], "strict": false }"#,
+    );
+    let (code, json) = check_json(&dir.path().join("pkg"), &["other.json"]);
+    assert_eq!(code, Some(0));
+    assert_eq!(json["summary"]["skipped_files"], 1, "json: {json:#}");
+}
+
+#[test]
+fn extends_cycle_is_reported() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "jvl.json", r#"{ "extends": "./a.json" }"#);
+    write(dir.path(), "a.json", r#"{ "extends": "./jvl.json" }"#);
+
+    let output = jvl()
+        .args(["check"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run jvl");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("extends back to"), "stderr: {stderr}");
+}
+
+#[test]
+fn extends_missing_file_is_reported() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "jvl.json", r#"{ "extends": "./missing.json" }"#);
+
+    let output = jvl()
+        .args(["check"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run jvl");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("missing.json"), "stderr: {stderr}");
+}
+
+/// Running from the root of a monorepo applies each package's jvl.json to
+/// its own subtree:
+///   project/
+///     jvl.json               (port schema for *.app.json)
+///     port.schema.json
+///     root.app.json          (missing port)
+///     pkg/jvl.json           (extends the root, name schema for *.app.json, ignores skip.json)
+///     pkg/name.schema.json
+///     pkg/a.app.json         (has port, missing name)
+///     pkg/skip.json
+fn setup_monorepo() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    write(
+        root,
+        "jvl.json",
+        r#"{
+  "files": ["**/*.json", "!**/*.schema.json", "!**/jvl.json"],
+  "schemas": [{ "path": "port.schema.json", "files": ["**/*.app.json"] }]
+}"#,
+    );
+    write(root, "port.schema.json", PORT_SCHEMA);
+    write(root, "root.app.json", r#"{ "name": "root" }"#);
+    write(
+        root,
+        "pkg/jvl.json",
+        r#"{
+  "extends": "../jvl.json",
+  "files": ["!skip.json"],
+  "schemas": [{ "path": "name.schema.json", "files": ["*.app.json"] }]
+}"#,
+    );
+    write(root, "pkg/name.schema.json", NAME_SCHEMA);
+    write(root, "pkg/a.app.json", r#"{ "port": 1 }"#);
+    write(root, "pkg/skip.json", "{}");
+    dir
+}
+
+#[test]
+fn nested_config_applies_to_its_subtree() {
+    let dir = setup_monorepo();
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(1));
+    assert_eq!(
+        checked_paths(&json, dir.path()),
+        ["pkg/a.app.json", "root.app.json"]
+    );
+
+    for file in json["files"].as_array().unwrap() {
+        let message = file["errors"][0]["message"].as_str().unwrap();
+        let expected = if file["path"].as_str().unwrap().contains("pkg") {
+            "\"name\""
+        } else {
+            "\"port\""
+        };
+        assert!(message.contains(expected), "file: {file:#}");
+    }
+}
+
+#[test]
+fn nested_config_applies_to_explicit_files() {
+    let dir = setup_monorepo();
+    let (code, json) = check_json(dir.path(), &["pkg/a.app.json", "pkg"]);
+    assert_eq!(code, Some(1));
+    assert_eq!(
+        checked_paths(&json, dir.path()),
+        ["pkg/a.app.json", "pkg/a.app.json"]
+    );
+    let message = json["files"][0]["errors"][0]["message"].as_str().unwrap();
+    assert!(message.contains("\"name\""), "json: {json:#}");
+}
@@ -0,0 +1,209 @@
+mod common;
+
+use std::path::Path;
+
+use common::{jvl, write};
+
+/// Requires `{ port: number }`.
+const PORT_SCHEMA: &str = r#"{ "type": "object", "required": ["port"] }"#;
+
+/// Run `jvl config print` in `dir`, returning the printed config and stderr.
+fn config_print(dir: &Path) -> (serde_json::Value, String) {
+    let output = jvl()
+        .args(["config", "print"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run jvl");
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    assert_eq!(output.status.code(), Some(0), "stderr: {stderr}");
+    let json = serde_json::from_slice(&output.stdout).expect("invalid JSON");
+    (json, stderr)
+}
+
+/// Run `jvl check` in `dir` and return the exit code.
+fn check(dir: &Path) -> Option<i32> {
+    jvl()
+        .args(["check"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run jvl")
+        .status
+        .code()
+}
+
+/// Project with a port schema and an invalid `app.json`; the config comes
+/// from each test.
+fn setup_project() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "port.schema.json", PORT_SCHEMA);
+    write(dir.path(), "app.json", "{}");
+    dir
+}
+
+#[test]
+fn jvl_jsonc_with_comments() {
+    let dir = setup_project();
+    write(
+        dir.path(),
+        "jvl.jsonc",
+        r#"{
+  // Only the app config.
+  "files": ["app.json"],
+  "schemas": [{ "path": "port.schema.json", "files": ["app.json"] }],
+}"#,
+    );
+    assert_eq!(check(dir.path()), Some(1));
+    let (_, stderr) = config_print(dir.path());
+    assert!(stderr.contains("jvl.jsonc"), "stderr: {stderr}");
+}
+
+#[test]
+fn jvl_yaml() {
+    let dir = setup_project();
+    write(
+        dir.path(),
+        "jvl.yaml",
+        "# Only the app config.
+files: [app.json]
+schemas:
+  - path: port.schema.json
+    files: [app.json]
+",
+    );
+    assert_eq!(check(dir.path()), Some(1));
+    let (json, stderr) = config_print(dir.path());
+    assert_eq!(json["files"], serde_json::json!(["app.json"]));
+    assert!(stderr.contains("jvl.yaml"), "stderr: {stderr}");
+}
+
+/// A `.yml` config, and a YAML file it extends, are read as YAML too.
+#[test]
+fn jvl_yml_extending_yaml() {
+    let dir = setup_project();
+    write(dir.path(), "base.yaml", "strict: true\n");
+    write(
+        dir.path(),
+        "jvl.yml",
+        "extends: ./base.yaml\nfiles: [app.json]\n",
+    );
+    let (json, _) = config_print(dir.path());
+    assert_eq!(json["strict"], true);
+    assert_eq!(json["files"], serde_json::json!(["app.json"]));
+}
+
+#[test]
+fn package_json_jvl_key() {
+    let dir = setup_project();
+    write(
+        dir.path(),
+        "package.json",
+        r#"{
+  "name": "app",
+  "jvl": {
+    "files": ["app.json"],
+    "schemas": [{ "path": "port.schema.json", "files": ["app.json"] }]
+  }
+}"#,
+    );
+    assert_eq!(check(dir.path()), Some(1));
+    let (json, stderr) = config_print(dir.path());
+    assert_eq!(json["files"], serde_json::json!(["app.json"]));
+    assert!(
+        stderr.contains("package.json \"jvl\" key"),
+        "stderr: {stderr}"
+    );
+}
+
+/// A `package.json` without a `"jvl"` key is not a config: discovery keeps
+/// walking up.
+#[test]
+fn package_json_without_jvl_key_is_ignored() {
+    let dir = setup_project();
+    write(
+        dir.path(),
+        ".jvl.json",
+        r#"{ "files": ["**/app.json"], "schemas": [{ "path": "port.schema.json", "files": ["**/app.json"] }] }"#,
+    );
+    write(dir.path(), "pkg/package.json", r#"{ "name": "pkg" }"#);
+    write(dir.path(), "pkg/app.json", "{}");
+
+    let (_, stderr) = config_print(&dir.path().join("pkg"));
+    assert!(stderr.contains(".jvl.json"), "stderr: {stderr}");
+    assert_eq!(check(&dir.path().join("pkg")), Some(1));
+}
+
+#[test]
+fn cargo_toml_metadata() {
+    let dir = setup_project();
+    write(
+        dir.path(),
+        "Cargo.toml",
+        r#"[package]
+name = "app"
+
+[package.metadata.jvl]
+files = ["app.json"]
+schemas = [{ path = "port.schema.json", files = ["app.json"] }]
+"#,
+    );
+    assert_eq!(check(dir.path()), Some(1));
+    let (_, stderr) = config_print(dir.path());
+    assert!(
+        stderr.contains("Cargo.toml [package.metadata.jvl]"),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn cargo_toml_workspace_metadata() {
+    let dir = setup_project();
+    write(
+        dir.path(),
+        "Cargo.toml",
+        r#"[workspace]
+members = []
+
+[workspace.metadata.jvl]
+strict = true
+"#,
+    );
+    let (json, stderr) = config_print(dir.path());
+    assert_eq!(json["strict"], true);
+    assert!(
+        stderr.contains("[workspace.metadata.jvl]"),
+        "stderr: {stderr}"
+    );
+}
+
+/// Within a directory, `jvl.json` beats the other sources, and
+/// `config print` lists the ones it shadows.
+#[test]
+fn precedence_within_directory() {
+    let dir = setup_project();
+    write(dir.path(), "jvl.json", r#"{ "strict": true }"#);
+    write(dir.path(), "jvl.jsonc", r#"{ "strict": false }"#);
+    write(dir.path(), "package.json", r#"{ "jvl": {} }"#);
+
+    let (json, stderr) = config_print(dir.path());
+    assert_eq!(json["strict"], true);
+    let lines: Vec<&str> = stderr.lines().collect();
+    assert_eq!(lines.len(), 3, "stderr: {stderr}");
+    assert!(lines[0].starts_with("config: ") && lines[0].ends_with("jvl.json"));
+    assert!(lines[1].starts_with("shadowed: ") && lines[1].ends_with("jvl.jsonc"));
+    assert!(lines[2].starts_with("shadowed: ") && lines[2].ends_with("package.json"));
+}
+
+/// An explicit `--config` pointing at a file without jvl settings is an error.
+#[test]
+fn explicit_package_json_without_section() {
+    let dir = setup_project();
+    write(dir.path(), "package.json", r#"{ "name": "app" }"#);
+    let output = jvl()
+        .args(["check", "--config", "package.json"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run jvl");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("no jvl settings"), "stderr: {stderr}");
+}
@@ -0,0 +1,205 @@
+mod common;
+
+use common::jvl;
+
+/// `payload` holds a JSON object as a string, `blob` the same as base64.
+const SCHEMA: &str = r##"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "type": "object",
+  "properties": {
+    "payload": {
+      "type": "string",
+      "contentMediaType": "application/json",
+      "contentSchema": { "$ref": "#/$defs/payload" }
+    },
+    "blob": {
+      "type": "string",
+      "contentEncoding": "base64",
+      "contentMediaType": "application/json",
+      "contentSchema": { "$ref": "#/$defs/payload" }
+    }
+  },
+  "$defs": {
+    "payload": {
+      "type": "object",
+      "properties": { "a": { "type": "integer" } },
+      "required": ["a"]
+    }
+  }
+}"##;
+
+/// Helper: a temp project with `data.json` checked against [`SCHEMA`].
+fn setup_project(config_extra: &str, data: &str) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("jvl.json"),
+        format!(
+            r#"{{
+  "files": ["data.json"],
+  "schemas": [{{ "path": "schema.json", "files": ["data.json"] }}]{config_extra}
+}}"#
+        ),
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("schema.json"), SCHEMA).unwrap();
+    std::fs::write(dir.path().join("data.json"), data).unwrap();
+    dir
+}
+
+fn check(dir: &tempfile::TempDir, extra_args: &[&str]) -> (Option<i32>, serde_json::Value) {
+    let output = jvl()
+        .args(["check", "--format", "json"])
+        .args(extra_args)
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run jvl");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json = serde_json::from_str(&stdout).unwrap_or_else(|e| {
+        panic!(
+            "invalid JSON: {e}\nstdout: {stdout}\nstderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )
+    });
+    (output.status.code(), json)
+}
+
+/// `(code, source text under the span)` for each error.
+fn errors(json: &serde_json::Value, data: &str) -> Vec<(String, String)> {
+    json["files"][0]["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| {
+            let offset = e["location"]["offset"].as_u64().unwrap() as usize;
+            let length = e["location"]["length"].as_u64().unwrap() as usize;
+            (
+                e["code"].as_str().unwrap().to_string(),
+                data[offset..offset + length].to_string(),
+            )
+        })
+        .collect()
+}
+
+const INVALID_PAYLOAD: &str = r#"{ "payload": "{\"a\": \"one\"}" }"#;
+
+#[test]
+fn content_is_ignored_by_default() {
+    let dir = setup_project("", INVALID_PAYLOAD);
+    let (code, json) = check(&dir, &[]);
+    assert_eq!(code, Some(0), "{json}");
+}
+
+#[test]
+fn content_errors_point_into_the_escaped_string() {
+    let dir = setup_project("", INVALID_PAYLOAD);
+    let (code, json) = check(&dir, &["--validate-content"]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(
+        errors(&json, INVALID_PAYLOAD),
+        [("schema(type)".to_string(), r#"\"one\""#.to_string())]
+    );
+    assert_eq!(
+        json["files"][0]["errors"][0]["schema_path"],
+        "/$defs/payload/properties/a/type"
+    );
+}
+
+#[test]
+fn config_enables_content_validation() {
+    let data = r#"{ "payload": "{\"a\": 1,}" }"#;
+    let dir = setup_project(r#", "validateContent": true"#, data);
+    let (code, json) = check(&dir, &[]);
+    assert_eq!(code, Some(1), "{json}");
+    let errors = errors(&json, data);
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert_eq!(errors[0].0, "content(parse)");
+}
+
+#[test]
+fn base64_content_is_decoded() {
+    // {"b": 1}
+    let data = r#"{ "blob": "eyJiIjogMX0=", "payload": "{\"a\": 1}" }"#;
+    let dir = setup_project("", data);
+    let (code, json) = check(&dir, &["--validate-content"]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(
+        errors(&json, data),
+        [(
+            "schema(required)".to_string(),
+            r#""eyJiIjogMX0=""#.to_string()
+        )]
+    );
+
+    let data = r#"{ "blob": "not base64!" }"#;
+    let dir = setup_project("", data);
+    let (code, json) = check(&dir, &["--validate-content"]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(errors(&json, data)[0].0, "content(encoding)");
+}
+
+#[test]
+fn content_schema_refs_resolve_against_the_schema_id() {
+    let schema = r#"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "$id": "https://schemas.invalid/root.json",
+  "properties": {
+    "payload": {
+      "type": "string",
+      "contentMediaType": "application/json",
+      "contentSchema": { "$ref": "payload.json" }
+    }
+  }
+}"#;
+    let dir = setup_project(
+        r#", "urlRewrites": [{ "prefix": "https://schemas.invalid/", "path": "vendor" }]"#,
+        INVALID_PAYLOAD,
+    );
+    std::fs::write(dir.path().join("schema.json"), schema).unwrap();
+    std::fs::create_dir(dir.path().join("vendor")).unwrap();
+    std::fs::write(
+        dir.path().join("vendor/payload.json"),
+        r#"{ "properties": { "a": { "type": "integer" } } }"#,
+    )
+    .unwrap();
+
+    let (code, json) = check(&dir, &["--validate-content"]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(
+        errors(&json, INVALID_PAYLOAD),
+        [("schema(type)".to_string(), r#"\"one\""#.to_string())]
+    );
+}
+
+#[test]
+fn content_schema_in_a_referenced_document_is_applied() {
+    let schema = r#"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "$id": "https://schemas.invalid/root.json",
+  "properties": { "payload": { "$ref": "defs.json#/$defs/payload" } }
+}"#;
+    let defs = r##"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "$defs": {
+    "payload": {
+      "type": "string",
+      "contentMediaType": "application/json",
+      "contentSchema": { "$ref": "#/$defs/inner" }
+    },
+    "inner": { "properties": { "a": { "type": "integer" } } }
+  }
+}"##;
+    let dir = setup_project(
+        r#", "urlRewrites": [{ "prefix": "https://schemas.invalid/", "path": "vendor" }]"#,
+        INVALID_PAYLOAD,
+    );
+    std::fs::write(dir.path().join("schema.json"), schema).unwrap();
+    std::fs::create_dir(dir.path().join("vendor")).unwrap();
+    std::fs::write(dir.path().join("vendor/defs.json"), defs).unwrap();
+
+    let (code, json) = check(&dir, &["--validate-content"]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(
+        errors(&json, INVALID_PAYLOAD),
+        [("schema(type)".to_string(), r#"\"one\""#.to_string())]
+    );
+}
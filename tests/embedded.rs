@@ -0,0 +1,215 @@
+mod common;
+
+use common::{check_json, write};
+
+/// Requires `{ port: number }`.
+const PORT_SCHEMA: &str = r#"{
+  "type": "object",
+  "properties": { "port": { "type": "number" } },
+  "required": ["port"]
+}"#;
+/// Requires `{ title: string }`.
+const POST_SCHEMA: &str = r#"{ "type": "object", "required": ["title"] }"#;
+
+/// `(code, line, column)` of each error of the only checked file.
+fn error_positions(json: &serde_json::Value) -> Vec<(String, u64, u64)> {
+    json["files"][0]["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| {
+            (
+                e["code"].as_str().unwrap().to_string(),
+                e["location"]["line"].as_u64().unwrap(),
+                e["location"]["column"].as_u64().unwrap(),
+            )
+        })
+        .collect()
+}
+
+const GUIDE: &str = r#"# Guide
+
+A valid example:
+
+```json schema=./port.schema.json
+{ "port": 8080 }
+```
+
+Not checked, since it has no schema:
+
+```json
+{ "port": "anything", ... }
+```
+
+An outdated example:
+
+  ```jsonc schema=./port.schema.json
+  {
+    // The port to listen on.
+    "port": "8080"
+  }
+  ```
+"#;
+
+#[test]
+fn code_block_errors_point_into_the_markdown_file() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "port.schema.json", PORT_SCHEMA);
+    write(dir.path(), "guide.md", GUIDE);
+
+    let (code, json) = check_json(dir.path(), &["guide.md"]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(
+        error_positions(&json),
+        [("schema(type)".to_string(), 20, 13)]
+    );
+}
+
+#[test]
+fn parse_errors_and_missing_schemas_point_at_the_block() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "doc.md",
+        "Intro\n\n```json schema=./missing.json\n{}\n```\n\n```json schema=./port.schema.json\n{ \"port\": }\n```\n",
+    );
+    write(dir.path(), "port.schema.json", PORT_SCHEMA);
+
+    let (code, json) = check_json(dir.path(), &["doc.md"]);
+    assert_eq!(code, Some(2), "{json}");
+    assert_eq!(
+        error_positions(&json),
+        [
+            ("schema(load)".to_string(), 3, 1),
+            ("parse(syntax)".to_string(), 8, 11),
+        ]
+    );
+}
+
+#[test]
+fn front_matter_uses_config_mapping() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "jvl.json",
+        r#"{
+          "files": ["content/**/*.md"],
+          "schemas": [{ "files": ["content/**/*.md"], "path": "post.schema.json" }]
+        }"#,
+    );
+    write(dir.path(), "post.schema.json", POST_SCHEMA);
+    write(
+        dir.path(),
+        "content/ok.md",
+        "---json\n{ \"title\": \"Hello\" }\n---\n\nBody.\n",
+    );
+    write(
+        dir.path(),
+        "content/hugo.md",
+        "{\n  \"draft\": true\n}\n\n# No title\n",
+    );
+    write(dir.path(), "content/plain.md", "No front matter.\n");
+
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(json["summary"]["valid_files"], 1);
+    assert_eq!(json["summary"]["invalid_files"], 1);
+    assert_eq!(json["summary"]["skipped_files"], 1);
+    let invalid = json["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|f| f["valid"] == false)
+        .unwrap();
+    assert!(invalid["path"].as_str().unwrap().ends_with("hugo.md"));
+    assert_eq!(invalid["errors"][0]["code"], "schema(required)");
+    assert_eq!(invalid["errors"][0]["location"]["line"], 1);
+}
+
+#[test]
+fn yaml_front_matter_errors_point_into_the_yaml() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "post.schema.json",
+        r#"{
+          "type": "object",
+          "properties": {
+            "title": { "type": "string" },
+            "tags": { "type": "array", "items": { "type": "string" } }
+          },
+          "required": ["title"]
+        }"#,
+    );
+    write(
+        dir.path(),
+        "ok.md",
+        "---\ntitle: Hello\ntags: [a, b]\n---\n\nBody.\n",
+    );
+    write(
+        dir.path(),
+        "bad.md",
+        "---\ntitle: 42\ntags:\n  - a\n  - 7\n---\n\nBody.\n",
+    );
+    write(dir.path(), "broken.md", "---\ntitle: [x\n---\n");
+
+    let (code, json) = check_json(dir.path(), &["--schema", "post.schema.json", "ok.md"]);
+    assert_eq!(code, Some(0), "{json}");
+    assert_eq!(json["summary"]["valid_files"], 1);
+
+    let (code, json) = check_json(dir.path(), &["--schema", "post.schema.json", "bad.md"]);
+    assert_eq!(code, Some(1), "{json}");
+    let mut positions = error_positions(&json);
+    positions.sort();
+    assert_eq!(
+        positions,
+        [
+            ("schema(type)".to_string(), 2, 8),
+            ("schema(type)".to_string(), 5, 5),
+        ]
+    );
+
+    let (code, json) = check_json(dir.path(), &["--schema", "post.schema.json", "broken.md"]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(error_positions(&json)[0].0, "parse(syntax)");
+}
+
+#[test]
+fn code_blocks_in_block_quotes_and_list_items_are_checked() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "port.schema.json", PORT_SCHEMA);
+    write(
+        dir.path(),
+        "doc.md",
+        "> Note:\n>\n> ```json schema=./port.schema.json\n> {\n>   \"port\": \"80\"\n> }\n> ```\n\n\
+         1. Step\n\n   ```json schema=./port.schema.json\n   { \"port\": true }\n   ```\n",
+    );
+
+    let (code, json) = check_json(dir.path(), &["doc.md"]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(
+        error_positions(&json),
+        [
+            ("schema(type)".to_string(), 5, 13),
+            ("schema(type)".to_string(), 12, 14),
+        ]
+    );
+}
+
+#[test]
+fn strict_requires_a_schema_for_front_matter() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "post.md",
+        "---json\n{ \"title\": \"Hello\" }\n---\n\n```json\n{}\n```\n",
+    );
+
+    let (code, json) = check_json(dir.path(), &["post.md"]);
+    assert_eq!(code, Some(0), "{json}");
+    assert_eq!(json["summary"]["skipped_files"], 1);
+
+    let (code, json) = check_json(dir.path(), &["--strict", "post.md"]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(error_positions(&json), [("no-schema".to_string(), 1, 1)]);
+}
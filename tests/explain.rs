@@ -0,0 +1,199 @@
+mod common;
+
+use std::path::Path;
+
+use common::jvl;
+
+const SCHEMA: &str = r##"{
+  "title": "App config",
+  "type": "object",
+  "properties": { "server": { "$ref": "#/$defs/server" } },
+  "$defs": {
+    "server": {
+      "description": "Server settings",
+      "properties": {
+        "port": {
+          "title": "Port",
+          "oneOf": [
+            { "title": "Number", "type": "integer" },
+            { "title": "Named", "type": "string", "enum": ["http", "https"] }
+          ]
+        }
+      }
+    }
+  }
+}"##;
+
+/// Project layout:
+///   project/
+///     jvl.json          (maps mapped/*.json to schema.json)
+///     schema.json
+///     app.json          ($schema: ./schema.json, invalid port)
+///     mapped/a.json     (valid port)
+fn setup_project() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::write(root.join("schema.json"), SCHEMA).unwrap();
+    std::fs::write(
+        root.join("jvl.json"),
+        r#"{ "schemas": [{ "path": "schema.json", "files": ["mapped/*.json"] }] }"#,
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("app.json"),
+        r#"{ "$schema": "./schema.json", "server": { "port": "ftp" } }"#,
+    )
+    .unwrap();
+    std::fs::create_dir(root.join("mapped")).unwrap();
+    std::fs::write(
+        root.join("mapped/a.json"),
+        r#"{ "server": { "port": "http" } }"#,
+    )
+    .unwrap();
+    dir
+}
+
+/// Run `jvl explain` in `dir`, expecting it to exit with `code`.
+fn explain(dir: &Path, args: &[&str], code: i32) -> String {
+    let output = jvl()
+        .arg("explain")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run jvl");
+    assert_eq!(
+        output.status.code(),
+        Some(code),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let root = std::fs::canonicalize(dir).unwrap();
+    String::from_utf8_lossy(&output.stdout).replace(&root.display().to_string(), "[DIR]")
+}
+
+#[test]
+fn explain_schema_field_and_chain() {
+    let dir = setup_project();
+    insta::assert_snapshot!(explain(dir.path(), &["app.json", "/server/port"], 1), @r#"
+    file: app.json
+    schema: [DIR]/schema.json
+      chosen by: $schema field
+      cache: local file, not cached
+    result: 1 error
+      1:51 schema(enum): "ftp" is not one of "http" or "https"
+
+    (root) -> #
+      title: App config
+    /server -> #/$defs/server
+      $ref: #/$defs/server
+      description: Server settings
+    /server/port -> #/$defs/server/properties/port
+      title: Port
+      oneOf[0] #/$defs/server/properties/port/oneOf/0 (Number): invalid
+      oneOf[1] #/$defs/server/properties/port/oneOf/1 (Named): invalid
+    "#);
+}
+
+#[test]
+fn explain_config_mapping_selects_one_of_branch() {
+    let dir = setup_project();
+    insta::assert_snapshot!(explain(dir.path(), &["mapped/a.json", "#/server/port"], 0), @r#"
+    file: mapped/a.json
+    schema: [DIR]/schema.json
+      chosen by: schemas[0] matching "mapped/*.json" in [DIR]/jvl.json
+      cache: local file, not cached
+    result: valid
+
+    (root) -> #
+      title: App config
+    /server -> #/$defs/server
+      $ref: #/$defs/server
+      description: Server settings
+    /server/port -> #/$defs/server/properties/port
+      title: Port
+      oneOf[0] #/$defs/server/properties/port/oneOf/0 (Number): invalid
+      oneOf[1] #/$defs/server/properties/port/oneOf/1 (Named): valid, selected
+    "#);
+}
+
+#[test]
+fn explain_flag_and_unresolved_pointer() {
+    let dir = setup_project();
+    insta::assert_snapshot!(explain(dir.path(), &["mapped/a.json", "/nope", "--schema", "schema.json"], 0), @r#"
+    file: mapped/a.json
+    schema: [DIR]/schema.json
+      chosen by: --schema flag
+      cache: local file, not cached
+    result: valid
+
+    (root) -> #
+      title: App config
+    /nope
+      no subschema found
+    "#);
+}
+
+#[test]
+fn explain_without_schema() {
+    let dir = setup_project();
+    std::fs::write(dir.path().join("plain.json"), "{}").unwrap();
+    insta::assert_snapshot!(explain(dir.path(), &["plain.json"], 0), @r"
+    file: plain.json
+    schema: none
+      no --schema flag, $schema field, or matching config mapping
+    ");
+}
+
+#[test]
+fn explain_parses_with_the_configured_dialect() {
+    let dir = setup_project();
+    std::fs::write(
+        dir.path().join("jvl.json"),
+        r#"{ "parsers": [{ "files": ["*.json5"], "parser": "json5" }] }"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("app.json5"),
+        "{ '$schema': './schema.json', server: { port: 'http' } }",
+    )
+    .unwrap();
+    insta::assert_snapshot!(explain(dir.path(), &["app.json5", "/server/port"], 0), @r#"
+    file: app.json5
+    schema: [DIR]/schema.json
+      chosen by: $schema field
+      cache: local file, not cached
+    result: valid
+
+    (root) -> #
+      title: App config
+    /server -> #/$defs/server
+      $ref: #/$defs/server
+      description: Server settings
+    /server/port -> #/$defs/server/properties/port
+      title: Port
+      oneOf[0] #/$defs/server/properties/port/oneOf/0 (Number): invalid
+      oneOf[1] #/$defs/server/properties/port/oneOf/1 (Named): valid, selected
+    "#);
+}
+
+#[test]
+fn explain_fails_on_parse_and_schema_errors() {
+    let dir = setup_project();
+    std::fs::write(dir.path().join("broken.json"), "{ \"a\": ").unwrap();
+    insta::assert_snapshot!(explain(dir.path(), &["broken.json", "--schema", "schema.json"], 1), @r"
+    file: broken.json
+    schema: [DIR]/schema.json
+      chosen by: --schema flag
+      cache: local file, not cached
+    result: 1 error
+      1:8 parse(syntax): Expected value after colon in object property on line 1 column 8
+    ");
+    insta::assert_snapshot!(explain(dir.path(), &["app.json", "--schema", "missing.json"], 2), @r"
+    file: app.json
+    schema: [DIR]/missing.json
+      chosen by: --schema flag
+      cache: local file, not cached
+    result: 1 error
+      schema(load): Failed to read schema file '[DIR]/missing.json': No such file or directory (os error 2)
+    ");
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use common::jvl;
+
+/// Helper: set up a temp project validating data.json against schema.json,
+/// with the given `keywords` enabled in jvl.json.
+fn setup_project(keywords: &str, schema: &str, data: &str) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+
+    std::fs::write(
+        dir.path().join("jvl.json"),
+        format!(
+            r#"{{
+  "files": ["data.json"],
+  "schemas": [{{ "path": "schema.json", "files": ["data.json"] }}],
+  "keywords": {keywords}
+}}"#
+        ),
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("schema.json"), schema).unwrap();
+    std::fs::write(dir.path().join("data.json"), data).unwrap();
+
+    dir
+}
+
+fn check(dir: &tempfile::TempDir) -> (Option<i32>, serde_json::Value) {
+    let output = jvl()
+        .args(["check", "--format", "json"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run jvl");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json = serde_json::from_str(&stdout).unwrap_or_else(|e| {
+        panic!(
+            "invalid JSON: {e}\nstdout: {stdout}\nstderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )
+    });
+    (output.status.code(), json)
+}
+
+const SERVICES_SCHEMA: &str = r#"{
+  "type": "object",
+  "properties": {
+    "services": {
+      "type": "array",
+      "x-unique-by": "name",
+      "items": {
+        "type": "object",
+        "properties": { "legacy": { "x-deprecated": "use 'mode' instead" } }
+      }
+    }
+  }
+}"#;
+
+const SERVICES_DATA: &str = r#"{
+  "services": [
+    { "name": "api" },
+    { "name": "web", "legacy": true },
+    { "name": "api" }
+  ]
+}"#;
+
+#[test]
+fn unique_by_reports_duplicate_items() {
+    let dir = setup_project(r#"["x-unique-by"]"#, SERVICES_SCHEMA, SERVICES_DATA);
+
+    let (code, json) = check(&dir);
+    assert_eq!(code, Some(1));
+    let errors = json["files"][0]["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 1, "errors: {errors:#?}");
+    assert_eq!(errors[0]["code"], "schema(x-unique-by)");
+    assert_eq!(
+        errors[0]["message"],
+        r#"items at index 0 and 2 have the same 'name' ("api")"#
+    );
+    assert_eq!(errors[0]["location"]["line"], 2);
+}
+
+/// Deprecations are warnings, so a file whose only finding is one passes.
+#[test]
+fn deprecated_reports_usage() {
+    let dir = setup_project(r#"["x-deprecated"]"#, SERVICES_SCHEMA, SERVICES_DATA);
+
+    let (code, json) = check(&dir);
+    assert_eq!(code, Some(0), "{json:#}");
+    let errors = json["files"][0]["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 1, "errors: {errors:#?}");
+    assert_eq!(errors[0]["code"], "schema(x-deprecated)");
+    assert_eq!(errors[0]["severity"], "warning");
+    assert_eq!(
+        errors[0]["message"],
+        "value is deprecated: use 'mode' instead"
+    );
+    assert_eq!(errors[0]["location"]["line"], 4);
+}
+
+/// A `severity` override turns deprecations into errors.
+#[test]
+fn deprecated_severity_can_be_raised() {
+    let dir = setup_project(r#"["x-deprecated"]"#, SERVICES_SCHEMA, SERVICES_DATA);
+    let config = std::fs::read_to_string(dir.path().join("jvl.json")).unwrap();
+    std::fs::write(
+        dir.path().join("jvl.json"),
+        config.replacen(
+            '{',
+            r#"{ "severity": { "schema(x-deprecated)": "error" },"#,
+            1,
+        ),
+    )
+    .unwrap();
+
+    let (code, json) = check(&dir);
+    assert_eq!(code, Some(1), "{json:#}");
+    assert_eq!(json["files"][0]["errors"][0]["severity"], "error");
+}
+
+/// Keywords not enabled in the config are ignored like any unknown keyword.
+#[test]
+fn keywords_are_opt_in() {
+    let dir = setup_project("[]", SERVICES_SCHEMA, SERVICES_DATA);
+
+    let (code, _) = check(&dir);
+    assert_eq!(code, Some(0));
+}
+
+#[test]
+fn invalid_keyword_value_is_compile_error() {
+    let dir = setup_project(r#"["x-unique-by"]"#, r#"{ "x-unique-by": 5 }"#, "[]");
+
+    let (code, json) = check(&dir);
+    assert_eq!(code, Some(2));
+    assert_eq!(json["files"][0]["errors"][0]["code"], "schema(compile)");
+}
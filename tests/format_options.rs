@@ -0,0 +1,221 @@
+mod common;
+
+use common::jvl;
+
+/// Helper: set up a temp project with a schema that has no `$schema` keyword:
+///   project/
+///     jvl.json            (maps data.json to schema.json)
+///     schema.json
+///     data.json
+fn setup_project(config_extra: &str, schema: &str, data: &str) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+
+    std::fs::write(
+        dir.path().join("jvl.json"),
+        format!(
+            r#"{{
+  "files": ["data.json"],
+  "schemas": [{{ "path": "schema.json", "files": ["data.json"] }}]{config_extra}
+}}"#
+        ),
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("schema.json"), schema).unwrap();
+    std::fs::write(dir.path().join("data.json"), data).unwrap();
+
+    dir
+}
+
+const VERSION_SCHEMA: &str = r#"{
+  "type": "object",
+  "properties": {
+    "version": { "type": "string", "format": "semver" },
+    "timeout": { "type": "string", "format": "duration" }
+  }
+}"#;
+
+fn check(dir: &tempfile::TempDir, extra_args: &[&str]) -> (Option<i32>, serde_json::Value) {
+    let output = jvl()
+        .args(["check", "--format", "json"])
+        .args(extra_args)
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run jvl");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json = serde_json::from_str(&stdout).unwrap_or_else(|e| {
+        panic!(
+            "invalid JSON: {e}\nstdout: {stdout}\nstderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )
+    });
+    (output.status.code(), json)
+}
+
+fn error_codes(json: &serde_json::Value) -> Vec<String> {
+    json["files"][0]["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["code"].as_str().unwrap().to_string())
+        .collect()
+}
+
+/// 2020-12 treats `format` as an annotation by default.
+#[test]
+fn formats_not_validated_by_default() {
+    let dir = setup_project(
+        "",
+        VERSION_SCHEMA,
+        r#"{ "version": "1.2", "timeout": "soon" }"#,
+    );
+
+    let (code, _) = check(&dir, &[]);
+    assert_eq!(code, Some(0));
+}
+
+#[test]
+fn validate_formats_config_checks_extra_formats() {
+    let dir = setup_project(
+        r#",
+  "validateFormats": true"#,
+        VERSION_SCHEMA,
+        r#"{ "version": "1.2", "timeout": "soon" }"#,
+    );
+
+    let (code, json) = check(&dir, &[]);
+    assert_eq!(code, Some(1));
+    assert_eq!(error_codes(&json), ["schema(format)", "schema(format)"]);
+
+    std::fs::write(
+        dir.path().join("data.json"),
+        r#"{ "version": "1.2.3-rc.1", "timeout": "PT30S" }"#,
+    )
+    .unwrap();
+    let (code, _) = check(&dir, &[]);
+    assert_eq!(code, Some(0));
+}
+
+#[test]
+fn validate_formats_cli_flags_override_config() {
+    let dir = setup_project(
+        r#",
+  "validateFormats": true"#,
+        VERSION_SCHEMA,
+        r#"{ "version": "1.2" }"#,
+    );
+
+    let (code, _) = check(&dir, &["--no-validate-formats"]);
+    assert_eq!(code, Some(0));
+
+    let dir = setup_project("", VERSION_SCHEMA, r#"{ "version": "1.2" }"#);
+    let (code, json) = check(&dir, &["--validate-formats"]);
+    assert_eq!(code, Some(1));
+    assert_eq!(error_codes(&json), ["schema(format)"]);
+}
+
+/// Draft-04 style boolean `exclusiveMaximum` only compiles under draft-04.
+#[test]
+fn default_draft_applies_to_schemas_without_dollar_schema() {
+    let schema = r#"{ "maximum": 10, "exclusiveMaximum": true }"#;
+
+    let dir = setup_project("", schema, "10");
+    let (code, json) = check(&dir, &[]);
+    assert_eq!(code, Some(2));
+    assert_eq!(error_codes(&json), ["schema(compile)"]);
+
+    let (code, json) = check(&dir, &["--default-draft", "draft-04"]);
+    assert_eq!(code, Some(1));
+    assert_eq!(error_codes(&json), ["schema(exclusiveMaximum)"]);
+
+    let dir = setup_project(
+        r#",
+  "defaultDraft": "draft-04""#,
+        schema,
+        "9",
+    );
+    let (code, _) = check(&dir, &[]);
+    assert_eq!(code, Some(0));
+}
+
+/// An explicit `$schema` in the schema wins over the configured default.
+#[test]
+fn default_draft_does_not_override_dollar_schema() {
+    let schema = r#"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "maximum": 10,
+  "exclusiveMaximum": true
+}"#;
+    let dir = setup_project(
+        r#",
+  "defaultDraft": "draft-04""#,
+        schema,
+        "10",
+    );
+
+    let (code, json) = check(&dir, &[]);
+    assert_eq!(code, Some(2));
+    assert_eq!(error_codes(&json), ["schema(compile)"]);
+}
+
+#[test]
+fn unknown_formats_ignored_unless_denied() {
+    let schema = r#"{ "type": "string", "format": "not-a-format" }"#;
+    let dir = setup_project(
+        r#",
+  "validateFormats": true"#,
+        schema,
+        r#""x""#,
+    );
+
+    let (code, _) = check(&dir, &[]);
+    assert_eq!(code, Some(0));
+
+    let (code, json) = check(&dir, &["--deny-unknown-formats"]);
+    assert_eq!(code, Some(2));
+    assert_eq!(error_codes(&json), ["schema(compile)"]);
+}
+
+#[test]
+fn custom_regex_formats() {
+    let schema = r#"{
+  "type": "object",
+  "properties": { "name": { "type": "string", "format": "k8s-name" } }
+}"#;
+    let dir = setup_project(
+        r#",
+  "validateFormats": true,
+  "formats": { "k8s-name": "^[a-z0-9]([-a-z0-9]*[a-z0-9])?$" }"#,
+        schema,
+        r#"{ "name": "My_Service" }"#,
+    );
+
+    let (code, json) = check(&dir, &[]);
+    assert_eq!(code, Some(1));
+    assert_eq!(error_codes(&json), ["schema(format)"]);
+
+    std::fs::write(dir.path().join("data.json"), r#"{ "name": "my-service" }"#).unwrap();
+    let (code, _) = check(&dir, &[]);
+    assert_eq!(code, Some(0));
+}
+
+#[test]
+fn invalid_custom_format_regex_is_config_error() {
+    let dir = setup_project(
+        r#",
+  "formats": { "broken": "([a-z" }"#,
+        VERSION_SCHEMA,
+        "{}",
+    );
+
+    let output = jvl()
+        .args(["check"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run jvl");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Invalid regex for custom format 'broken'"),
+        "stderr: {stderr}"
+    );
+}
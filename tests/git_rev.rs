@@ -0,0 +1,103 @@
+mod common;
+
+use std::path::Path;
+use std::process::Command;
+
+use common::{check_json, jvl, write};
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(["-c", "user.name=jvl", "-c", "user.email=jvl@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run git")
+        .status;
+    assert!(status.success(), "git {args:?} failed");
+}
+
+/// Paths of the files reported invalid.
+fn invalid(json: &serde_json::Value) -> Vec<&str> {
+    json["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|f| f["valid"] == false)
+        .map(|f| f["path"].as_str().unwrap())
+        .collect()
+}
+
+/// A repo whose first commit has valid files and whose second breaks
+/// `data/a.json` by tightening the schema. The worktree is then emptied, so
+/// only the object database has the files.
+fn setup() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    write(
+        root,
+        "jvl.json",
+        r#"{
+  "files": ["data/*.json"],
+  "schemas": [{ "files": ["data/a.json"], "path": "schema.json" }]
+}"#,
+    );
+    write(root, "schema.json", r#"{ "type": "object" }"#);
+    write(root, "other.json", r#"{ "type": "object" }"#);
+    write(root, "data/a.json", "{}");
+    write(root, "data/b.json", r#"{ "$schema": "../other.json" }"#);
+    git(root, &["init", "-q"]);
+    git(root, &["add", "."]);
+    git(root, &["commit", "-qm", "valid"]);
+    write(root, "schema.json", r#"{ "required": ["name"] }"#);
+    git(root, &["commit", "-qam", "invalid"]);
+    for file in [
+        "jvl.json",
+        "schema.json",
+        "other.json",
+        "data/a.json",
+        "data/b.json",
+    ] {
+        std::fs::remove_file(root.join(file)).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn checks_a_commit_without_a_worktree() {
+    let dir = setup();
+    let root = dir.path();
+
+    let (code, json) = check_json(root, &["--rev", "HEAD~1"]);
+    assert_eq!(code, Some(0), "{json}");
+    assert_eq!(json["summary"]["checked_files"], 2, "{json}");
+
+    let (code, json) = check_json(root, &["--rev", "HEAD"]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(invalid(&json), ["data/a.json"]);
+}
+
+#[test]
+fn paths_are_relative_to_the_tree() {
+    let dir = setup();
+    let root = dir.path();
+
+    let (code, json) = check_json(&root.join("data"), &["--rev", "HEAD", "a.json"]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(invalid(&json), ["data/a.json"]);
+}
+
+#[test]
+fn unknown_revision_is_a_tool_error() {
+    let dir = setup();
+    let output = jvl()
+        .args(["check", "--rev", "no-such-branch"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("'no-such-branch' is not a commit"),
+        "{stderr}"
+    );
+}
@@ -0,0 +1,171 @@
+mod common;
+
+use std::path::Path;
+use std::process::Command;
+
+use common::{check_json, jvl, write};
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(["-c", "user.name=jvl", "-c", "user.email=jvl@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run git")
+        .status;
+    assert!(status.success(), "git {args:?} failed");
+}
+
+/// Names of the reported files, sorted.
+fn reported(json: &serde_json::Value) -> Vec<String> {
+    let mut names: Vec<String> = json["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| {
+            let path = Path::new(f["path"].as_str().unwrap());
+            path.file_name().unwrap().to_string_lossy().into_owned()
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+/// A committed repo with `a.json` and `b.json` mapped to `schema.json` by
+/// config, and `c.json` declaring `other.json` as its `$schema`.
+fn setup() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    write(
+        root,
+        "jvl.json",
+        r#"{
+  "files": ["data/*.json"],
+  "schemas": [{ "files": ["data/a.json", "data/b.json"], "path": "schema.json" }]
+}"#,
+    );
+    write(root, "schema.json", r#"{ "type": "object" }"#);
+    write(root, "other.json", r#"{ "type": "object" }"#);
+    write(root, "data/a.json", "{}");
+    write(root, "data/b.json", "{}");
+    write(root, "data/c.json", r#"{ "$schema": "../other.json" }"#);
+    git(root, &["init", "-q"]);
+    git(root, &["add", "."]);
+    git(root, &["commit", "-qm", "init"]);
+    dir
+}
+
+#[test]
+fn changed_selects_modified_and_untracked_files() {
+    let dir = setup();
+    let root = dir.path();
+
+    let output = jvl()
+        .args(["check", "--changed"])
+        .current_dir(root)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("no files to check"), "{stderr}");
+
+    write(root, "data/a.json", r#"{ "a": 1 }"#);
+    write(root, "data/new.json", r#"{ "$schema": "../other.json" }"#);
+    write(root, "notes.json", "{}");
+    let (_, json) = check_json(root, &["--changed"]);
+    assert_eq!(reported(&json), ["a.json", "new.json"]);
+}
+
+#[test]
+fn staged_selects_only_the_index() {
+    let dir = setup();
+    let root = dir.path();
+    write(root, "data/a.json", r#"{ "a": 1 }"#);
+    write(root, "data/b.json", r#"{ "b": 1 }"#);
+    git(root, &["add", "data/a.json"]);
+
+    let (_, json) = check_json(root, &["--staged"]);
+    assert_eq!(reported(&json), ["a.json"]);
+}
+
+#[test]
+fn changed_schemas_select_the_files_they_govern() {
+    let dir = setup();
+    let root = dir.path();
+
+    write(root, "schema.json", r#"{ "type": "array" }"#);
+    let (code, json) = check_json(root, &["--changed"]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(reported(&json), ["a.json", "b.json"]);
+
+    git(root, &["commit", "-qam", "schema"]);
+    write(root, "other.json", r#"{ "required": ["x"] }"#);
+    let (_, json) = check_json(root, &["--since", "HEAD~1"]);
+    assert_eq!(reported(&json), ["a.json", "b.json", "c.json"]);
+}
+
+#[test]
+fn outside_a_repository_is_a_tool_error() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "a.json", "{}");
+    let output = jvl()
+        .args(["check", "--changed"])
+        .env("GIT_CEILING_DIRECTORIES", dir.path().parent().unwrap())
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("git rev-parse failed"), "{stderr}");
+}
+
+#[test]
+fn staged_checks_the_staged_content() {
+    let dir = setup();
+    let root = dir.path();
+    write(
+        root,
+        "schema.json",
+        r#"{ "type": "object", "required": ["a"] }"#,
+    );
+    write(root, "data/a.json", r#"{ "b": 1 }"#);
+    git(root, &["add", "."]);
+    // Fixing the working tree copy doesn't fix what gets committed.
+    write(root, "data/a.json", r#"{ "a": 1 }"#);
+
+    let (code, json) = check_json(root, &["--staged"]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(reported(&json), ["a.json", "b.json"]);
+    assert_eq!(json["summary"]["invalid_files"], 2, "{json}");
+}
+
+#[test]
+fn changed_referenced_documents_select_their_dependents() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    write(
+        root,
+        "jvl.json",
+        r#"{
+  "files": ["data/*.json"],
+  "schemas": [{ "files": ["data/a.json"], "url": "https://schemas.invalid/root.json" }],
+  "urlRewrites": [{ "prefix": "https://schemas.invalid/", "path": "vendor" }]
+}"#,
+    );
+    write(
+        root,
+        "vendor/root.json",
+        r#"{ "$id": "https://schemas.invalid/root.json", "$ref": "defs.json" }"#,
+    );
+    write(root, "vendor/defs.json", r#"{ "type": "object" }"#);
+    write(root, "data/a.json", "{}");
+    write(root, "data/b.json", "{}");
+    git(root, &["init", "-q"]);
+    git(root, &["add", "."]);
+    git(root, &["commit", "-qm", "init"]);
+
+    write(root, "vendor/defs.json", r#"{ "type": "array" }"#);
+    let (code, json) = check_json(root, &["--changed"]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(reported(&json), ["a.json"]);
+}
@@ -102,6 +102,18 @@ fn enum_mismatch() {
     with_human_settings(|| insta::assert_snapshot!(stderr));
 }
 
+#[test]
+fn deprecated_warnings() {
+    let (stderr, code) = jvl_human(&[
+        "check",
+        "--schema",
+        &fixture("deprecated-schema.json"),
+        &fixture("deprecated-usage.json"),
+    ]);
+    assert_eq!(code, 0);
+    with_human_settings(|| insta::assert_snapshot!(stderr));
+}
+
 #[test]
 fn const_mismatch() {
     let (stderr, code) = jvl_human(&[
@@ -342,6 +354,18 @@ fn one_of_not_valid() {
     with_human_settings(|| insta::assert_snapshot!(stderr));
 }
 
+#[test]
+fn one_of_discriminator_picks_closest_branch() {
+    let (stderr, code) = jvl_human(&[
+        "check",
+        "--schema",
+        &fixture("oneOf-discriminator-schema.json"),
+        &fixture("oneOf-discriminator-invalid.json"),
+    ]);
+    assert_eq!(code, 1);
+    with_human_settings(|| insta::assert_snapshot!(stderr));
+}
+
 #[test]
 fn one_of_multiple_valid() {
     let (stderr, code) = jvl_human(&[
@@ -398,3 +422,96 @@ fn schema_network_error_points_at_schema_value() {
     assert_eq!(code, 2);
     with_human_settings(|| insta::assert_snapshot!(stderr));
 }
+
+// ── Compact format, quiet mode, and diagnostic cap ────────────────────
+
+#[test]
+fn compact_prints_one_line_per_diagnostic() {
+    let output = jvl()
+        .env("NO_COLOR", "1")
+        .args([
+            "check",
+            "--format",
+            "compact",
+            "--schema",
+            &fixture("simple-schema.json"),
+            &fixture("invalid-type.json"),
+            &fixture("missing-required.json"),
+        ])
+        .output()
+        .expect("failed to run jvl");
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    with_human_settings(|| {
+        insta::assert_snapshot!(stdout, @r#"
+        [fixtures]/invalid-type.json:1:29: error schema(type): "not-a-number" is not of type "number"
+        [fixtures]/missing-required.json:1:1: error schema(required): "name" is a required property
+        [fixtures]/missing-required.json:1:1: error schema(required): "port" is a required property
+        "#);
+    });
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Found 3 errors in 2 files"), "{stderr}");
+}
+
+#[test]
+fn quiet_prints_only_the_summary() {
+    let (stderr, code) = jvl_human(&[
+        "check",
+        "-q",
+        "--schema",
+        &fixture("simple-schema.json"),
+        &fixture("invalid-type.json"),
+    ]);
+    assert_eq!(code, 1);
+    with_human_settings(|| {
+        insta::assert_snapshot!(stderr, @r"
+        ✗ Found 1 error in 1 file
+          Checked 1 file ([duration])
+        ");
+    });
+
+    let (stderr, code) = jvl_human(&[
+        "check",
+        "--quiet",
+        "--schema",
+        &fixture("simple-schema.json"),
+        &fixture("valid.json"),
+    ]);
+    assert_eq!(code, 0);
+    assert_eq!(stderr, "");
+}
+
+#[test]
+fn max_diagnostics_counts_the_rest() {
+    let output = jvl()
+        .env("NO_COLOR", "1")
+        .args([
+            "check",
+            "--format",
+            "compact",
+            "--max-diagnostics",
+            "1",
+            "--schema",
+            &fixture("simple-schema.json"),
+            &fixture("invalid-type.json"),
+            &fixture("missing-required.json"),
+        ])
+        .output()
+        .expect("failed to run jvl");
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 1, "{stdout}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("… 2 more errors not shown"), "{stderr}");
+
+    let (stderr, _) = jvl_human(&[
+        "check",
+        "--max-diagnostics",
+        "0",
+        "--schema",
+        &fixture("simple-schema.json"),
+        &fixture("invalid-type.json"),
+    ]);
+    assert!(!stderr.contains("schema(type)"), "{stderr}");
+    assert!(stderr.contains("… 1 more error not shown"), "{stderr}");
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use std::path::Path;
+
+use common::{jvl, write};
+
+/// Project layout:
+///   project/
+///     a/app.json          ($schema: ../schemas/app.json)
+///     b/app.json          (no $schema)
+///     schemas/app.json
+///     tsconfig.json       (catalog match)
+///     data.json           (no schema)
+fn setup_project() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    write(
+        root,
+        "a/app.json",
+        r#"{ "$schema": "../schemas/app.json" }"#,
+    );
+    write(root, "b/app.json", "{}");
+    write(root, "schemas/app.json", r#"{ "type": "object" }"#);
+    write(root, "tsconfig.json", "{}");
+    write(root, "data.json", "{}");
+    dir
+}
+
+fn init(dir: &Path, args: &[&str]) -> std::process::Output {
+    jvl()
+        .arg("init")
+        .args(args)
+        .current_dir(dir)
+        .stdin(std::process::Stdio::null())
+        .output()
+        .expect("failed to run jvl")
+}
+
+#[test]
+fn init_yes_writes_suggested_mappings() {
+    let dir = setup_project();
+    let output = init(dir.path(), &["--yes"]);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let config = std::fs::read_to_string(dir.path().join("jvl.json")).unwrap();
+    insta::assert_snapshot!(config, @r#"
+    // jvl configuration, generated by `jvl init`.
+    // See https://github.com/sargunv/jvl#configuration for all options.
+    {
+      "$schema": "https://raw.githubusercontent.com/sargunv/jvl/main/config.schema.json",
+      // Files to validate. Prefix a pattern with "!" to exclude matches.
+      "files": ["**/*.json", "**/*.jsonc"],
+      // Schemas for files without a "$schema" field. The first match wins.
+      "schemas": [
+        // 1 of 2 `app.json` files declare this $schema (1 file without $schema)
+        { "files": ["**/app.json"], "path": "schemas/app.json" },
+        // TypeScript compiler configuration, from the schema catalog (1 file without $schema)
+        {
+          "files": ["**/tsconfig.json", "**/tsconfig.*.json"],
+          "url": "https://json.schemastore.org/tsconfig.json"
+        }
+      ]
+      // Schemas declared by files' own "$schema" fields:
+      //   schemas/app.json (1 file)
+      // No schema found for 1 file:
+      //   data.json
+    }
+    "#);
+
+    // The generated config is picked up by `jvl check`.
+    let output = jvl()
+        .args(["check", "--format", "json", "b/app.json"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run jvl");
+    assert_eq!(output.status.code(), Some(0));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["summary"]["checked_files"], 1);
+}
+
+#[test]
+fn init_refuses_to_replace_existing_config() {
+    let dir = setup_project();
+    write(dir.path(), "jvl.json", "{}");
+
+    let output = init(dir.path(), &["--yes"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--force"), "stderr: {stderr}");
+
+    let output = init(dir.path(), &["--yes", "--force"]);
+    assert_eq!(output.status.code(), Some(0));
+    let config = std::fs::read_to_string(dir.path().join("jvl.json")).unwrap();
+    assert!(config.contains("\"schemas\""), "config: {config}");
+}
+
+#[test]
+fn init_force_does_not_shadow_other_config_files() {
+    let dir = setup_project();
+    write(dir.path(), "package.json", r#"{ "jvl": {} }"#);
+
+    let output = init(dir.path(), &["--yes", "--force"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("package.json"), "stderr: {stderr}");
+    assert!(!dir.path().join("jvl.json").exists());
+}
+
+#[test]
+fn init_requires_yes_without_terminal() {
+    let dir = setup_project();
+    let output = init(dir.path(), &[]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--yes"), "stderr: {stderr}");
+    assert!(!dir.path().join("jvl.json").exists());
+}
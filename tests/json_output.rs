@@ -27,6 +27,7 @@ fn valid_file() {
         }
       ],
       "summary": {
+        "cached_files": 0,
         "checked_files": 1,
         "duration_ms": "[duration]",
         "errors": 0,
@@ -80,6 +81,7 @@ fn type_error() {
         }
       ],
       "summary": {
+        "cached_files": 0,
         "checked_files": 1,
         "duration_ms": "[duration]",
         "errors": 1,
@@ -145,6 +147,7 @@ fn missing_required() {
         }
       ],
       "summary": {
+        "cached_files": 0,
         "checked_files": 1,
         "duration_ms": "[duration]",
         "errors": 2,
@@ -207,6 +210,7 @@ fn syntax_error() {
         }
       ],
       "summary": {
+        "cached_files": 0,
         "checked_files": 1,
         "duration_ms": "[duration]",
         "errors": 1,
@@ -233,6 +237,7 @@ fn skipped_file() {
     {
       "files": [],
       "summary": {
+        "cached_files": 0,
         "checked_files": 0,
         "duration_ms": "[duration]",
         "errors": 0,
@@ -278,6 +283,7 @@ fn strict_no_schema() {
         }
       ],
       "summary": {
+        "cached_files": 0,
         "checked_files": 1,
         "duration_ms": "[duration]",
         "errors": 1,
@@ -324,6 +330,7 @@ fn tool_error() {
         }
       ],
       "summary": {
+        "cached_files": 0,
         "checked_files": 1,
         "duration_ms": "[duration]",
         "errors": 1,
@@ -374,6 +381,7 @@ fn schema_load_error_with_schema_field() {
         }
       ],
       "summary": {
+        "cached_files": 0,
         "checked_files": 1,
         "duration_ms": "[duration]",
         "errors": 1,
@@ -424,6 +432,7 @@ fn schema_compile_error_with_schema_field() {
         }
       ],
       "summary": {
+        "cached_files": 0,
         "checked_files": 1,
         "duration_ms": "[duration]",
         "errors": 1,
@@ -474,6 +483,7 @@ fn schema_network_error_with_schema_field() {
         }
       ],
       "summary": {
+        "cached_files": 0,
         "checked_files": 1,
         "duration_ms": "[duration]",
         "errors": 1,
@@ -527,6 +537,7 @@ fn deeply_nested_error() {
         }
       ],
       "summary": {
+        "cached_files": 0,
         "checked_files": 1,
         "duration_ms": "[duration]",
         "errors": 1,
@@ -541,3 +552,216 @@ fn deeply_nested_error() {
     }
     "#);
 }
+
+/// Deprecation warnings are reported with `severity: "warning"` and don't
+/// make the file invalid.
+#[test]
+fn deprecated_warnings() {
+    let (json, code) = jvl_json(&[
+        "check",
+        "--format",
+        "json",
+        "--schema",
+        &fixture("deprecated-schema.json"),
+        &fixture("deprecated-usage.json"),
+    ]);
+
+    assert_eq!(code, 0);
+    assert_eq!(json["valid"], true);
+    assert_eq!(json["files"][0]["valid"], true);
+    let errors = json["files"][0]["errors"].as_array().unwrap();
+    let summary: Vec<_> = errors
+        .iter()
+        .map(|e| {
+            (
+                e["code"].as_str().unwrap(),
+                e["severity"].as_str().unwrap(),
+                e["message"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (
+                "schema(deprecated)",
+                "warning",
+                "property 'legacy' is deprecated"
+            ),
+            (
+                "schema(deprecated)",
+                "warning",
+                "value \"nightly\" is deprecated"
+            ),
+        ]
+    );
+    assert_eq!(json["summary"]["errors"], 0);
+    assert_eq!(json["summary"]["warnings"], 2);
+}
+
+/// A failed `oneOf` reports the closest branch's errors, with the other
+/// branches as related information.
+#[test]
+fn one_of_closest_branch_with_related() {
+    let (json, code) = jvl_json(&[
+        "check",
+        "--format",
+        "json",
+        "--schema",
+        &fixture("oneOf-discriminator-schema.json"),
+        &fixture("oneOf-discriminator-invalid.json"),
+    ]);
+
+    assert_eq!(code, 1);
+    insta::assert_json_snapshot!(json["files"][0]["errors"], @r#"
+    [
+      {
+        "code": "schema(type)",
+        "location": {
+          "column": 52,
+          "length": 3,
+          "line": 2,
+          "offset": 53
+        },
+        "message": "\"4\" is not of type \"number\"",
+        "related": [
+          {
+            "location": {
+              "column": 12,
+              "length": 45,
+              "line": 2,
+              "offset": 13
+            },
+            "message": "oneOf/0: \"radius\" is a required property (and 1 more)"
+          }
+        ],
+        "schema_path": "/properties/shape/oneOf/1/properties/height/type",
+        "severity": "error"
+      }
+    ]
+    "#);
+}
+
+/// The other branches are listed once, not on every error of the closest.
+#[test]
+fn one_of_related_notes_are_attached_once() {
+    let (json, code) = jvl_json(&[
+        "check",
+        "--format",
+        "json",
+        "--schema",
+        &fixture("oneOf-discriminator-schema.json"),
+        &fixture("oneOf-discriminator-two-errors.json"),
+    ]);
+
+    assert_eq!(code, 1);
+    let errors = json["files"][0]["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 2, "{errors:#?}");
+    assert_eq!(errors[0]["related"].as_array().unwrap().len(), 1);
+    assert!(errors[1].get("related").is_none(), "{errors:#?}");
+}
+
+#[test]
+fn jsonl_writes_a_record_per_file_then_a_summary() {
+    let output = common::jvl()
+        .args([
+            "check",
+            "--format",
+            "jsonl",
+            "--schema",
+            &fixture("simple-schema.json"),
+            &fixture("invalid-type.json"),
+            &fixture("valid.json"),
+        ])
+        .output()
+        .expect("failed to run jvl");
+    assert_eq!(output.status.code(), Some(1));
+
+    let records: Vec<serde_json::Value> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(records.len(), 3, "{records:?}");
+    // Files are written as they finish, so in any order.
+    let mut files: Vec<(String, bool)> = records[..2]
+        .iter()
+        .map(|r| {
+            assert_eq!(r["type"], "file");
+            let path = r["path"].as_str().unwrap();
+            (
+                path.rsplit('/').next().unwrap().to_string(),
+                r["valid"] == true,
+            )
+        })
+        .collect();
+    files.sort();
+    assert_eq!(
+        files,
+        [
+            ("invalid-type.json".to_string(), false),
+            ("valid.json".to_string(), true)
+        ]
+    );
+    assert_eq!(records[2]["type"], "summary");
+    assert_eq!(records[2]["valid"], false);
+    assert_eq!(records[2]["summary"]["checked_files"], 2);
+}
+
+/// jvl's own output validates against the schema `jvl output-schema` prints.
+#[test]
+fn output_validates_against_output_schema() {
+    let dir = tempfile::tempdir().unwrap();
+    let run = |args: &[&str]| {
+        common::jvl()
+            .args(args)
+            .current_dir(dir.path())
+            .output()
+            .expect("failed to run jvl")
+    };
+    let write = |name: &str, content: &[u8]| std::fs::write(dir.path().join(name), content);
+
+    write("output.schema.json", &run(&["output-schema"]).stdout).unwrap();
+    write(
+        "line.schema.json",
+        &run(&["output-schema", "--jsonl"]).stdout,
+    )
+    .unwrap();
+
+    let schema = fixture("simple-schema.json");
+    let files = [
+        fixture("invalid-type.json"),
+        fixture("missing-required.json"),
+    ];
+    let check = |format: &str| {
+        let mut args = vec!["check", "--format", format, "--schema", &schema];
+        args.extend(files.iter().map(String::as_str));
+        run(&args).stdout
+    };
+    write("output.json", &check("json")).unwrap();
+    let lines = check("jsonl");
+    for (i, line) in String::from_utf8_lossy(&lines).lines().enumerate() {
+        write(&format!("line{i}.json"), line.as_bytes()).unwrap();
+    }
+
+    let output = run(&["check", "--schema", "output.schema.json", "output.json"]);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let output = run(&[
+        "check",
+        "--schema",
+        "line.schema.json",
+        "line0.json",
+        "line1.json",
+        "line2.json",
+    ]);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,148 @@
+mod common;
+
+use common::{check_json, write};
+
+/// `(code, severity, source text under the span)` for each diagnostic of
+/// the file named `name`.
+fn diagnostics(
+    json: &serde_json::Value,
+    name: &str,
+    source: &str,
+) -> Vec<(String, String, String)> {
+    let file = json["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|f| f["path"].as_str().unwrap().ends_with(name))
+        .unwrap_or_else(|| panic!("{name} not reported: {json}"));
+    file["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| {
+            let text = match e.get("location") {
+                Some(location) => {
+                    let offset = location["offset"].as_u64().unwrap() as usize;
+                    let length = location["length"].as_u64().unwrap() as usize;
+                    source[offset..offset + length].to_string()
+                }
+                None => String::new(),
+            };
+            (
+                e["code"].as_str().unwrap().to_string(),
+                e["severity"].as_str().unwrap().to_string(),
+                text,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn duplicate_keys_are_always_reported() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = r#"{ "name": "a", "port": 1, "name": "b" }"#;
+    write(dir.path(), "data.json", source);
+
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(
+        diagnostics(&json, "data.json", source),
+        [(
+            "lint(duplicate-key)".to_string(),
+            "error".to_string(),
+            r#""name""#.to_string()
+        )]
+    );
+    let related = &json["files"][0]["errors"][0]["related"][0];
+    assert_eq!(related["message"], "first defined here");
+    assert_eq!(related["location"]["offset"], 2);
+}
+
+#[test]
+fn rules_apply_by_pattern() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "jvl.json",
+        r#"{
+  "files": ["config/*.json"],
+  "lint": [
+    { "files": ["config/*.json"], "keyCase": "camelCase", "maxDepth": 2 },
+    { "files": ["config/app.json"], "keyCase": "kebab-case", "noEmptyObjects": true, "noEmptyArrays": true },
+    { "files": ["config/big.json"], "maxFileSize": 16 }
+  ]
+}"#,
+    );
+    let app =
+        r#"{ "log-level": "info", "dbHost": "x", "extra": {}, "tags": [], "deep": { "a": [1] } }"#;
+    write(dir.path(), "config/app.json", app);
+    let big = r#"{ "$schema_ok": 1, "snake_key": [] }"#;
+    write(dir.path(), "config/big.json", big);
+
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(1), "{json}");
+    let error = |code: &str, text: &str| (code.to_string(), "error".to_string(), text.to_string());
+    assert_eq!(
+        diagnostics(&json, "app.json", app),
+        [
+            error("lint(key-case)", r#""dbHost""#),
+            error("lint(empty-object)", "{}"),
+            error("lint(empty-array)", "[]"),
+            error("lint(max-depth)", "[1]"),
+        ]
+    );
+    assert_eq!(
+        diagnostics(&json, "big.json", big),
+        [
+            error("lint(max-file-size)", ""),
+            error("lint(key-case)", r#""snake_key""#),
+        ]
+    );
+}
+
+#[test]
+fn severity_overrides_apply_to_lint_and_schema_codes() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "jvl.json",
+        r#"{
+  "files": ["data.json"],
+  "schemas": [{ "files": ["data.json"], "path": "schema.json" }],
+  "lint": [{ "files": ["*.json"], "noEmptyArrays": true }],
+  "severity": { "lint": "warning", "lint(empty-array)": "off", "schema(type)": "warning" }
+}"#,
+    );
+    write(
+        dir.path(),
+        "schema.json",
+        r#"{ "properties": { "port": { "type": "integer" } } }"#,
+    );
+    let source = r#"{ "port": "80", "tags": [], "port": 80 }"#;
+    write(dir.path(), "data.json", source);
+
+    // The duplicate hides the invalid first value from the schema.
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(0), "{json}");
+    assert_eq!(
+        diagnostics(&json, "data.json", source),
+        [(
+            "lint(duplicate-key)".to_string(),
+            "warning".to_string(),
+            r#""port""#.to_string()
+        )]
+    );
+
+    let source = r#"{ "port": "80" }"#;
+    write(dir.path(), "data.json", source);
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(0), "{json}");
+    assert_eq!(
+        diagnostics(&json, "data.json", source),
+        [(
+            "schema(type)".to_string(),
+            "warning".to_string(),
+            r#""80""#.to_string()
+        )]
+    );
+}
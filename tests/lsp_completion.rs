@@ -546,3 +546,81 @@ async fn completion_includes_documentation() {
         "expected description in documentation, got: {doc}"
     );
 }
+
+/// Deprecated properties carry the `deprecated` tag so clients strike them through.
+#[tokio::test]
+async fn completion_marks_deprecated_properties() {
+    let mut client = TestClient::new();
+    client.initialize().await;
+
+    let content = doc_with_schema("");
+    let uri = doc_uri();
+    open_and_wait(&mut client, &uri, &content).await;
+
+    let cursor_col = (content.len() - 1) as u32;
+    let result = client.completion(&uri, 0, cursor_col).await;
+
+    let items = result["items"].as_array().unwrap();
+    let item = |label: &str| {
+        items
+            .iter()
+            .find(|i| i["label"] == label)
+            .unwrap_or_else(|| panic!("missing '{label}' in {items:?}"))
+    };
+    assert_eq!(item("legacy_name")["tags"], serde_json::json!([1]));
+    assert!(item("name").get("tags").is_none());
+}
+
+/// Deprecated `oneOf` branches mark their values as deprecated.
+#[tokio::test]
+async fn completion_marks_deprecated_values() {
+    let mut client = TestClient::new();
+    client.initialize().await;
+
+    let content = doc_with_schema(r#""channel": null"#);
+    let uri = doc_uri();
+    open_and_wait(&mut client, &uri, &content).await;
+
+    let idx = content.find("\"channel\": ").unwrap() + "\"channel\": ".len();
+    let result = client.completion(&uri, 0, idx as u32).await;
+
+    let items = result["items"].as_array().unwrap();
+    let nightly = items.iter().find(|i| i["label"] == r#""nightly""#).unwrap();
+    let stable = items.iter().find(|i| i["label"] == r#""stable""#).unwrap();
+    assert_eq!(nightly["tags"], serde_json::json!([1]));
+    assert!(stable.get("tags").is_none());
+}
+
+/// Values under a `references` pointer complete to the IDs defined by the
+/// target files, even without a schema.
+#[tokio::test]
+async fn completion_suggests_reference_ids() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("jvl.json"),
+        r#"{"references": [{"files": ["services/*.json"], "pointer": "/owners/*",
+            "target": {"files": ["teams/*.json"], "pointer": "/id"}}]}"#,
+    )
+    .unwrap();
+    std::fs::create_dir_all(dir.path().join("teams")).unwrap();
+    std::fs::create_dir_all(dir.path().join("services")).unwrap();
+    std::fs::write(dir.path().join("teams/core.json"), r#"{"id": "core"}"#).unwrap();
+    std::fs::write(dir.path().join("teams/web.json"), r#"{"id": "web"}"#).unwrap();
+
+    let content = r#"{"owners": []}"#;
+    let file_path = dir.path().join("services/api.json");
+    std::fs::write(&file_path, content).unwrap();
+
+    let mut client = TestClient::new();
+    client.initialize().await;
+    let uri = file_uri(file_path.to_str().unwrap());
+    open_and_wait(&mut client, &uri, content).await;
+
+    let idx = content.find('[').unwrap() + 1;
+    let result = client.completion(&uri, 0, idx as u32).await;
+
+    assert_eq!(labels(&result), [r#""core""#, r#""web""#]);
+    let item = &result["items"][0];
+    assert_eq!(item["kind"], 18); // Reference
+    assert_eq!(item["detail"], "defined in teams/core.json");
+}
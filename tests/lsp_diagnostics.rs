@@ -382,3 +382,272 @@ async fn non_file_uri_handled_gracefully() {
     // that the server is still responding.)
     client.shutdown().await;
 }
+
+/// Deprecation warnings are published with the `Deprecated` diagnostic tag.
+#[tokio::test]
+async fn deprecated_usage_publishes_tagged_warning() {
+    let mut client = TestClient::new();
+    client.initialize().await;
+
+    let schema = format!(
+        "{}/tests/fixtures/deprecated-schema.json",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let content = format!(r#"{{"$schema": "{schema}", "legacy": "x"}}"#);
+    client.did_open(&doc_uri(), "json", 1, &content).await;
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let notification = client
+        .recv_notification("textDocument/publishDiagnostics")
+        .await;
+
+    let diagnostics = notification["params"]["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1, "diagnostics: {diagnostics:?}");
+    let diag = &diagnostics[0];
+    assert_eq!(diag["code"], "schema(deprecated)");
+    // DiagnosticSeverity::WARNING = 2, DiagnosticTag::DEPRECATED = 2
+    assert_eq!(diag["severity"], 2);
+    assert_eq!(diag["tags"], serde_json::json!([2]));
+}
+
+/// The other `oneOf` branches are published as `relatedInformation`.
+#[tokio::test]
+async fn one_of_failure_includes_related_information() {
+    let mut client = TestClient::new();
+    client.initialize().await;
+
+    let schema = format!(
+        "{}/tests/fixtures/oneOf-discriminator-schema.json",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let content = format!(
+        r#"{{"$schema": "{schema}", "shape": {{"kind": "rect", "width": 3, "height": "4"}}}}"#
+    );
+    let uri = doc_uri();
+    client.did_open(&uri, "json", 1, &content).await;
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let notification = client
+        .recv_notification("textDocument/publishDiagnostics")
+        .await;
+
+    let diagnostics = notification["params"]["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1, "diagnostics: {diagnostics:?}");
+    let diag = &diagnostics[0];
+    assert_eq!(diag["code"], "schema(type)");
+    let related = diag["relatedInformation"].as_array().unwrap();
+    assert_eq!(related.len(), 1);
+    assert_eq!(related[0]["location"]["uri"], uri.as_str());
+    assert!(
+        related[0]["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("oneOf/0: "),
+        "related: {related:?}"
+    );
+}
+
+/// Diagnostics for a JSON code block in Markdown land on the block's lines.
+#[tokio::test]
+async fn markdown_code_block_diagnostics_point_into_the_document() {
+    let mut client = TestClient::new();
+    client.initialize().await;
+
+    let content = format!(
+        "# Example\n\n```json schema={}\n{{\n  \"name\": \"app\",\n  \"port\": \"wrong\"\n}}\n```\n",
+        simple_schema_path()
+    );
+    let uri = file_uri(&format!(
+        "{}/tests/fixtures/test-lsp-doc.md",
+        env!("CARGO_MANIFEST_DIR")
+    ));
+    client.did_open(&uri, "markdown", 1, &content).await;
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let notification = client
+        .recv_notification("textDocument/publishDiagnostics")
+        .await;
+
+    let diagnostics = notification["params"]["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1, "diagnostics: {diagnostics:?}");
+    let diag = &diagnostics[0];
+    assert_eq!(diag["code"], "schema(type)");
+    assert_eq!(diag["range"]["start"]["line"], 5);
+    assert_eq!(diag["range"]["start"]["character"], 10);
+}
+
+/// A value naming an ID no target file defines is flagged, even without a
+/// schema.
+#[tokio::test]
+async fn dangling_reference_produces_diagnostic() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("jvl.json"),
+        r#"{"references": [{"files": ["services/*.json"], "pointer": "/owner",
+            "target": {"files": ["teams/*.json"], "pointer": "/id"}}]}"#,
+    )
+    .unwrap();
+    std::fs::create_dir_all(dir.path().join("teams")).unwrap();
+    std::fs::create_dir_all(dir.path().join("services")).unwrap();
+    std::fs::write(dir.path().join("teams/core.json"), r#"{"id": "core"}"#).unwrap();
+
+    let content = r#"{"owner": "mobile"}"#;
+    let file_path = dir.path().join("services/api.json");
+    std::fs::write(&file_path, content).unwrap();
+
+    let mut client = TestClient::new();
+    client.initialize().await;
+
+    let uri = file_uri(file_path.to_str().unwrap());
+    client.did_open(&uri, "json", 1, content).await;
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let notification = client
+        .recv_notification("textDocument/publishDiagnostics")
+        .await;
+
+    let diagnostics = notification["params"]["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1, "diagnostics: {diagnostics:?}");
+    assert_eq!(diagnostics[0]["code"], "reference(missing)");
+    assert_eq!(diagnostics[0]["range"]["start"]["character"], 10);
+}
+
+/// An unsaved edit to an open target file counts: the IDs it defines clear
+/// the diagnostics of documents referencing them.
+#[tokio::test]
+async fn open_target_document_defines_references() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("jvl.json"),
+        r#"{"references": [{"files": ["services/*.json"], "pointer": "/owner",
+            "target": {"files": ["teams/*.json"], "pointer": "/id"}}]}"#,
+    )
+    .unwrap();
+    std::fs::create_dir_all(dir.path().join("teams")).unwrap();
+    std::fs::create_dir_all(dir.path().join("services")).unwrap();
+    let team_path = dir.path().join("teams/mobile.json");
+    std::fs::write(&team_path, r#"{"id": "core"}"#).unwrap();
+
+    let content = r#"{"owner": "mobile"}"#;
+    let file_path = dir.path().join("services/api.json");
+    std::fs::write(&file_path, content).unwrap();
+
+    let mut client = TestClient::new();
+    client.initialize().await;
+
+    let uri = file_uri(file_path.to_str().unwrap());
+    client.did_open(&uri, "json", 1, content).await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let notification = client
+        .recv_notification("textDocument/publishDiagnostics")
+        .await;
+    assert_eq!(
+        notification["params"]["diagnostics"]
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+
+    // Define the ID in the editor only; the file on disk still says "core".
+    let team_uri = file_uri(team_path.to_str().unwrap());
+    client
+        .did_open(&team_uri, "json", 1, r#"{"id": "mobile"}"#)
+        .await;
+
+    // The team document's own (empty) diagnostics, then the re-validated
+    // service document's.
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    let mut cleared = false;
+    for _ in 0..2 {
+        let notification = client
+            .recv_notification("textDocument/publishDiagnostics")
+            .await;
+        if notification["params"]["uri"] == uri.as_str() {
+            let diagnostics = notification["params"]["diagnostics"].as_array().unwrap();
+            assert!(diagnostics.is_empty(), "diagnostics: {diagnostics:?}");
+            cleared = true;
+        }
+    }
+    assert!(cleared, "the service document was not re-validated");
+}
+
+/// A value that a `unique` rule covers is flagged when another file on disk
+/// uses it, with a related location in that file.
+#[tokio::test]
+async fn duplicate_across_files_produces_diagnostic() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("jvl.json"),
+        r#"{"unique": [{"files": ["*.json", "!jvl.json"], "pointer": "/id"}]}"#,
+    )
+    .unwrap();
+    let other = dir.path().join("other.json");
+    std::fs::write(&other, r#"{"id": "api"}"#).unwrap();
+
+    let content = r#"{"id": "api"}"#;
+    let file_path = dir.path().join("this.json");
+    std::fs::write(&file_path, content).unwrap();
+
+    let mut client = TestClient::new();
+    client.initialize().await;
+
+    let uri = file_uri(file_path.to_str().unwrap());
+    client.did_open(&uri, "json", 1, content).await;
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let notification = client
+        .recv_notification("textDocument/publishDiagnostics")
+        .await;
+
+    let diagnostics = notification["params"]["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1, "diagnostics: {diagnostics:?}");
+    assert_eq!(diagnostics[0]["code"], "unique(duplicate)");
+    let related = &diagnostics[0]["relatedInformation"][0]["location"];
+    assert!(
+        related["uri"].as_str().unwrap().ends_with("/other.json"),
+        "related: {related}"
+    );
+    assert_eq!(related["range"]["start"]["character"], 7);
+}
+
+/// A related location in another file is converted using that file's text,
+/// so columns after non-ASCII characters count UTF-16 code units.
+#[tokio::test]
+async fn duplicate_across_files_uses_other_files_positions() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("jvl.json"),
+        r#"{"unique": [{"files": ["*.json", "!jvl.json"], "pointer": "/id"}]}"#,
+    )
+    .unwrap();
+    let other = dir.path().join("other.json");
+    std::fs::write(&other, "{\n  \"ñame\": 1, \"id\": \"api\"\n}").unwrap();
+
+    let content = r#"{"id": "api"}"#;
+    let file_path = dir.path().join("this.json");
+    std::fs::write(&file_path, content).unwrap();
+
+    let mut client = TestClient::new();
+    client.initialize().await;
+
+    let uri = file_uri(file_path.to_str().unwrap());
+    client.did_open(&uri, "json", 1, content).await;
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let notification = client
+        .recv_notification("textDocument/publishDiagnostics")
+        .await;
+
+    let diagnostics = notification["params"]["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1, "diagnostics: {diagnostics:?}");
+    let range = &diagnostics[0]["relatedInformation"][0]["location"]["range"];
+    assert_eq!(
+        range["start"],
+        serde_json::json!({ "line": 1, "character": 19 })
+    );
+    assert_eq!(
+        range["end"],
+        serde_json::json!({ "line": 1, "character": 24 })
+    );
+}
@@ -0,0 +1,207 @@
+mod common;
+
+use std::time::Duration;
+
+use common::lsp_client::{TestClient, file_uri};
+
+fn simple_schema_path() -> String {
+    format!(
+        "{}/tests/fixtures/simple-schema.json",
+        env!("CARGO_MANIFEST_DIR")
+    )
+}
+
+fn doc_uri(name: &str) -> String {
+    file_uri(&format!(
+        "{}/tests/fixtures/{name}",
+        env!("CARGO_MANIFEST_DIR")
+    ))
+}
+
+/// A multi-line document whose third line has a non-BMP character before the
+/// `port` value:
+///
+/// ```text
+/// {
+///   "$schema": "...",
+///   "name": "🎉 app", "port": 8080
+/// }
+/// ```
+///
+/// On line 2, `8080` spans UTF-16 characters 28..32 but bytes 30..34.
+fn doc_with_emoji() -> String {
+    let schema = simple_schema_path();
+    format!("{{\n  \"$schema\": \"{schema}\",\n  \"name\": \"🎉 app\", \"port\": 8080\n}}")
+}
+
+async fn recv_diagnostics(client: &mut TestClient) -> Vec<serde_json::Value> {
+    let notification = client
+        .recv_notification("textDocument/publishDiagnostics")
+        .await;
+    notification["params"]["diagnostics"]
+        .as_array()
+        .unwrap()
+        .clone()
+}
+
+/// Range edits are applied in UTF-16 code units by default.
+#[tokio::test]
+async fn incremental_edit_uses_utf16_positions() {
+    let mut client = TestClient::new();
+    client.initialize().await;
+
+    let uri = doc_uri("test-incremental-utf16.json");
+    client.did_open(&uri, "json", 1, &doc_with_emoji()).await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(recv_diagnostics(&mut client).await.is_empty());
+
+    // Replace `8080` with `"bad"`.
+    client
+        .did_change_ranges(&uri, 2, &[((2, 28), (2, 32), r#""bad""#)])
+        .await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let diagnostics = recv_diagnostics(&mut client).await;
+    assert_eq!(diagnostics.len(), 1, "got: {diagnostics:?}");
+    assert_eq!(diagnostics[0]["code"], "schema(type)");
+    assert_eq!(
+        diagnostics[0]["range"],
+        serde_json::json!({
+            "start": { "line": 2, "character": 28 },
+            "end": { "line": 2, "character": 33 }
+        })
+    );
+
+    // Multiple changes in one notification are applied in order: restore the
+    // number, then append a property after it.
+    client
+        .did_change_ranges(
+            &uri,
+            3,
+            &[
+                ((2, 28), (2, 33), "8080"),
+                ((2, 32), (2, 32), r#", "extra": true"#),
+            ],
+        )
+        .await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(recv_diagnostics(&mut client).await.is_empty());
+}
+
+/// With UTF-8 negotiated, range edits are applied in bytes.
+#[tokio::test]
+async fn incremental_edit_uses_utf8_positions() {
+    let mut client = TestClient::new();
+    client
+        .initialize_with_params(serde_json::json!({
+            "general": { "positionEncodings": ["utf-8"] }
+        }))
+        .await;
+
+    let uri = doc_uri("test-incremental-utf8.json");
+    client.did_open(&uri, "json", 1, &doc_with_emoji()).await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(recv_diagnostics(&mut client).await.is_empty());
+
+    client
+        .did_change_ranges(&uri, 2, &[((2, 30), (2, 34), r#""bad""#)])
+        .await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let diagnostics = recv_diagnostics(&mut client).await;
+    assert_eq!(diagnostics.len(), 1, "got: {diagnostics:?}");
+    assert_eq!(
+        diagnostics[0]["range"],
+        serde_json::json!({
+            "start": { "line": 2, "character": 30 },
+            "end": { "line": 2, "character": 35 }
+        })
+    );
+}
+
+/// Edits confined to whitespace or comments republish the previous
+/// diagnostics at their new positions right away, without waiting for the
+/// debounced re-validation. Any other edit goes through the debounce.
+#[tokio::test(start_paused = true)]
+async fn trivia_edit_skips_revalidation() {
+    let mut client = TestClient::new();
+    client.initialize().await;
+
+    let schema = simple_schema_path();
+    let uri = doc_uri("test-incremental-trivia.json");
+    let content = format!(
+        "{{\n  \"$schema\": \"{schema}\",\n  // note\n  \"name\": \"app\", \"port\": \"bad\"\n}}"
+    );
+    client.did_open(&uri, "json", 1, &content).await;
+    tokio::time::advance(Duration::from_millis(250)).await;
+    let diagnostics = recv_diagnostics(&mut client).await;
+    assert_eq!(diagnostics.len(), 1, "got: {diagnostics:?}");
+    assert_eq!(
+        diagnostics[0]["range"]["start"],
+        serde_json::json!({ "line": 3, "character": 25 })
+    );
+
+    // Edit the comment and insert a blank line with indentation before the
+    // `name` property.
+    let before = tokio::time::Instant::now();
+    client
+        .did_change_ranges(
+            &uri,
+            2,
+            &[
+                ((2, 5), (2, 9), "a longer note"),
+                ((3, 0), (3, 0), "\n    "),
+            ],
+        )
+        .await;
+    let diagnostics = recv_diagnostics(&mut client).await;
+    assert_eq!(
+        before.elapsed(),
+        Duration::ZERO,
+        "trivia edit should not wait for the debounce"
+    );
+    assert_eq!(diagnostics.len(), 1, "got: {diagnostics:?}");
+    assert_eq!(
+        diagnostics[0]["range"],
+        serde_json::json!({
+            "start": { "line": 4, "character": 29 },
+            "end": { "line": 4, "character": 34 }
+        })
+    );
+
+    // Fixing the value is not trivia: it is re-validated after the debounce.
+    let before = tokio::time::Instant::now();
+    client
+        .did_change_ranges(&uri, 3, &[((4, 29), (4, 34), "8080")])
+        .await;
+    let diagnostics = recv_diagnostics(&mut client).await;
+    assert!(before.elapsed() >= Duration::from_millis(200));
+    assert!(diagnostics.is_empty(), "got: {diagnostics:?}");
+}
+
+/// With `maxFileSize` set, growing a comment can push the file over the
+/// limit, so trivia edits are re-validated like any other.
+#[tokio::test]
+async fn trivia_edit_revalidates_when_file_size_is_linted() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("jvl.json"),
+        r#"{ "lint": [{ "files": ["*.json"], "maxFileSize": 40 }] }"#,
+    )
+    .unwrap();
+    let path = dir.path().join("data.json");
+    let content = "{\n  // note\n  \"name\": \"app\"\n}";
+    std::fs::write(&path, content).unwrap();
+
+    let mut client = TestClient::new();
+    client.initialize().await;
+    let uri = file_uri(path.to_str().unwrap());
+    client.did_open(&uri, "json", 1, content).await;
+    let diagnostics = recv_diagnostics(&mut client).await;
+    assert!(diagnostics.is_empty(), "got: {diagnostics:?}");
+
+    client
+        .did_change_ranges(&uri, 2, &[((1, 5), (1, 9), "a much longer note")])
+        .await;
+    let diagnostics = recv_diagnostics(&mut client).await;
+    assert_eq!(diagnostics.len(), 1, "got: {diagnostics:?}");
+    assert_eq!(diagnostics[0]["code"], "lint(max-file-size)");
+}
@@ -8,10 +8,10 @@ async fn initialize_and_shutdown() {
     let mut client = TestClient::new();
     let result = client.initialize().await;
 
-    // Server advertises FULL sync and reports its name.
+    // Server advertises INCREMENTAL sync and reports its name.
     assert_eq!(
         result["capabilities"]["textDocumentSync"],
-        serde_json::json!(1) // TextDocumentSyncKind::FULL = 1
+        serde_json::json!(2) // TextDocumentSyncKind::INCREMENTAL = 2
     );
     assert_eq!(result["serverInfo"]["name"], "jvl");
 
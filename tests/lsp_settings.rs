@@ -0,0 +1,303 @@
+mod common;
+
+use std::time::Duration;
+
+use common::lsp_client::{TestClient, file_uri};
+
+/// Requires `{ name: string }`.
+const NAME_SCHEMA: &str = r#"{ "type": "object", "required": ["name"] }"#;
+/// Requires `{ port: number }`.
+const PORT_SCHEMA: &str = r#"{ "type": "object", "required": ["port"] }"#;
+
+/// Temp workspace with two schemas and no jvl.json:
+///   workspace/
+///     name.schema.json
+///     port.schema.json
+fn setup_workspace() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("name.schema.json"), NAME_SCHEMA).unwrap();
+    std::fs::write(dir.path().join("port.schema.json"), PORT_SCHEMA).unwrap();
+    dir
+}
+
+fn uri_in(dir: &tempfile::TempDir, name: &str) -> String {
+    let root = std::fs::canonicalize(dir.path()).unwrap();
+    file_uri(&root.join(name).display().to_string())
+}
+
+async fn recv_diagnostics(client: &mut TestClient) -> (String, Vec<serde_json::Value>) {
+    let notification = client
+        .recv_notification("textDocument/publishDiagnostics")
+        .await;
+    let uri = notification["params"]["uri"].as_str().unwrap().to_string();
+    let diagnostics = notification["params"]["diagnostics"]
+        .as_array()
+        .unwrap()
+        .clone();
+    (uri, diagnostics)
+}
+
+fn codes(diagnostics: &[serde_json::Value]) -> Vec<&str> {
+    diagnostics
+        .iter()
+        .map(|d| d["code"].as_str().unwrap())
+        .collect()
+}
+
+/// Editor mappings from `initializationOptions` apply to documents without a
+/// `$schema`, relative to the workspace folder.
+#[tokio::test]
+async fn initialization_options_schema_mapping() {
+    let dir = setup_workspace();
+    let mut client = TestClient::new();
+    client
+        .initialize_with_options(
+            &uri_in(&dir, ""),
+            serde_json::json!({
+                "schemas": [{ "path": "name.schema.json", "files": ["*.app.json"] }]
+            }),
+        )
+        .await;
+
+    let uri = uri_in(&dir, "a.app.json");
+    client.did_open(&uri, "json", 1, r#"{ "port": 1 }"#).await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let (_, diagnostics) = recv_diagnostics(&mut client).await;
+    assert_eq!(codes(&diagnostics), ["schema(required)"]);
+
+    // The document's own `$schema` wins over the editor mapping.
+    let uri = uri_in(&dir, "b.app.json");
+    client
+        .did_open(
+            &uri,
+            "json",
+            1,
+            r#"{ "$schema": "./port.schema.json", "port": 1 }"#,
+        )
+        .await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let (_, diagnostics) = recv_diagnostics(&mut client).await;
+    assert!(diagnostics.is_empty(), "got: {diagnostics:?}");
+}
+
+/// jvl.json mappings take priority over editor mappings.
+#[tokio::test]
+async fn jvl_json_mappings_take_priority() {
+    let dir = setup_workspace();
+    std::fs::write(
+        dir.path().join("jvl.json"),
+        r#"{ "schemas": [{ "path": "port.schema.json", "files": ["a.json"] }] }"#,
+    )
+    .unwrap();
+    // jvl.json globs match canonical paths, so the documents must exist.
+    std::fs::write(dir.path().join("a.json"), r#"{ "name": "x" }"#).unwrap();
+    std::fs::write(dir.path().join("b.json"), r#"{ "port": 1 }"#).unwrap();
+    let mut client = TestClient::new();
+    client
+        .initialize_with_options(
+            &uri_in(&dir, ""),
+            serde_json::json!({
+                "schemas": [{ "path": "name.schema.json", "files": ["*.json"] }]
+            }),
+        )
+        .await;
+
+    client
+        .did_open(&uri_in(&dir, "a.json"), "json", 1, r#"{ "name": "x" }"#)
+        .await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let (_, diagnostics) = recv_diagnostics(&mut client).await;
+    let message = diagnostics[0]["message"].as_str().unwrap();
+    assert!(message.contains("\"port\""), "got: {diagnostics:?}");
+
+    client
+        .did_open(&uri_in(&dir, "b.json"), "json", 1, r#"{ "port": 1 }"#)
+        .await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let (_, diagnostics) = recv_diagnostics(&mut client).await;
+    let message = diagnostics[0]["message"].as_str().unwrap();
+    assert!(message.contains("\"name\""), "got: {diagnostics:?}");
+}
+
+/// `workspace/didChangeConfiguration` applies settings to open documents
+/// without reopening them.
+#[tokio::test]
+async fn did_change_configuration_applies_live() {
+    let dir = setup_workspace();
+    let mut client = TestClient::new();
+    client
+        .initialize_with_options(&uri_in(&dir, ""), serde_json::Value::Null)
+        .await;
+
+    let uri = uri_in(&dir, "doc.json");
+    client.did_open(&uri, "json", 1, r#"{ "port": 1 }"#).await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let (_, diagnostics) = recv_diagnostics(&mut client).await;
+    assert!(diagnostics.is_empty(), "got: {diagnostics:?}");
+
+    client
+        .did_change_configuration(serde_json::json!({ "jvl": { "strict": true } }))
+        .await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let (_, diagnostics) = recv_diagnostics(&mut client).await;
+    assert_eq!(codes(&diagnostics), ["no-schema"]);
+
+    client
+        .did_change_configuration(serde_json::json!({
+            "jvl": {
+                "schemas": [{ "path": "name.schema.json", "files": ["doc.json"] }]
+            }
+        }))
+        .await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let (_, diagnostics) = recv_diagnostics(&mut client).await;
+    assert_eq!(codes(&diagnostics), ["schema(required)"]);
+
+    // Disabling diagnostics clears them immediately.
+    client
+        .did_change_configuration(serde_json::json!({
+            "jvl": { "disable": ["diagnostics"] }
+        }))
+        .await;
+    let (_, diagnostics) = recv_diagnostics(&mut client).await;
+    assert!(diagnostics.is_empty(), "got: {diagnostics:?}");
+}
+
+/// Clearing the settings (`null`) goes back to the defaults.
+#[tokio::test]
+async fn null_configuration_resets_to_defaults() {
+    let dir = setup_workspace();
+    let mut client = TestClient::new();
+    client
+        .initialize_with_options(&uri_in(&dir, ""), serde_json::json!({ "strict": true }))
+        .await;
+
+    let uri = uri_in(&dir, "doc.json");
+    client.did_open(&uri, "json", 1, r#"{ "port": 1 }"#).await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let (_, diagnostics) = recv_diagnostics(&mut client).await;
+    assert_eq!(codes(&diagnostics), ["no-schema"]);
+
+    client
+        .did_change_configuration(serde_json::Value::Null)
+        .await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let (_, diagnostics) = recv_diagnostics(&mut client).await;
+    assert!(diagnostics.is_empty(), "got: {diagnostics:?}");
+}
+
+/// Settings and features from a newer editor extension are skipped rather
+/// than discarding everything else.
+#[tokio::test]
+async fn unknown_settings_and_features_are_ignored() {
+    let dir = setup_workspace();
+    let mut client = TestClient::new();
+    client
+        .initialize_with_options(
+            &uri_in(&dir, ""),
+            serde_json::json!({ "strict": true, "disable": ["formatting"], "futureSetting": 1 }),
+        )
+        .await;
+
+    let uri = uri_in(&dir, "doc.json");
+    client.did_open(&uri, "json", 1, r#"{ "port": 1 }"#).await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let (_, diagnostics) = recv_diagnostics(&mut client).await;
+    assert_eq!(codes(&diagnostics), ["no-schema"]);
+}
+
+#[tokio::test]
+async fn disabled_hover_and_completion_return_null() {
+    let dir = setup_workspace();
+    std::fs::write(
+        dir.path().join("described.schema.json"),
+        r#"{ "properties": { "name": { "description": "The name" } } }"#,
+    )
+    .unwrap();
+    let mut client = TestClient::new();
+    client
+        .initialize_with_options(
+            &uri_in(&dir, ""),
+            serde_json::json!({ "disable": ["hover", "completion"] }),
+        )
+        .await;
+
+    let uri = uri_in(&dir, "doc.json");
+    client
+        .did_open(
+            &uri,
+            "json",
+            1,
+            r#"{ "$schema": "./described.schema.json", "name": "x" }"#,
+        )
+        .await;
+    assert!(client.hover(&uri, 0, 45).await["result"].is_null());
+    assert!(client.completion(&uri, 0, 43).await["result"].is_null());
+}
+
+#[tokio::test(start_paused = true)]
+async fn debounce_setting_delays_validation() {
+    let dir = setup_workspace();
+    let mut client = TestClient::new();
+    client
+        .initialize_with_options(&uri_in(&dir, ""), serde_json::json!({ "debounceMs": 1000 }))
+        .await;
+
+    let before = tokio::time::Instant::now();
+    client
+        .did_open(
+            &uri_in(&dir, "doc.json"),
+            "json",
+            1,
+            r#"{ "$schema": "./name.schema.json" }"#,
+        )
+        .await;
+    let (_, diagnostics) = recv_diagnostics(&mut client).await;
+    assert!(before.elapsed() >= Duration::from_millis(1000));
+    assert_eq!(codes(&diagnostics), ["schema(required)"]);
+}
+
+/// In offline mode an uncached URL schema is reported instead of fetched.
+#[tokio::test]
+async fn offline_mode_does_not_fetch() {
+    let dir = setup_workspace();
+    let mut client = TestClient::new();
+    client
+        .initialize_with_options(&uri_in(&dir, ""), serde_json::json!({ "offline": true }))
+        .await;
+
+    client
+        .did_open(
+            &uri_in(&dir, "doc.json"),
+            "json",
+            1,
+            r#"{ "$schema": "https://jvl-offline-test.invalid/schema.json" }"#,
+        )
+        .await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let (_, diagnostics) = recv_diagnostics(&mut client).await;
+    let message = diagnostics[0]["message"].as_str().unwrap();
+    assert!(message.contains("offline mode"), "got: {diagnostics:?}");
+}
+
+/// In offline mode a remote config that isn't cached isn't fetched either.
+#[tokio::test]
+async fn offline_mode_does_not_fetch_extended_configs() {
+    let dir = setup_workspace();
+    std::fs::write(
+        dir.path().join("jvl.json"),
+        r#"{ "extends": "https://jvl-offline-test.invalid/jvl.json" }"#,
+    )
+    .unwrap();
+    let mut client = TestClient::new();
+    client
+        .initialize_with_options(&uri_in(&dir, ""), serde_json::json!({ "offline": true }))
+        .await;
+
+    client
+        .did_open(&uri_in(&dir, "doc.json"), "json", 1, "{}")
+        .await;
+    let log = client.recv_notification("window/logMessage").await;
+    let message = log["params"]["message"].as_str().unwrap();
+    assert!(message.contains("offline mode"), "got: {message}");
+}
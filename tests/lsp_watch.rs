@@ -134,3 +134,149 @@ async fn schema_file_deleted_triggers_load_error() {
 
     client.shutdown().await;
 }
+
+/// A change to a config file that jvl.json extends evicts the cached config.
+///
+/// Setup: jvl.json extends base.json, which maps test.json to a schema that
+/// requires `name`. After base.json drops the mapping and the change is
+/// reported, the document is re-validated without a schema.
+#[tokio::test]
+async fn extended_config_change_triggers_revalidation() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = std::fs::canonicalize(dir.path()).unwrap();
+    std::fs::write(
+        root.join("schema.json"),
+        r#"{"type":"object","required":["name"]}"#,
+    )
+    .unwrap();
+    std::fs::write(root.join("jvl.json"), r#"{"extends":"./base.json"}"#).unwrap();
+    let base_path = root.join("base.json");
+    std::fs::write(
+        &base_path,
+        r#"{"schemas":[{"path":"schema.json","files":["test.json"]}]}"#,
+    )
+    .unwrap();
+    let doc_path = root.join("test.json");
+    std::fs::write(&doc_path, "{}").unwrap();
+    let doc_uri = file_uri(&doc_path.display().to_string());
+
+    let mut client = TestClient::new();
+    client.initialize().await;
+
+    client.did_open(&doc_uri, "json", 1, "{}").await;
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    let n1 = client
+        .recv_notification("textDocument/publishDiagnostics")
+        .await;
+    let diags1 = n1["params"]["diagnostics"].as_array().unwrap();
+    assert_eq!(diags1.len(), 1, "expected a required error: {diags1:?}");
+
+    std::fs::write(&base_path, r#"{"schemas":[]}"#).unwrap();
+    client
+        .did_change_watched_files(&[(&file_uri(&base_path.display().to_string()), 2)])
+        .await;
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    let n2 = client
+        .recv_notification("textDocument/publishDiagnostics")
+        .await;
+    let diags2 = n2["params"]["diagnostics"].as_array().unwrap();
+    assert!(diags2.is_empty(), "expected no diagnostics: {diags2:?}");
+
+    client.shutdown().await;
+}
+
+/// A target file changed on disk → the workspace's cross-file index picks up
+/// its IDs and documents referencing them are re-validated.
+#[tokio::test]
+async fn reference_target_change_triggers_revalidation() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("jvl.json"),
+        r#"{"references": [{"files": ["services/*.json"], "pointer": "/owner",
+            "target": {"files": ["teams/*.json"], "pointer": "/id"}}]}"#,
+    )
+    .unwrap();
+    std::fs::create_dir_all(dir.path().join("teams")).unwrap();
+    std::fs::create_dir_all(dir.path().join("services")).unwrap();
+    let team_path = dir.path().join("teams/mobile.json");
+    std::fs::write(&team_path, r#"{"id": "core"}"#).unwrap();
+
+    let content = r#"{"owner": "mobile"}"#;
+    let doc_path = dir.path().join("services/api.json");
+    std::fs::write(&doc_path, content).unwrap();
+
+    let mut client = TestClient::new();
+    client.initialize().await;
+
+    let doc_uri = file_uri(doc_path.to_str().unwrap());
+    client.did_open(&doc_uri, "json", 1, content).await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let n1 = client
+        .recv_notification("textDocument/publishDiagnostics")
+        .await;
+    assert_eq!(n1["params"]["diagnostics"].as_array().unwrap().len(), 1);
+
+    std::fs::write(&team_path, r#"{"id": "mobile"}"#).unwrap();
+    let team_uri = file_uri(team_path.to_str().unwrap());
+    client.did_change_watched_files(&[(&team_uri, 2)]).await;
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let n2 = client
+        .recv_notification("textDocument/publishDiagnostics")
+        .await;
+    let diagnostics = n2["params"]["diagnostics"].as_array().unwrap();
+    assert!(diagnostics.is_empty(), "diagnostics: {diagnostics:?}");
+
+    client.shutdown().await;
+}
+
+/// The watcher registered up front covers config files only; the files a
+/// cross-file rule reads are watched once a config with such a rule loads.
+#[tokio::test]
+async fn cross_file_targets_are_watched_once_their_config_loads() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = std::fs::canonicalize(dir.path()).unwrap();
+    std::fs::write(
+        root.join("jvl.json"),
+        r#"{"references": [{"files": ["services/*.json"], "pointer": "/owner",
+            "target": {"files": ["teams/*.json"], "pointer": "/id"}}]}"#,
+    )
+    .unwrap();
+    std::fs::create_dir_all(root.join("services")).unwrap();
+    let content = r#"{"owner": "mobile"}"#;
+    let doc_path = root.join("services/api.json");
+    std::fs::write(&doc_path, content).unwrap();
+
+    let globs = |registration: &serde_json::Value| -> Vec<String> {
+        registration["params"]["registrations"][0]["registerOptions"]["watchers"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|w| w["globPattern"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let mut client = TestClient::new();
+    client.initialize().await;
+    let initial = client.recv_notification("client/registerCapability").await;
+    let initial = globs(&initial);
+    assert!(initial.contains(&"**/jvl.json".to_string()), "{initial:?}");
+    assert!(!initial.contains(&"**/*".to_string()), "{initial:?}");
+
+    let doc_uri = file_uri(doc_path.to_str().unwrap());
+    client.did_open(&doc_uri, "json", 1, content).await;
+    let expected = format!("{}/teams/**/*.json", root.display());
+    let found = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let registration = client.recv_notification("client/registerCapability").await;
+            if globs(&registration).contains(&expected) {
+                break;
+            }
+        }
+    })
+    .await;
+    assert!(found.is_ok(), "no watcher registered for {expected}");
+
+    client.shutdown().await;
+}
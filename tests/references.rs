@@ -0,0 +1,143 @@
+mod common;
+
+use common::{check_json, jvl, write};
+
+const CONFIG: &str = r#"{
+  "files": ["services/*.json", "teams/*.json"],
+  "references": [
+    {
+      "files": ["services/*.json"],
+      "pointer": "/owner",
+      "target": { "files": ["teams/*.json"], "pointer": "/id" }
+    },
+    {
+      "files": ["services/*.json"],
+      "pointer": "/dependsOn/*",
+      "target": { "files": ["services/*.json"], "pointer": "/services", "keys": true }
+    }
+  ]
+}"#;
+
+fn setup_project() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "jvl.json", CONFIG);
+    write(dir.path(), "teams/core.json", r#"{ "id": "core" }"#);
+    write(dir.path(), "teams/web.json", r#"{ "id": "web" }"#);
+    write(
+        dir.path(),
+        "services/api.json",
+        r#"{ "owner": "core", "services": { "api": {}, "db": {} }, "dependsOn": ["db"] }"#,
+    );
+    dir
+}
+
+#[test]
+fn valid_references_pass() {
+    let dir = setup_project();
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(0), "{json}");
+    // Files without a schema are still skipped.
+    assert_eq!(json["summary"]["skipped_files"], 3);
+}
+
+#[test]
+fn dangling_references_point_at_the_value() {
+    let dir = setup_project();
+    let source = r#"{ "owner": "mobile", "dependsOn": ["db", "cache"] }"#;
+    write(dir.path(), "services/worker.json", source);
+
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(1), "{json}");
+    let file = json["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|f| f["valid"] == false)
+        .unwrap();
+    assert!(file["path"].as_str().unwrap().ends_with("worker.json"));
+
+    let errors: Vec<(&str, &str, &str)> = file["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| {
+            let offset = e["location"]["offset"].as_u64().unwrap() as usize;
+            let length = e["location"]["length"].as_u64().unwrap() as usize;
+            (
+                e["code"].as_str().unwrap(),
+                e["message"].as_str().unwrap(),
+                &source[offset..offset + length],
+            )
+        })
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            (
+                "reference(missing)",
+                r#"unknown reference "mobile""#,
+                r#""mobile""#
+            ),
+            (
+                "reference(missing)",
+                r#"unknown reference "cache""#,
+                r#""cache""#
+            ),
+        ]
+    );
+}
+
+#[test]
+fn explicit_files_are_checked_against_the_whole_project() {
+    let dir = setup_project();
+    write(dir.path(), "services/worker.json", r#"{ "owner": "web" }"#);
+    let (code, json) = check_json(dir.path(), &["services/worker.json"]);
+    assert_eq!(code, Some(0), "{json}");
+
+    std::fs::remove_file(dir.path().join("teams/web.json")).unwrap();
+    let (code, json) = check_json(dir.path(), &["services/worker.json"]);
+    assert_eq!(code, Some(1), "{json}");
+}
+
+#[test]
+fn invalid_pointer_is_a_config_error() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "jvl.json",
+        r#"{ "references": [{ "files": ["*.json"], "pointer": "owner", "target": { "files": ["*.json"], "pointer": "/id" } }] }"#,
+    );
+    let output = jvl()
+        .args(["check"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Invalid JSON pointer 'owner'"), "{stderr}");
+}
+
+#[test]
+fn targets_are_parsed_in_their_configured_dialect() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "jvl.json",
+        r#"{
+  "files": ["services/*.json"],
+  "parsers": [{ "files": ["teams/*.json5"], "parser": "json5" }],
+  "references": [
+    {
+      "files": ["services/*.json"],
+      "pointer": "/owner",
+      "target": { "files": ["teams/*.json5"], "pointer": "/id" }
+    }
+  ]
+}"#,
+    );
+    write(dir.path(), "teams/core.json5", "{ id: 'core' }");
+    write(dir.path(), "services/api.json", r#"{ "owner": "core" }"#);
+
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(0), "{json}");
+}
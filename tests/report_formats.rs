@@ -0,0 +1,122 @@
+mod common;
+
+use common::{fixture, jvl, with_human_settings};
+
+/// Run `jvl check` in the fixtures directory with the given format, checking
+/// `files` against `simple-schema.json`. Returns (stdout, exit code).
+fn check(format: &str, files: &[&str]) -> (String, i32) {
+    let output = jvl()
+        .args(["check", "--format", format, "--schema"])
+        .arg(fixture("simple-schema.json"))
+        .args(files)
+        .current_dir(fixture(""))
+        .output()
+        .expect("failed to run jvl");
+    let code = output.status.code().unwrap_or(-1);
+    (String::from_utf8_lossy(&output.stdout).into_owned(), code)
+}
+
+/// Redact the run time, which JUnit reports in seconds.
+fn with_report_settings(f: impl FnOnce()) {
+    let mut settings = insta::Settings::clone_current();
+    settings.add_filter(r#"time="[\d.]+""#, r#"time="[duration]""#);
+    settings.bind(|| with_human_settings(f));
+}
+
+#[test]
+fn junit_has_one_testcase_per_file() {
+    let (stdout, code) = check("junit", &["invalid-type.json", "valid.json"]);
+    assert_eq!(code, 1);
+    with_report_settings(|| {
+        insta::assert_snapshot!(stdout, @r#"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <testsuites name="jvl" tests="2" failures="1" errors="0" skipped="0" time="[duration]">
+          <testsuite name="jvl" tests="2" failures="1" errors="0" skipped="0" time="[duration]">
+            <testcase name="invalid-type.json" classname="invalid-type.json">
+              <failure message="schema(type): &quot;not-a-number&quot; is not of type &quot;number&quot;" type="schema(type)">1:29: error schema(type): &quot;not-a-number&quot; is not of type &quot;number&quot; (schema path: /properties/port/type)</failure>
+            </testcase>
+            <testcase name="valid.json" classname="valid.json"/>
+          </testsuite>
+        </testsuites>
+        "#);
+    });
+}
+
+#[test]
+fn junit_reports_skipped_files() {
+    let output = jvl()
+        .args(["check", "--format", "junit", "no-schema.json"])
+        .current_dir(fixture(""))
+        .output()
+        .expect("failed to run jvl");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(
+        stdout.contains(r#"<skipped message="no schema"/>"#),
+        "{stdout}"
+    );
+    assert!(stdout.contains(r#"skipped="1""#), "{stdout}");
+}
+
+#[test]
+fn checkstyle_carries_locations() {
+    let (stdout, code) = check("checkstyle", &["invalid-type.json", "valid.json"]);
+    assert_eq!(code, 1);
+    insta::assert_snapshot!(stdout, @r#"
+    <?xml version="1.0" encoding="UTF-8"?>
+    <checkstyle version="4.3">
+      <file name="invalid-type.json">
+        <error line="1" column="29" severity="error" message="&quot;not-a-number&quot; is not of type &quot;number&quot;" source="jvl.schema(type)"/>
+      </file>
+      <file name="valid.json"/>
+    </checkstyle>
+    "#);
+}
+
+#[test]
+fn github_emits_workflow_commands() {
+    let (stdout, code) = check("github", &["invalid-type.json", "valid.json"]);
+    assert_eq!(code, 1);
+    insta::assert_snapshot!(stdout, @r#"::error file=invalid-type.json,line=1,col=29,title=schema(type)::"not-a-number" is not of type "number""#);
+}
+
+#[test]
+fn github_paths_are_relative_to_the_working_directory() {
+    let (stdout, code) = check("github", &[&fixture("invalid-type.json")]);
+    assert_eq!(code, 1);
+    assert!(
+        stdout.starts_with("::error file=invalid-type.json,"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn no_files_still_produce_a_report() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("jvl.json"),
+        r#"{ "files": ["data/*.json"] }"#,
+    )
+    .unwrap();
+    let run = |format: &str| {
+        let output = jvl()
+            .args(["check", "--format", format])
+            .current_dir(dir.path())
+            .output()
+            .expect("failed to run jvl");
+        assert_eq!(output.status.code(), Some(0), "{format}");
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    let json: serde_json::Value = serde_json::from_str(&run("json")).unwrap();
+    assert_eq!(json["summary"]["checked_files"], 0);
+    let jsonl = run("jsonl");
+    let records: Vec<serde_json::Value> = jsonl
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(records.len(), 1, "{jsonl}");
+    assert_eq!(records[0]["type"], "summary");
+    assert!(run("junit").contains(r#"tests="0""#));
+    assert!(run("checkstyle").contains("<checkstyle"));
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use std::path::Path;
+
+use common::{check_json, write};
+
+fn setup(dir: &Path) {
+    write(
+        dir,
+        "jvl.json",
+        r#"{
+  "files": ["data/*.json"],
+  "resultCache": true,
+  "schemas": [{ "files": ["data/*.json"], "path": "schema.json" }]
+}"#,
+    );
+    write(
+        dir,
+        "schema.json",
+        r#"{ "properties": { "port": { "type": "integer" } } }"#,
+    );
+    write(dir, "data/a.json", r#"{ "port": 1 }"#);
+    write(dir, "data/b.json", r#"{ "port": 2 }"#);
+}
+
+#[test]
+fn unchanged_valid_files_are_not_validated_again() {
+    let dir = tempfile::tempdir().unwrap();
+    setup(dir.path());
+
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(0), "{json}");
+    assert_eq!(json["summary"]["cached_files"], 0);
+    assert!(dir.path().join(".jvl/cache/.gitignore").is_file());
+
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(0), "{json}");
+    assert_eq!(json["summary"]["checked_files"], 2);
+    assert_eq!(json["summary"]["cached_files"], 2);
+
+    // A changed file is validated again.
+    write(dir.path(), "data/b.json", r#"{ "port": "2" }"#);
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(json["summary"]["cached_files"], 1);
+
+    // Invalid results are never reused.
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(json["summary"]["cached_files"], 1);
+    assert_eq!(json["summary"]["invalid_files"], 1);
+}
+
+#[test]
+fn schema_and_setting_changes_invalidate_results() {
+    let dir = tempfile::tempdir().unwrap();
+    setup(dir.path());
+    check_json(dir.path(), &[]);
+
+    write(
+        dir.path(),
+        "schema.json",
+        r#"{ "properties": { "port": { "type": "string" } } }"#,
+    );
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(json["summary"]["cached_files"], 0);
+    assert_eq!(json["summary"]["invalid_files"], 2);
+
+    write(
+        dir.path(),
+        "schema.json",
+        r#"{ "properties": { "port": { "type": "integer" } } }"#,
+    );
+    check_json(dir.path(), &[]);
+    let (_, json) = check_json(dir.path(), &["--validate-formats"]);
+    assert_eq!(json["summary"]["cached_files"], 0);
+}
+
+#[test]
+fn the_cache_is_opt_in() {
+    let dir = tempfile::tempdir().unwrap();
+    setup(dir.path());
+    write(
+        dir.path(),
+        "jvl.json",
+        r#"{
+  "files": ["data/*.json"],
+  "schemas": [{ "files": ["data/*.json"], "path": "schema.json" }]
+}"#,
+    );
+
+    check_json(dir.path(), &[]);
+    let (_, json) = check_json(dir.path(), &[]);
+    assert_eq!(json["summary"]["cached_files"], 0);
+    assert!(!dir.path().join(".jvl").exists());
+
+    check_json(dir.path(), &["--result-cache"]);
+    let (_, json) = check_json(dir.path(), &["--result-cache"]);
+    assert_eq!(json["summary"]["cached_files"], 2);
+}
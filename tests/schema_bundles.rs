@@ -0,0 +1,145 @@
+mod common;
+
+use std::path::Path;
+
+use common::{jvl, write};
+use sha2::{Digest, Sha256};
+
+const ROOT_URL: &str = "https://schemas.invalid/root.json";
+const DEFS_URL: &str = "https://schemas.invalid/defs.json";
+
+/// Put `content` in the schema cache under `cache_home` as if `url` had just
+/// been fetched.
+fn seed(cache_home: &Path, url: &str, content: &str) {
+    let dir = cache_home.join("jvl/schemas");
+    std::fs::create_dir_all(&dir).unwrap();
+    let hash = format!("{:x}", Sha256::digest(url.as_bytes()));
+    std::fs::write(dir.join(format!("{hash}.json")), content).unwrap();
+    let meta = serde_json::json!({ "url": url, "fetched_at": jiff::Timestamp::now().to_string() });
+    std::fs::write(dir.join(format!("{hash}.meta")), meta.to_string()).unwrap();
+}
+
+fn unseed(cache_home: &Path, url: &str) {
+    let dir = cache_home.join("jvl/schemas");
+    let hash = format!("{:x}", Sha256::digest(url.as_bytes()));
+    std::fs::remove_file(dir.join(format!("{hash}.json"))).unwrap();
+    std::fs::remove_file(dir.join(format!("{hash}.meta"))).unwrap();
+}
+
+fn bundles(cache_home: &Path) -> usize {
+    std::fs::read_dir(cache_home.join("jvl/schemas/bundles")).map_or(0, |d| d.count())
+}
+
+fn check(dir: &Path, cache_home: &Path) -> (Option<i32>, serde_json::Value) {
+    let output = jvl()
+        .args(["check", "--format", "json"])
+        .env("XDG_CACHE_HOME", cache_home)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run jvl");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json = serde_json::from_str(&stdout).unwrap_or_else(|e| {
+        panic!(
+            "invalid JSON: {e}\nstdout: {stdout}\nstderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )
+    });
+    (output.status.code(), json)
+}
+
+#[test]
+fn referenced_documents_are_bundled_for_later_runs() {
+    let cache_home = tempfile::tempdir().unwrap();
+    seed(
+        cache_home.path(),
+        ROOT_URL,
+        &format!(r#"{{ "$ref": "{DEFS_URL}" }}"#),
+    );
+    seed(
+        cache_home.path(),
+        DEFS_URL,
+        r#"{ "type": "object", "required": ["name"] }"#,
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "good.json",
+        &format!(r#"{{ "$schema": "{ROOT_URL}", "name": "a" }}"#),
+    );
+    write(
+        dir.path(),
+        "bad.json",
+        &format!(r#"{{ "$schema": "{ROOT_URL}" }}"#),
+    );
+
+    let (code, json) = check(dir.path(), cache_home.path());
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(json["summary"]["invalid_files"], 1);
+    assert_eq!(bundles(cache_home.path()), 1);
+
+    // The referenced document now comes from the bundle: without it, the
+    // unresolvable host would fail compilation.
+    unseed(cache_home.path(), DEFS_URL);
+    let (code, json) = check(dir.path(), cache_home.path());
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(json["summary"]["invalid_files"], 1);
+    assert_eq!(
+        json["files"][0]["errors"][0]["code"], "schema(required)",
+        "{json}"
+    );
+
+    let output = jvl()
+        .args(["cache", "clear"])
+        .env("XDG_CACHE_HOME", cache_home.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(bundles(cache_home.path()), 0);
+}
+
+#[test]
+fn bundle_is_rebuilt_when_a_local_document_changes() {
+    let cache_home = tempfile::tempdir().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "jvl.json",
+        r#"{
+  "files": ["data.json"],
+  "urlRewrites": [{ "prefix": "https://schemas.invalid/", "path": "vendor" }]
+}"#,
+    );
+    write(
+        dir.path(),
+        "schema.json",
+        &format!(r#"{{ "$ref": "{DEFS_URL}" }}"#),
+    );
+    write(
+        dir.path(),
+        "vendor/defs.json",
+        r#"{ "type": "object", "required": ["name"] }"#,
+    );
+    write(
+        dir.path(),
+        "data.json",
+        r#"{ "$schema": "./schema.json", "name": "a" }"#,
+    );
+
+    let (code, json) = check(dir.path(), cache_home.path());
+    assert_eq!(code, Some(0), "{json}");
+    assert_eq!(bundles(cache_home.path()), 1);
+
+    write(
+        dir.path(),
+        "vendor/defs.json",
+        r#"{ "type": "object", "required": ["id"] }"#,
+    );
+    let (code, json) = check(dir.path(), cache_home.path());
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(
+        json["files"][0]["errors"][0]["code"], "schema(required)",
+        "{json}"
+    );
+    assert_eq!(bundles(cache_home.path()), 1);
+}
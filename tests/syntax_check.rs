@@ -0,0 +1,191 @@
+mod common;
+
+use std::path::Path;
+
+use common::{check_json, write};
+
+/// `(file name, error codes)` for each reported file.
+fn reported(json: &serde_json::Value) -> Vec<(String, Vec<String>)> {
+    let mut files: Vec<_> = json["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| {
+            let path = Path::new(f["path"].as_str().unwrap());
+            let codes = f["errors"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|e| e["code"].as_str().unwrap().to_string())
+                .collect();
+            (
+                path.file_name().unwrap().to_string_lossy().into_owned(),
+                codes,
+            )
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+#[test]
+fn files_without_schema_are_skipped_by_default() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "jvl.json", r#"{ "files": ["data/*.json"] }"#);
+    write(dir.path(), "data/a.json", r#"{ "a": 1 }"#);
+    write(dir.path(), "data/b.json", r#"{ "b": 2 }"#);
+
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(0), "{json}");
+    assert_eq!(json["summary"]["checked_files"], 0);
+    assert_eq!(json["summary"]["skipped_files"], 2);
+
+    let (code, json) = check_json(dir.path(), &["--syntax"]);
+    assert_eq!(code, Some(0), "{json}");
+    assert_eq!(json["summary"]["checked_files"], 2);
+    assert_eq!(json["summary"]["skipped_files"], 0);
+    assert_eq!(
+        reported(&json),
+        [
+            ("a.json".to_string(), vec![]),
+            ("b.json".to_string(), vec![])
+        ]
+    );
+}
+
+#[test]
+fn config_checks_syntax_of_all_files() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "jvl.json",
+        r#"{ "files": ["data/*.json"], "checkSyntax": "all" }"#,
+    );
+    write(dir.path(), "data/good.json", r#"{ "a": 1 }"#);
+    write(dir.path(), "data/broken.json", r#"{ "a": 1 "b": 2 }"#);
+
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(json["summary"]["checked_files"], 2);
+    assert_eq!(
+        reported(&json),
+        [
+            ("broken.json".to_string(), vec!["parse(syntax)".to_string()]),
+            ("good.json".to_string(), vec![]),
+        ]
+    );
+}
+
+#[test]
+fn parsers_choose_the_dialect_per_pattern() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "jvl.json",
+        r#"{
+  "files": ["*.json", "*.jsonc"],
+  "checkSyntax": "all",
+  "parsers": [
+    { "files": ["jvl.json", "*.jsonc"], "parser": "jsonc" },
+    { "files": ["*.json"], "parser": "json" }
+  ]
+}"#,
+    );
+    let source = "{\n  // build settings\n  \"a\": 1,\n}\n";
+    write(dir.path(), "package.json", source);
+    write(dir.path(), "tsconfig.jsonc", source);
+
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(
+        reported(&json),
+        [
+            ("jvl.json".to_string(), vec![]),
+            (
+                "package.json".to_string(),
+                vec!["parse(comment-not-allowed)".to_string()]
+            ),
+            ("tsconfig.jsonc".to_string(), vec![]),
+        ]
+    );
+    let error = &json["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|f| f["valid"] == false)
+        .unwrap()["errors"][0];
+    assert_eq!(error["location"]["line"], 2);
+}
+
+#[test]
+fn disallowed_features_have_their_own_codes() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "jvl.json",
+        r#"{
+  "files": ["data/*"],
+  "checkSyntax": "all",
+  "parsers": [{ "files": ["data/*.json"], "parser": "json" }]
+}"#,
+    );
+    write(dir.path(), "data/comment.json", "{ /* no */ }");
+    write(dir.path(), "data/comma.json", r#"{ "a": 1, }"#);
+    write(dir.path(), "data/quote.jsonc", r#"{ "a": 'b' }"#);
+    write(dir.path(), "data/key.jsonc", r#"{ a: 1 }"#);
+    write(dir.path(), "data/hex.jsonc", r#"{ "a": 0x1F }"#);
+    write(dir.path(), "data/plus.jsonc", r#"{ "a": +1 }"#);
+
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(
+        reported(&json),
+        [
+            ("comma.json", "parse(trailing-comma-not-allowed)"),
+            ("comment.json", "parse(comment-not-allowed)"),
+            ("hex.jsonc", "parse(hex-number-not-allowed)"),
+            ("key.jsonc", "parse(unquoted-key-not-allowed)"),
+            ("plus.jsonc", "parse(unary-plus-not-allowed)"),
+            ("quote.jsonc", "parse(single-quote-not-allowed)"),
+        ]
+        .map(|(file, code)| (file.to_string(), vec![code.to_string()]))
+    );
+}
+
+#[test]
+fn json5_files_are_validated_against_their_schema() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "jvl.json",
+        r#"{
+  "files": ["*.json5"],
+  "parsers": [{ "files": ["*.json5"], "parser": "json5" }],
+  "schemas": [{ "files": ["*.json5"], "path": "schema.json" }]
+}"#,
+    );
+    write(
+        dir.path(),
+        "schema.json",
+        r#"{ "properties": { "port": { "maximum": 65535 } } }"#,
+    );
+    write(
+        dir.path(),
+        "babel.json5",
+        "{\n  // JSON5\n  name: 'app',\n  port: 0x1F90,\n  retries: +3,\n}\n",
+    );
+
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(0), "{json}");
+
+    write(dir.path(), "big.json5", "{ port: 0x10000 }");
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(1), "{json}");
+    assert_eq!(
+        reported(&json),
+        [
+            ("babel.json5".to_string(), vec![]),
+            ("big.json5".to_string(), vec!["schema(maximum)".to_string()]),
+        ]
+    );
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use common::{check_json, write};
+
+const CONFIG: &str = r#"{
+  "files": ["services/*.json"],
+  "unique": [
+    { "files": ["services/*.json"], "pointer": "/id" },
+    { "files": ["services/*.json"], "pointer": "/routes/*/path" }
+  ]
+}"#;
+
+/// `(file name, code, line, column)` of each error.
+fn errors(json: &serde_json::Value) -> Vec<(String, String, u64, u64)> {
+    let mut errors: Vec<_> = json["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|f| {
+            let name = f["path"].as_str().unwrap().rsplit('/').next().unwrap();
+            f["errors"].as_array().unwrap().iter().map(move |e| {
+                (
+                    name.to_string(),
+                    e["code"].as_str().unwrap().to_string(),
+                    e["location"]["line"].as_u64().unwrap(),
+                    e["location"]["column"].as_u64().unwrap(),
+                )
+            })
+        })
+        .collect();
+    errors.sort();
+    errors
+}
+
+#[test]
+fn unique_values_pass() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "jvl.json", CONFIG);
+    write(dir.path(), "services/a.json", r#"{ "id": "a" }"#);
+    write(dir.path(), "services/b.json", r#"{ "id": "b" }"#);
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(0), "{json}");
+}
+
+#[test]
+fn every_duplicate_is_reported() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "jvl.json", CONFIG);
+    write(
+        dir.path(),
+        "services/a.json",
+        "{ \"id\": \"api\",\n  \"routes\": [{ \"path\": \"/a\" }, { \"path\": \"/a\" }] }",
+    );
+    write(dir.path(), "services/b.json", r#"{ "id": "api" }"#);
+    write(dir.path(), "services/c.json", r#"{ "id": "web" }"#);
+
+    let (code, json) = check_json(dir.path(), &[]);
+    assert_eq!(code, Some(1), "{json}");
+    let duplicate = |file: &str, line, column| {
+        (
+            file.to_string(),
+            "unique(duplicate)".to_string(),
+            line,
+            column,
+        )
+    };
+    assert_eq!(
+        errors(&json),
+        [
+            duplicate("a.json", 1, 9),
+            duplicate("a.json", 2, 24),
+            duplicate("a.json", 2, 42),
+            duplicate("b.json", 1, 9),
+        ]
+    );
+}
+
+#[test]
+fn related_locations_name_the_other_file() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "jvl.json", CONFIG);
+    write(dir.path(), "services/a.json", r#"{ "id": "api" }"#);
+    write(dir.path(), "services/b.json", r#"{ "id": "api" }"#);
+
+    // Only b.json is checked, but a.json is still read for its values.
+    let (code, json) = check_json(dir.path(), &["services/b.json"]);
+    assert_eq!(code, Some(1), "{json}");
+    let error = &json["files"][0]["errors"][0];
+    assert_eq!(error["message"], r#"duplicate value "api""#);
+    let related = error["related"].as_array().unwrap();
+    assert_eq!(related.len(), 1, "{related:?}");
+    assert!(
+        related[0]["path"]
+            .as_str()
+            .unwrap()
+            .ends_with("services/a.json"),
+        "{related:?}"
+    );
+    assert_eq!(related[0]["location"]["column"], 9);
+}
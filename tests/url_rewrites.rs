@@ -0,0 +1,124 @@
+mod common;
+
+use common::jvl;
+
+/// Helper: set up a temp project whose jvl.json rewrites a remote URL prefix
+/// to a local `vendor/` directory:
+///   project/
+///     jvl.json
+///     vendor/
+///       app.schema.json   (requires port: number)
+///     config.json         ($schema points at the remote URL)
+fn setup_project(config_json: &str) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+
+    std::fs::create_dir_all(dir.path().join("vendor")).unwrap();
+    std::fs::write(
+        dir.path().join("vendor/app.schema.json"),
+        r#"{
+  "type": "object",
+  "properties": { "port": { "type": "number" } },
+  "required": ["port"]
+}"#,
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("jvl.json"), config_json).unwrap();
+    std::fs::write(
+        dir.path().join("config.json"),
+        r#"{ "$schema": "https://schemas.invalid/app.schema.json", "port": "nope" }"#,
+    )
+    .unwrap();
+
+    dir
+}
+
+fn parse_json_output(output: &std::process::Output) -> serde_json::Value {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    serde_json::from_str(&stdout).unwrap_or_else(|e| {
+        panic!(
+            "invalid JSON: {e}\nstdout: {stdout}\nstderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )
+    })
+}
+
+/// A `path` rewrite serves the schema from the local directory without
+/// touching the network, and the reported schema is still the original URL.
+#[test]
+fn path_rewrite_loads_schema_from_local_directory() {
+    let dir = setup_project(
+        r#"{
+  "files": ["config.json"],
+  "urlRewrites": [
+    { "prefix": "https://schemas.invalid/", "path": "vendor" }
+  ]
+}"#,
+    );
+
+    let output = jvl()
+        .args(["check", "--format", "json", "--verbose", "config.json"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run jvl");
+
+    assert_eq!(output.status.code(), Some(1));
+    let json = parse_json_output(&output);
+    let file = &json["files"][0];
+    assert_eq!(file["schema"], "https://schemas.invalid/app.schema.json");
+    assert_eq!(file["errors"][0]["code"], "schema(type)");
+    assert!(
+        file.get("cache").is_none(),
+        "local mirror should bypass the disk cache\njson: {json:#}"
+    );
+}
+
+/// Rewritten paths that escape the mirror directory are rejected.
+#[test]
+fn path_rewrite_rejects_escaping_paths() {
+    let dir = setup_project(
+        r#"{
+  "files": ["config.json"],
+  "urlRewrites": [
+    { "prefix": "https://schemas.invalid/", "path": "vendor" }
+  ]
+}"#,
+    );
+    std::fs::write(
+        dir.path().join("config.json"),
+        r#"{ "$schema": "https://schemas.invalid/../jvl.json" }"#,
+    )
+    .unwrap();
+
+    let output = jvl()
+        .args(["check", "--format", "json", "config.json"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run jvl");
+
+    assert_eq!(output.status.code(), Some(2));
+    let json = parse_json_output(&output);
+    assert_eq!(json["files"][0]["errors"][0]["code"], "schema(load)");
+}
+
+/// Without a matching rewrite rule, the URL is fetched as-is (and fails here).
+#[test]
+fn unmatched_prefix_is_not_rewritten() {
+    let dir = setup_project(
+        r#"{
+  "files": ["config.json"],
+  "urlRewrites": [
+    { "prefix": "https://other.invalid/", "path": "vendor" }
+  ]
+}"#,
+    );
+
+    let output = jvl()
+        .args(["check", "--format", "json", "--no-cache", "config.json"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run jvl");
+
+    assert_eq!(output.status.code(), Some(2));
+    let json = parse_json_output(&output);
+    assert_eq!(json["files"][0]["errors"][0]["code"], "schema(load)");
+}